@@ -6,6 +6,7 @@ pub use clap::Parser;
 pub enum DoubanWeb {
     Deploy(Deploy),
     Update(Update),
+    Import(Import),
 }
 
 #[derive(clap::Args)]
@@ -21,4 +22,37 @@ pub struct Deploy {
 #[derive(clap::Args, Debug)]
 pub struct Update {}
 
-pub mod handle {}
+#[derive(clap::Args, Debug)]
+#[command(about = "Import movies and celebrities from the IMDb datasets", long_about = None)]
+pub struct Import {
+    /// Directory of title.basics, title.ratings, title.principals and name.basics, in .tsv or .tsv.gz
+    #[arg(long, short = 'd', value_name = "DATASET_DIR", default_value = ".")]
+    pub dir: std::path::PathBuf,
+
+    #[arg(
+        long,
+        env = "PG_DB",
+        default_value = "postgres://root:@localhost/s_douban_rs"
+    )]
+    pub pg_dsn: String,
+
+    /// Title types of title.basics to import
+    #[arg(long, value_delimiter = ',', default_value = "movie,tvMovie")]
+    pub title_types: Vec<String>,
+
+    /// Titles with fewer votes in title.ratings are skipped
+    #[arg(long, default_value_t = 1000)]
+    pub min_votes: i64,
+
+    /// Rows put in one statement, bounded by the 65535 bind parameters of Postgres
+    #[arg(
+        long,
+        default_value_t = 1000,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..=10000)
+    )]
+    pub batch_size: usize,
+}
+
+pub mod handle {
+    pub mod import;
+}
@@ -0,0 +1,524 @@
+//! Import of the public IMDb datasets, see <https://developer.imdb.com/non-commercial-datasets/>.
+//!
+//! Titles are read first, so that only the principals and names of imported titles are
+//! kept in memory. Movies and celebrities are upserted by their IMDb number without
+//! overwriting what has been put by the service: an existing movie only takes the runtime
+//! and the release year, and its title and name only if they are empty, an existing
+//! celebrity only fills the empty fields. Categories are only written for the movies
+//! inserted by the import, credits only for the movies which have none, so that importing
+//! the same datasets again changes nothing, and a rerun of an interrupted import credits
+//! the movies it has put.
+//!
+//! `title.ratings` is only read to skip titles with fewer votes than `--min-votes`, the
+//! ratings of IMDb are not imported as scores, which are made by the users here.
+//! Pinyin is not computed by the import, the movie service fills the missing pinyin of
+//! movies and celebrities when it starts.
+
+use crate::cli::Import;
+use chrono::NaiveDate;
+use diesel::dsl::sql;
+use diesel::pg::upsert::excluded;
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Nullable, Text};
+use flate2::read::GzDecoder;
+use migration::{
+    t_celebrities, t_movies, t_movies_actors, t_movies_categories, t_movies_directors,
+    t_movies_writers,
+};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+type ImportResult<T> = Result<T, Box<dyn Error>>;
+
+// null of the datasets
+const NULL: &str = "\\N";
+// unit (character), lengths of the varchar columns
+const MAX_TITLE_LEN: usize = 512;
+const MAX_NAME_LEN: usize = 128;
+const MAX_CATEGORY_LEN: usize = 128;
+// bind parameters of a statement of Postgres
+const MAX_BIND_PARAMS: usize = 65535;
+
+struct Movie {
+    imdb: String,
+    title: String,
+    name: String,
+    time_length: i32,
+    released_date: NaiveDate,
+    categories: Vec<String>,
+}
+
+struct Celebrity {
+    imdb: String,
+    name: String,
+    gender: &'static str,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Director,
+    Writer,
+    Actor,
+}
+
+/// Rows read and kept from a dataset, reported every batch.
+struct Progress {
+    dataset: &'static str,
+    read: usize,
+    kept: usize,
+    start: Instant,
+}
+
+impl Progress {
+    fn new(dataset: &'static str) -> Self {
+        Self {
+            dataset,
+            read: 0,
+            kept: 0,
+            start: Instant::now(),
+        }
+    }
+
+    fn report(&self) {
+        eprintln!(
+            "{}: {} rows read, {} kept, {:.1}s",
+            self.dataset,
+            self.read,
+            self.kept,
+            self.start.elapsed().as_secs_f64()
+        );
+    }
+}
+
+#[inline]
+fn truncate(text: &str, len: usize) -> String {
+    text.chars().take(len).collect()
+}
+
+#[inline]
+fn nullable(field: &str) -> Option<&str> {
+    (field != NULL && !field.is_empty()).then_some(field)
+}
+
+/// The dataset in the directory, the gzipped one as downloaded or the decompressed one.
+fn open(dir: &Path, dataset: &str) -> ImportResult<Box<dyn BufRead>> {
+    let gzipped: PathBuf = dir.join(format!("{}.tsv.gz", dataset));
+    if gzipped.exists() {
+        let decoder: Box<dyn Read> = Box::new(GzDecoder::new(File::open(gzipped)?));
+        return Ok(Box::new(BufReader::new(decoder)));
+    }
+    let plain = dir.join(format!("{}.tsv", dataset));
+    if plain.exists() {
+        return Ok(Box::new(BufReader::new(File::open(plain)?)));
+    }
+    Err(format!("{} is not found in {}", dataset, dir.display()).into())
+}
+
+/// Call `f` with the fields of every row after the header. Fields are not quoted in the
+/// datasets, a tab never appears in a field.
+fn for_each_row(
+    dir: &Path,
+    dataset: &'static str,
+    batch_size: usize,
+    mut f: impl FnMut(&[&str]) -> ImportResult<bool>,
+) -> ImportResult<()> {
+    let mut progress = Progress::new(dataset);
+    for line in open(dir, dataset)?.lines().skip(1) {
+        let line = line?;
+        let fields: Vec<&str> = line.split('\t').collect();
+        progress.read += 1;
+        if f(&fields)? {
+            progress.kept += 1;
+        }
+        if progress.read % (batch_size * 100) == 0 {
+            progress.report();
+        }
+    }
+    progress.report();
+    Ok(())
+}
+
+pub fn run(args: &Import) -> ImportResult<()> {
+    let batch_size = args.batch_size;
+    let conn = &mut PgConnection::establish(&args.pg_dsn)?;
+
+    // tconst -> numVotes
+    let mut votes: HashMap<String, i64> = HashMap::new();
+    for_each_row(&args.dir, "title.ratings", batch_size, |fields| {
+        let [tconst, _, num_votes] = fields else {
+            return Ok(false);
+        };
+        let num_votes: i64 = num_votes.parse().unwrap_or_default();
+        if num_votes < args.min_votes {
+            return Ok(false);
+        }
+        votes.insert(tconst.to_string(), num_votes);
+        Ok(true)
+    })?;
+
+    // tconst -> id of the movies to be credited
+    let mut movies: HashMap<String, i64> = HashMap::new();
+    let mut batch: Vec<Movie> = Vec::with_capacity(batch_size);
+    for_each_row(&args.dir, "title.basics", batch_size, |fields| {
+        let [tconst, title_type, primary, original, is_adult, start_year, _, runtime, genres] =
+            fields
+        else {
+            return Ok(false);
+        };
+        if *is_adult == "1"
+            || !votes.contains_key(*tconst)
+            || !args.title_types.iter().any(|t| t == title_type)
+        {
+            return Ok(false);
+        }
+        // both are required by movies
+        let released_date = nullable(start_year)
+            .and_then(|year| year.parse().ok())
+            .and_then(|year| NaiveDate::from_ymd_opt(year, 1, 1));
+        let time_length = nullable(runtime).and_then(|minutes| minutes.parse().ok());
+        let (Some(released_date), Some(time_length)) = (released_date, time_length) else {
+            return Ok(false);
+        };
+        batch.push(Movie {
+            imdb: tconst.to_string(),
+            title: truncate(primary, MAX_TITLE_LEN),
+            name: truncate(original, MAX_TITLE_LEN),
+            time_length,
+            released_date,
+            categories: nullable(genres)
+                .map(|genres| {
+                    genres
+                        .split(',')
+                        .map(|genre| truncate(&genre.to_lowercase(), MAX_CATEGORY_LEN))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        });
+        if batch.len() >= batch_size {
+            movies.extend(put_movies(&batch, conn)?);
+            batch.clear();
+        }
+        Ok(true)
+    })?;
+    movies.extend(put_movies(&batch, conn)?);
+    drop(votes);
+
+    // movie id -> credits in the order of the dataset
+    let mut credits: HashMap<i64, Vec<(Role, String)>> = HashMap::new();
+    let mut credited: HashSet<String> = HashSet::new();
+    for_each_row(&args.dir, "title.principals", batch_size, |fields| {
+        let [tconst, _, nconst, category, ..] = fields else {
+            return Ok(false);
+        };
+        let Some(&movie_id) = movies.get(*tconst) else {
+            return Ok(false);
+        };
+        let role = match *category {
+            "director" => Role::Director,
+            "writer" => Role::Writer,
+            "actor" | "actress" | "self" => Role::Actor,
+            _ => return Ok(false),
+        };
+        let credit = (role, nconst.to_string());
+        let movie_credits = credits.entry(movie_id).or_default();
+        if !movie_credits.contains(&credit) {
+            movie_credits.push(credit);
+        }
+        credited.insert(nconst.to_string());
+        Ok(true)
+    })?;
+    drop(movies);
+
+    // nconst -> celebrity id
+    let mut celebrities: HashMap<String, i64> = HashMap::new();
+    let mut batch: Vec<Celebrity> = Vec::with_capacity(batch_size);
+    for_each_row(&args.dir, "name.basics", batch_size, |fields| {
+        let [nconst, name, _, _, professions, ..] = fields else {
+            return Ok(false);
+        };
+        if !credited.contains(*nconst) {
+            return Ok(false);
+        }
+        let professions = nullable(professions).unwrap_or_default();
+        let gender = if professions.contains("actress") {
+            "female"
+        } else if professions.contains("actor") {
+            "male"
+        } else {
+            "unknown"
+        };
+        batch.push(Celebrity {
+            imdb: nconst.to_string(),
+            name: truncate(name, MAX_NAME_LEN),
+            gender,
+        });
+        if batch.len() >= batch_size {
+            celebrities.extend(put_celebrities(&batch, conn)?);
+            batch.clear();
+        }
+        Ok(true)
+    })?;
+    celebrities.extend(put_celebrities(&batch, conn)?);
+    drop(credited);
+
+    let mut progress = Progress::new("credits");
+    let credits: Vec<(i64, Vec<(Role, i64)>)> = credits
+        .into_iter()
+        .map(|(movie_id, movie_credits)| {
+            let movie_credits = movie_credits
+                .into_iter()
+                // a name missing from name.basics is not credited
+                .filter_map(|(role, nconst)| celebrities.get(&nconst).map(|cid| (role, *cid)))
+                .collect();
+            (movie_id, movie_credits)
+        })
+        .collect();
+    for batch in credits.chunks(batch_size) {
+        progress.kept += put_credits(batch, conn)?;
+        progress.read += batch.len();
+    }
+    progress.report();
+    Ok(())
+}
+
+/// Upsert the movies and insert the categories of the inserted ones, returns `(imdb, id)`
+/// of the movies to be credited, which are the inserted ones and the existing ones without
+/// any credit.
+fn put_movies(batch: &[Movie], conn: &mut PgConnection) -> ImportResult<Vec<(String, i64)>> {
+    use migration::t_movies::dsl::*;
+
+    if batch.is_empty() {
+        return Ok(vec![]);
+    }
+    let uncredited = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        // language and plot are not in the datasets, they are kept as they are
+        let put: Vec<(String, i64, bool)> = diesel::insert_into(t_movies)
+            .values(
+                batch
+                    .iter()
+                    .map(|movie| {
+                        (
+                            title.eq(&movie.title),
+                            name.eq(&movie.name),
+                            language.eq(""),
+                            time_length.eq(movie.time_length),
+                            released_date.eq(movie.released_date),
+                            imdb.eq(&movie.imdb),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .on_conflict(imdb)
+            .do_update()
+            .set((
+                title.eq(sql::<Text>(
+                    "coalesce(nullif(t_movies.title, ''), excluded.title)",
+                )),
+                name.eq(sql::<Text>(
+                    "coalesce(nullif(t_movies.name, ''), excluded.name)",
+                )),
+                time_length.eq(excluded(time_length)),
+                released_date.eq(excluded(released_date)),
+            ))
+            // xmax of a row is 0 unless it is updated
+            .returning((imdb, id, sql::<Bool>("xmax = 0")))
+            .get_results(conn)?;
+
+        let inserted: HashMap<&str, i64> = put
+            .iter()
+            .filter(|(_, _, inserted)| *inserted)
+            .map(|(movie_imdb, movie_id, _)| (movie_imdb.as_str(), *movie_id))
+            .collect();
+        let categories: Vec<(i64, &String)> = batch
+            .iter()
+            .filter_map(|movie| Some((*inserted.get(movie.imdb.as_str())?, movie)))
+            .flat_map(|(movie_id, movie)| {
+                movie
+                    .categories
+                    .iter()
+                    .map(move |category| (movie_id, category))
+            })
+            .collect();
+        for chunk in categories.chunks(MAX_BIND_PARAMS / 2) {
+            diesel::insert_into(t_movies_categories::table)
+                .values(
+                    chunk
+                        .iter()
+                        .map(|(movie_id, category)| {
+                            (
+                                t_movies_categories::mid.eq(movie_id),
+                                t_movies_categories::category.eq(*category),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)?;
+        }
+
+        // an existing movie is credited if it has no credit, i.e. it is put by an import
+        // which has been interrupted before crediting it
+        let existing: Vec<i64> = put
+            .iter()
+            .filter(|(_, _, inserted)| !*inserted)
+            .map(|(_, movie_id, _)| *movie_id)
+            .collect();
+        let mut credited: HashSet<i64> = HashSet::new();
+        if !existing.is_empty() {
+            credited.extend(
+                t_movies_directors::table
+                    .select(t_movies_directors::mid)
+                    .filter(t_movies_directors::mid.eq_any(&existing))
+                    .distinct()
+                    .load::<i64>(conn)?,
+            );
+            credited.extend(
+                t_movies_writers::table
+                    .select(t_movies_writers::mid)
+                    .filter(t_movies_writers::mid.eq_any(&existing))
+                    .distinct()
+                    .load::<i64>(conn)?,
+            );
+            credited.extend(
+                t_movies_actors::table
+                    .select(t_movies_actors::mid)
+                    .filter(t_movies_actors::mid.eq_any(&existing))
+                    .distinct()
+                    .load::<i64>(conn)?,
+            );
+        }
+        Ok(put
+            .into_iter()
+            .filter(|(_, movie_id, _)| !credited.contains(movie_id))
+            .map(|(movie_imdb, movie_id, _)| (movie_imdb, movie_id))
+            .collect::<Vec<_>>())
+    })?;
+    Ok(uncredited)
+}
+
+/// Upsert the celebrities, returns `(imdb, id)` of the celebrities.
+fn put_celebrities(
+    batch: &[Celebrity],
+    conn: &mut PgConnection,
+) -> ImportResult<Vec<(String, i64)>> {
+    use migration::t_celebrities::dsl::*;
+
+    if batch.is_empty() {
+        return Ok(vec![]);
+    }
+    // info and the pictures are not in the datasets, they are kept as they are, and so
+    // are the fields of an existing celebrity unless they are empty
+    let put = diesel::insert_into(t_celebrities)
+        .values(
+            batch
+                .iter()
+                .map(|celebrity| {
+                    (
+                        name.eq(&celebrity.name),
+                        name_en.eq(&celebrity.name),
+                        gender.eq(celebrity.gender),
+                        imdb.eq(&celebrity.imdb),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+        .on_conflict(imdb)
+        .do_update()
+        .set((
+            name.eq(sql::<Text>(
+                "coalesce(nullif(t_celebrities.name, ''), excluded.name)",
+            )),
+            name_en.eq(sql::<Nullable<Text>>(
+                "coalesce(nullif(t_celebrities.name_en, ''), excluded.name_en)",
+            )),
+            gender.eq(sql::<Text>(
+                "CASE WHEN t_celebrities.gender IN ('', 'unknown') THEN excluded.gender \
+                 ELSE t_celebrities.gender END",
+            )),
+        ))
+        .returning((imdb, id))
+        .get_results(conn)?;
+    Ok(put)
+}
+
+/// Replace the directors, writers and actors of the movies to be credited, returns the count
+/// of credits.
+fn put_credits(batch: &[(i64, Vec<(Role, i64)>)], conn: &mut PgConnection) -> ImportResult<usize> {
+    let rows = |wanted: Role| -> Vec<(i64, i64)> {
+        batch
+            .iter()
+            .flat_map(|(movie_id, movie_credits)| {
+                movie_credits
+                    .iter()
+                    .filter(move |(role, _)| *role == wanted)
+                    .map(move |(_, cid)| (*movie_id, *cid))
+            })
+            .collect()
+    };
+    let movie_ids: Vec<i64> = batch.iter().map(|(movie_id, _)| *movie_id).collect();
+    let (directors, writers, actors) =
+        (rows(Role::Director), rows(Role::Writer), rows(Role::Actor));
+
+    let put = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        diesel::delete(
+            t_movies_directors::table.filter(t_movies_directors::mid.eq_any(&movie_ids)),
+        )
+        .execute(conn)?;
+        for chunk in directors.chunks(MAX_BIND_PARAMS / 2) {
+            diesel::insert_into(t_movies_directors::table)
+                .values(
+                    chunk
+                        .iter()
+                        .map(|(movie_id, cid)| {
+                            (
+                                t_movies_directors::mid.eq(movie_id),
+                                t_movies_directors::cid.eq(cid),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)?;
+        }
+
+        diesel::delete(t_movies_writers::table.filter(t_movies_writers::mid.eq_any(&movie_ids)))
+            .execute(conn)?;
+        for chunk in writers.chunks(MAX_BIND_PARAMS / 2) {
+            diesel::insert_into(t_movies_writers::table)
+                .values(
+                    chunk
+                        .iter()
+                        .map(|(movie_id, cid)| {
+                            (
+                                t_movies_writers::mid.eq(movie_id),
+                                t_movies_writers::cid.eq(cid),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)?;
+        }
+
+        diesel::delete(t_movies_actors::table.filter(t_movies_actors::mid.eq_any(&movie_ids)))
+            .execute(conn)?;
+        for chunk in actors.chunks(MAX_BIND_PARAMS / 2) {
+            diesel::insert_into(t_movies_actors::table)
+                .values(
+                    chunk
+                        .iter()
+                        .map(|(movie_id, cid)| {
+                            (
+                                t_movies_actors::mid.eq(movie_id),
+                                t_movies_actors::cid.eq(cid),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)?;
+        }
+        Ok(directors.len() + writers.len() + actors.len())
+    })?;
+    Ok(put)
+}
@@ -10,5 +10,11 @@ fn main() {
         DoubanWeb::Update(args) => {
             println!("{:?}", args)
         }
+        DoubanWeb::Import(args) => {
+            if let Err(e) = cli::handle::import::run(&args) {
+                eprintln!("import failed: {}", e);
+                std::process::exit(1);
+            }
+        }
     }
 }
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+drop table t_users_recovery_codes;
+drop table t_users_totp;
@@ -0,0 +1,55 @@
+-- Your SQL goes here
+
+create table t_users_totp
+(
+    id             bigserial
+        constraint t_users_totp_pk
+            primary key,
+    uid            bigint                  not null
+        constraint t_users_totp_t_users_id_fk
+            references t_users,
+    secret         varchar(256)            not null,
+    enabled        boolean   default false not null,
+    last_step      bigint    default 0     not null,
+    created_at     timestamp default now() not null,
+    updated_at     timestamp default now() not null
+);
+
+SELECT diesel_manage_updated_at('t_users_totp');
+
+comment on table t_users_totp is 'TOTP(RFC 6238) second factor of users';
+
+comment on column t_users_totp.uid is 'fk of users';
+
+comment on column t_users_totp.secret is 'encrypted TOTP secret, base64(nonce || ciphertext)';
+
+comment on column t_users_totp.enabled is 'whether the user has confirmed the TOTP enrollment';
+
+comment on column t_users_totp.last_step is 'last accepted time step, used to reject replayed codes';
+
+create unique index t_users_totp_uid_uindex
+    on t_users_totp (uid);
+
+create table t_users_recovery_codes
+(
+    id          bigserial
+        constraint t_users_recovery_codes_pk
+            primary key,
+    uid         bigint                  not null
+        constraint t_users_recovery_codes_t_users_id_fk
+            references t_users,
+    hashed_code varchar(64)             not null,
+    used_at     timestamp default null,
+    created_at  timestamp default now() not null
+);
+
+comment on table t_users_recovery_codes is 'one-time recovery codes used when TOTP device is lost';
+
+comment on column t_users_recovery_codes.uid is 'fk of users';
+
+comment on column t_users_recovery_codes.hashed_code is 'recovery code which is hashed';
+
+comment on column t_users_recovery_codes.used_at is 'the timestamp when this code was consumed';
+
+create index t_users_recovery_codes_uid_index
+    on t_users_recovery_codes (uid);
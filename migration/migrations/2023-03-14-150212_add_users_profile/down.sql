@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS forbid_rename ON t_users;
+DROP FUNCTION IF EXISTS t_users_forbid_rename();
+
+alter table t_users
+    drop column avatar_url,
+    drop column bio,
+    drop column gender,
+    drop column location;
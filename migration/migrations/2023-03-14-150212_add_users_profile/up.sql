@@ -0,0 +1,29 @@
+-- Your SQL goes here
+
+alter table t_users
+    add avatar_url varchar(512) default null,
+    add bio        varchar(512) default null,
+    add gender     varchar(16)  default null,
+    add location   varchar(128) default null;
+
+comment on column t_users.avatar_url is 'avatar url of user';
+
+comment on column t_users.bio is 'self introduction of user';
+
+comment on column t_users.gender is 'gender of user, one of male, female, other';
+
+comment on column t_users.location is 'location of user';
+
+-- username is used to login and is referred by others, it cannot be changed once registered
+CREATE OR REPLACE FUNCTION t_users_forbid_rename() RETURNS trigger AS
+$$
+BEGIN
+    IF NEW.username IS DISTINCT FROM OLD.username THEN
+        RAISE EXCEPTION 'username of user(%) cannot be changed', OLD.id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER forbid_rename BEFORE UPDATE ON t_users
+    FOR EACH ROW EXECUTE PROCEDURE t_users_forbid_rename();
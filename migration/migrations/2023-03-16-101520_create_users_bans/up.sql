@@ -0,0 +1,33 @@
+-- Your SQL goes here
+
+create table t_users_bans
+(
+    id         bigserial
+        constraint t_users_bans_pk
+            primary key,
+    uid        bigint                  not null
+        constraint t_users_bans_t_users_id_fk
+            references t_users,
+    reason     varchar(512)            not null,
+    banned_by  bigint                  not null
+        constraint t_users_bans_t_users_id_fk_2
+            references t_users,
+    expires_at timestamp default null,
+    created_at timestamp default now() not null,
+    updated_at timestamp default now() not null
+);
+
+SELECT diesel_manage_updated_at('t_users_bans');
+
+comment on table t_users_bans is 'users banned by admins, a user has at most one ban';
+
+comment on column t_users_bans.uid is 'fk of users, who is banned';
+
+comment on column t_users_bans.reason is 'why the user is banned, visible to the user';
+
+comment on column t_users_bans.banned_by is 'fk of users, the admin who banned the user';
+
+comment on column t_users_bans.expires_at is 'the ban is lifted after this timestamp, null means forever';
+
+create unique index t_users_bans_uid_uindex
+    on t_users_bans (uid);
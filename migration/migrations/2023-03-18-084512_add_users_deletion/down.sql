@@ -0,0 +1,16 @@
+-- This file should undo anything in `up.sql`
+CREATE OR REPLACE FUNCTION t_users_forbid_rename() RETURNS trigger AS
+$$
+BEGIN
+    IF NEW.username IS DISTINCT FROM OLD.username THEN
+        RAISE EXCEPTION 'username of user(%) cannot be changed', OLD.id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+drop index t_users_deleted_at_index;
+
+alter table t_users
+    drop column deleted_at,
+    drop column purged_at;
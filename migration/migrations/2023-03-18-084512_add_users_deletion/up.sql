@@ -0,0 +1,25 @@
+-- Your SQL goes here
+
+alter table t_users
+    add deleted_at timestamp default null,
+    add purged_at  timestamp default null;
+
+comment on column t_users.deleted_at is 'the timestamp when the user deleted the account, null if the account is alive';
+
+comment on column t_users.purged_at is 'the timestamp when the personal data of a deleted account was anonymised';
+
+create index t_users_deleted_at_index
+    on t_users (deleted_at)
+    where deleted_at is not null and purged_at is null;
+
+-- a deleted account is renamed when it is anonymised, which is the only exception
+CREATE OR REPLACE FUNCTION t_users_forbid_rename() RETURNS trigger AS
+$$
+BEGIN
+    IF NEW.username IS DISTINCT FROM OLD.username
+        AND NOT (OLD.deleted_at IS NOT NULL AND OLD.purged_at IS NULL AND NEW.purged_at IS NOT NULL) THEN
+        RAISE EXCEPTION 'username of user(%) cannot be changed', OLD.id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
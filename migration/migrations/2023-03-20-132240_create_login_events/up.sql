@@ -0,0 +1,34 @@
+-- Your SQL goes here
+
+create table t_login_events
+(
+    id              bigserial
+        constraint t_login_events_pk
+            primary key,
+    uid             bigint                  not null
+        constraint t_login_events_t_users_id_fk
+            references t_users,
+    identifier_kind varchar(16)             not null,
+    ip              varchar(64)  default null,
+    user_agent      varchar(512) default null,
+    ua_fingerprint  varchar(64)  default null,
+    succeeded       boolean                 not null,
+    created_at      timestamp default now() not null
+);
+
+comment on table t_login_events is 'login attempts of users, attempts with an unknown identifier are not recorded';
+
+comment on column t_login_events.uid is 'fk of users';
+
+comment on column t_login_events.identifier_kind is 'how the identifier was resolved, one of id, email, phone, username';
+
+comment on column t_login_events.ip is 'ip of the client';
+
+comment on column t_login_events.user_agent is 'user agent of the client';
+
+comment on column t_login_events.ua_fingerprint is 'hash of the user agent, used to detect a new device';
+
+comment on column t_login_events.succeeded is 'whether the password was correct';
+
+create index t_login_events_uid_created_at_index
+    on t_login_events (uid, created_at desc);
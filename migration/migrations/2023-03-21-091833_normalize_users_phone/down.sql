@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+update t_users
+set phone = substr(phone, 4)
+where phone ~ '^\+861[0-9]{10}$';
+
+comment on column t_users.phone is 'phone number of user';
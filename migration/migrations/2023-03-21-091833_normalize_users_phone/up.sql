@@ -0,0 +1,22 @@
+-- Your SQL goes here
+
+-- phones are stored in E.164 from now on, only mainland China numbers were accepted before,
+-- so existing numbers are normalised as numbers of region CN
+
+update t_users
+set phone = regexp_replace(phone, '[^0-9+]', '', 'g')
+where phone is not null;
+
+update t_users
+set phone = '+' || substr(phone, 3)
+where phone like '00%';
+
+update t_users
+set phone = '+' || phone
+where phone ~ '^861[0-9]{10}$';
+
+update t_users
+set phone = '+86' || phone
+where phone ~ '^1[0-9]{10}$';
+
+comment on column t_users.phone is 'phone number of user in E.164';
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+drop table t_user_blocks;
+drop table t_user_follows;
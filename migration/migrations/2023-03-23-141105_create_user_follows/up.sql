@@ -0,0 +1,57 @@
+-- Your SQL goes here
+
+create table t_user_follows
+(
+    id         bigserial
+        constraint t_user_follows_pk
+            primary key,
+    follower   bigint                  not null
+        constraint t_user_follows_t_users_id_fk
+            references t_users,
+    followee   bigint                  not null
+        constraint t_user_follows_t_users_id_fk_2
+            references t_users,
+    created_at timestamp default now() not null,
+    constraint t_user_follows_not_self
+        check (follower <> followee)
+);
+
+comment on table t_user_follows is 'follow relationships between users';
+
+comment on column t_user_follows.follower is 'fk of users, who follows';
+
+comment on column t_user_follows.followee is 'fk of users, who is followed';
+
+create unique index t_user_follows_follower_followee_uindex
+    on t_user_follows (follower, followee);
+
+create index t_user_follows_followee_index
+    on t_user_follows (followee);
+
+create table t_user_blocks
+(
+    id         bigserial
+        constraint t_user_blocks_pk
+            primary key,
+    blocker    bigint                  not null
+        constraint t_user_blocks_t_users_id_fk
+            references t_users,
+    blocked    bigint                  not null
+        constraint t_user_blocks_t_users_id_fk_2
+            references t_users,
+    created_at timestamp default now() not null,
+    constraint t_user_blocks_not_self
+        check (blocker <> blocked)
+);
+
+comment on table t_user_blocks is 'users blocked by users, follows are forbidden in both directions';
+
+comment on column t_user_blocks.blocker is 'fk of users, who blocks';
+
+comment on column t_user_blocks.blocked is 'fk of users, who is blocked';
+
+create unique index t_user_blocks_blocker_blocked_uindex
+    on t_user_blocks (blocker, blocked);
+
+create index t_user_blocks_blocked_index
+    on t_user_blocks (blocked);
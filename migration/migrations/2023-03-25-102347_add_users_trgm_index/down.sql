@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+drop index t_users_nickname_trgm_index;
+drop index t_users_username_trgm_index;
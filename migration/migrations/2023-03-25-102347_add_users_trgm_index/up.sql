@@ -0,0 +1,14 @@
+-- Your SQL goes here
+
+create extension if not exists pg_trgm;
+
+-- trigram indexes support case insensitive prefix and substring search, i.e. ilike '%keyword%'
+create index t_users_username_trgm_index
+    on t_users using gin (username gin_trgm_ops);
+
+comment on index t_users_username_trgm_index is 'trigram index used to search users by username';
+
+create index t_users_nickname_trgm_index
+    on t_users using gin (nickname gin_trgm_ops);
+
+comment on index t_users_nickname_trgm_index is 'trigram index used to search users by nickname';
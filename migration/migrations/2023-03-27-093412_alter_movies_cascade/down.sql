@@ -0,0 +1,39 @@
+-- This file should undo anything in `up.sql`
+
+alter table t_movies_directors
+    drop constraint t_movies_directors_t_movies_id_fk,
+    add constraint t_movies_directors_t_movies_id_fk
+        foreign key (mid) references t_movies;
+
+alter table t_movies_writers
+    drop constraint t_movies_writers_t_movies_id_fk,
+    add constraint t_movies_writers_t_movies_id_fk
+        foreign key (mid) references t_movies;
+
+alter table t_movies_actors
+    drop constraint t_movies_actors_t_movies_id_fk,
+    add constraint t_movies_actors_t_movies_id_fk
+        foreign key (mid) references t_movies;
+
+alter table t_movies_categories
+    drop constraint t_movies_categories_t_movies_id_fk,
+    add constraint t_movies_categories_t_movies_id_fk
+        foreign key (mid) references t_movies;
+
+alter table t_movies_country
+    drop constraint t_movies_countries_t_movies_id_fk,
+    add constraint t_movies_countries_t_movies_id_fk
+        foreign key (mid) references t_movies;
+
+alter table t_movies_scores
+    drop constraint t_movies_scores_t_movies_id_fk,
+    add constraint t_movies_scores_t_movies_id_fk
+        foreign key (mid) references t_movies;
+
+alter table t_movies
+    drop constraint t_movies_imdb_uk;
+
+create index t_movies_imdb_index
+    on t_movies (imdb);
+
+comment on index t_movies_imdb_index is 'movie imdb index';
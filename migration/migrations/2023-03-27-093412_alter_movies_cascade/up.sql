@@ -0,0 +1,45 @@
+-- Your SQL goes here
+
+drop index t_movies_imdb_index;
+
+alter table t_movies
+    add constraint t_movies_imdb_uk
+        unique (imdb);
+
+comment on constraint t_movies_imdb_uk on t_movies is 'a movie is put by its IMDb number';
+
+alter table t_movies_directors
+    drop constraint t_movies_directors_t_movies_id_fk,
+    add constraint t_movies_directors_t_movies_id_fk
+        foreign key (mid) references t_movies
+            on delete cascade;
+
+alter table t_movies_writers
+    drop constraint t_movies_writers_t_movies_id_fk,
+    add constraint t_movies_writers_t_movies_id_fk
+        foreign key (mid) references t_movies
+            on delete cascade;
+
+alter table t_movies_actors
+    drop constraint t_movies_actors_t_movies_id_fk,
+    add constraint t_movies_actors_t_movies_id_fk
+        foreign key (mid) references t_movies
+            on delete cascade;
+
+alter table t_movies_categories
+    drop constraint t_movies_categories_t_movies_id_fk,
+    add constraint t_movies_categories_t_movies_id_fk
+        foreign key (mid) references t_movies
+            on delete cascade;
+
+alter table t_movies_country
+    drop constraint t_movies_countries_t_movies_id_fk,
+    add constraint t_movies_countries_t_movies_id_fk
+        foreign key (mid) references t_movies
+            on delete cascade;
+
+alter table t_movies_scores
+    drop constraint t_movies_scores_t_movies_id_fk,
+    add constraint t_movies_scores_t_movies_id_fk
+        foreign key (mid) references t_movies
+            on delete cascade;
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+
+alter table t_celebrities
+    drop constraint t_celebrities_imdb_uk;
+
+create index t_celebrities_imdb_index
+    on t_celebrities (imdb);
+
+comment on index t_celebrities_imdb_index is 'celebrity imdb index';
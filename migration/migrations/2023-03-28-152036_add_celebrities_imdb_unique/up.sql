@@ -0,0 +1,9 @@
+-- Your SQL goes here
+
+drop index t_celebrities_imdb_index;
+
+alter table t_celebrities
+    add constraint t_celebrities_imdb_uk
+        unique (imdb);
+
+comment on constraint t_celebrities_imdb_uk on t_celebrities is 'a celebrity is put by its IMDb number';
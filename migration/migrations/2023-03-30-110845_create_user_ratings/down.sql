@@ -0,0 +1,36 @@
+-- This file should undo anything in `up.sql`
+
+drop trigger set_score_avg on t_movies_scores;
+
+CREATE OR REPLACE FUNCTION diesel_manage_set_score_avg(_tbl regclass) RETURNS VOID AS
+$$
+BEGIN
+    EXECUTE format('CREATE TRIGGER set_score_avg BEFORE UPDATE ON %s
+                    FOR EACH ROW EXECUTE PROCEDURE diesel_set_score_avg()', _tbl);
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION diesel_set_score_avg() RETURNS trigger AS
+$$
+BEGIN
+    IF (
+        NEW IS DISTINCT FROM OLD AND
+        NEW.score_avg IS NOT DISTINCT FROM OLD.score_avg
+    ) THEN
+        DECLARE
+            total FLOAT := cast(NEW.cnt_1 + NEW.cnt_2 + NEW.cnt_3 + NEW.cnt_4 + NEW.cnt_5 AS FLOAT) + 1e-5;
+        BEGIN
+            NEW.score_avg := round(cast((2 * (NEW.cnt_1 / total) + 4 * (NEW.cnt_2 / total) + 6 * (NEW.cnt_3 / total) +
+                                         8 * (NEW.cnt_4 / total) + 10 * (NEW.cnt_5 / total)) AS NUMERIC), 1);
+        END;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+select diesel_manage_set_score_avg('t_movies_scores');
+
+alter table t_movies_scores
+    drop constraint t_movies_scores_mid_uk;
+
+drop table t_user_ratings;
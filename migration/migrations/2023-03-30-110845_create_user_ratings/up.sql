@@ -0,0 +1,75 @@
+-- Your SQL goes here
+
+create table t_user_ratings
+(
+    id         bigserial
+        constraint t_user_ratings_pk
+            primary key,
+    uid        bigint                  not null
+        constraint t_user_ratings_t_users_id_fk
+            references t_users,
+    mid        bigint                  not null
+        constraint t_user_ratings_t_movies_id_fk
+            references t_movies
+            on delete cascade,
+    star       smallint                not null
+        constraint t_user_ratings_star_check
+            check (star between 1 and 5),
+    comment    text default null,
+    created_at timestamp default now() not null,
+    updated_at timestamp default now() not null
+);
+
+select diesel_manage_updated_at('t_user_ratings');
+
+comment on table t_user_ratings is 'ratings of movies by users, one rating per user and movie';
+
+comment on column t_user_ratings.uid is 'fk of users, who rates';
+
+comment on column t_user_ratings.mid is 'fk of movies, which is rated';
+
+comment on column t_user_ratings.star is 'stars of the rating, 1-5';
+
+comment on column t_user_ratings.comment is 'short comment along with the rating';
+
+create unique index t_user_ratings_uid_mid_uindex
+    on t_user_ratings (uid, mid);
+
+create index t_user_ratings_mid_index
+    on t_user_ratings (mid);
+
+-- aggregates are upserted by movie
+alter table t_movies_scores
+    add constraint t_movies_scores_mid_uk
+        unique (mid);
+
+-- compute score_avg of new rows as well
+CREATE OR REPLACE FUNCTION diesel_set_score_avg() RETURNS trigger AS
+$$
+DECLARE
+    total FLOAT;
+BEGIN
+    -- an update which sets score_avg explicitly is respected
+    IF TG_OP = 'UPDATE' THEN
+        IF NEW IS NOT DISTINCT FROM OLD OR NEW.score_avg IS DISTINCT FROM OLD.score_avg THEN
+            RETURN NEW;
+        END IF;
+    END IF;
+    total := cast(NEW.cnt_1 + NEW.cnt_2 + NEW.cnt_3 + NEW.cnt_4 + NEW.cnt_5 AS FLOAT) + 1e-5;
+    NEW.score_avg := round(cast((2 * (NEW.cnt_1 / total) + 4 * (NEW.cnt_2 / total) + 6 * (NEW.cnt_3 / total) +
+                                 8 * (NEW.cnt_4 / total) + 10 * (NEW.cnt_5 / total)) AS NUMERIC), 1);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION diesel_manage_set_score_avg(_tbl regclass) RETURNS VOID AS
+$$
+BEGIN
+    EXECUTE format('CREATE TRIGGER set_score_avg BEFORE INSERT OR UPDATE ON %s
+                    FOR EACH ROW EXECUTE PROCEDURE diesel_set_score_avg()', _tbl);
+END;
+$$ LANGUAGE plpgsql;
+
+drop trigger set_score_avg on t_movies_scores;
+
+select diesel_manage_set_score_avg('t_movies_scores');
@@ -0,0 +1,38 @@
+-- Your SQL goes here
+
+create table t_movies_rankings
+(
+    id             bigserial
+        constraint t_movies_rankings_pk
+            primary key,
+    chart          varchar(256)            not null,
+    mid            bigint                  not null
+        constraint t_movies_rankings_t_movies_id_fk
+            references t_movies
+            on delete cascade,
+    rank           integer                 not null,
+    prev_rank      integer default null,
+    weighted_score float                   not null,
+    votes          bigint                  not null,
+    computed_at    timestamp default now() not null
+);
+
+comment on table t_movies_rankings is 'materialised top charts of movies, replaced by the ranking job';
+
+comment on column t_movies_rankings.chart is 'empty for the overall chart, category:<name> or country:<name> otherwise';
+
+comment on column t_movies_rankings.mid is 'fk of movies';
+
+comment on column t_movies_rankings.rank is 'rank in the chart, starts from 1';
+
+comment on column t_movies_rankings.prev_rank is 'rank in the chart of the previous computation';
+
+comment on column t_movies_rankings.weighted_score is 'bayesian weighted score in 0-10';
+
+comment on column t_movies_rankings.votes is 'count of ratings when computed';
+
+create unique index t_movies_rankings_chart_mid_uindex
+    on t_movies_rankings (chart, mid);
+
+create index t_movies_rankings_chart_rank_index
+    on t_movies_rankings (chart, rank);
@@ -0,0 +1,18 @@
+-- This file should undo anything in `up.sql`
+drop trigger set_movies_search_vector on t_movies;
+drop trigger set_celebrities_search_vector on t_celebrities;
+drop function diesel_set_movies_search_vector();
+drop function diesel_set_celebrities_search_vector();
+
+alter table t_movies
+    drop column search_vector,
+    drop column pinyin,
+    drop column pinyin_initials;
+
+alter table t_celebrities
+    drop column search_vector,
+    drop column pinyin,
+    drop column pinyin_initials;
+
+drop function douban_search_query(TEXT);
+drop function douban_segment(TEXT);
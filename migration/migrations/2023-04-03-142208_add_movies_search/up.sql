@@ -0,0 +1,154 @@
+-- Your SQL goes here
+
+-- CJK text has no spaces between words, so that runs of CJK characters are split into
+-- overlapping bigrams, i.e. '花样年华' is indexed as '花样 样年 年华'. Other text is
+-- left to the parser of the 'simple' configuration.
+-- Replace this function to plug in another segmenter, e.g. zhparser, then recompute
+-- the vectors with `update t_movies set title = title` and the same for t_celebrities.
+CREATE OR REPLACE FUNCTION douban_segment(doc TEXT) RETURNS TEXT AS
+$$
+DECLARE
+    cjk    CONSTANT TEXT := '[぀-ヿ㐀-䶿一-鿿豈-﫿]+';
+    result TEXT      := regexp_replace(coalesce(doc, ''), cjk, ' ', 'g');
+    run    TEXT;
+BEGIN
+    FOR run IN SELECT unnest(regexp_matches(coalesce(doc, ''), cjk, 'g'))
+        LOOP
+            IF char_length(run) = 1 THEN
+                result := result || ' ' || run;
+            ELSE
+                FOR i IN 1 .. char_length(run) - 1
+                    LOOP
+                        result := result || ' ' || substr(run, i, 2);
+                    END LOOP;
+            END IF;
+        END LOOP;
+    RETURN result;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+-- Every word of the keyword must match: bigrams of a CJK run are matched as a phrase,
+-- a single CJK character and other words are matched as prefixes.
+CREATE OR REPLACE FUNCTION douban_search_query(keyword TEXT) RETURNS TSQUERY AS
+$$
+DECLARE
+    cjk   CONSTANT TEXT := '[぀-ヿ㐀-䶿一-鿿豈-﫿]+';
+    query TSQUERY   := ''::TSQUERY;
+    part  TSQUERY;
+    word  TEXT;
+    run   TEXT;
+BEGIN
+    FOR word IN SELECT unnest(tsvector_to_array(
+            to_tsvector('simple', regexp_replace(coalesce(keyword, ''), cjk, ' ', 'g'))))
+        LOOP
+            query := query && to_tsquery('simple', quote_literal(word) || ':*');
+        END LOOP;
+    FOR run IN SELECT unnest(regexp_matches(coalesce(keyword, ''), cjk, 'g'))
+        LOOP
+            IF char_length(run) = 1 THEN
+                part := to_tsquery('simple', quote_literal(run) || ':*');
+            ELSE
+                part := to_tsquery('simple', quote_literal(substr(run, 1, 2)));
+                FOR i IN 2 .. char_length(run) - 1
+                    LOOP
+                        part := tsquery_phrase(part, to_tsquery('simple', quote_literal(substr(run, i, 2))));
+                    END LOOP;
+            END IF;
+            query := query && part;
+        END LOOP;
+    RETURN query;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+alter table t_movies
+    add search_vector tsvector default ''::tsvector not null;
+
+alter table t_movies
+    add pinyin varchar(1024) default '' not null;
+
+alter table t_movies
+    add pinyin_initials varchar(256) default '' not null;
+
+comment on column t_movies.search_vector is 'full text vector of title, name, alias_name and plot, maintained by trigger';
+
+comment on column t_movies.pinyin is 'lowercase pinyin of title without spaces';
+
+comment on column t_movies.pinyin_initials is 'lowercase pinyin initials of title';
+
+alter table t_celebrities
+    add search_vector tsvector default ''::tsvector not null;
+
+alter table t_celebrities
+    add pinyin varchar(1024) default '' not null;
+
+alter table t_celebrities
+    add pinyin_initials varchar(256) default '' not null;
+
+comment on column t_celebrities.search_vector is 'full text vector of name and name_en, maintained by trigger';
+
+comment on column t_celebrities.pinyin is 'lowercase pinyin of name without spaces';
+
+comment on column t_celebrities.pinyin_initials is 'lowercase pinyin initials of name';
+
+CREATE OR REPLACE FUNCTION diesel_set_movies_search_vector() RETURNS trigger AS
+$$
+BEGIN
+    NEW.search_vector := setweight(to_tsvector('simple', douban_segment(NEW.title)), 'A') ||
+                         setweight(to_tsvector('simple', douban_segment(NEW.name)), 'A') ||
+                         setweight(to_tsvector('simple', douban_segment(NEW.alias_name)), 'B') ||
+                         setweight(to_tsvector('simple', douban_segment(NEW.plot)), 'D');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION diesel_set_celebrities_search_vector() RETURNS trigger AS
+$$
+BEGIN
+    NEW.search_vector := setweight(to_tsvector('simple', douban_segment(NEW.name)), 'A') ||
+                         setweight(to_tsvector('simple', douban_segment(NEW.name_en)), 'A');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER set_movies_search_vector
+    BEFORE INSERT OR UPDATE OF title, name, alias_name, plot
+    ON t_movies
+    FOR EACH ROW
+EXECUTE PROCEDURE diesel_set_movies_search_vector();
+
+CREATE TRIGGER set_celebrities_search_vector
+    BEFORE INSERT OR UPDATE OF name, name_en
+    ON t_celebrities
+    FOR EACH ROW
+EXECUTE PROCEDURE diesel_set_celebrities_search_vector();
+
+-- fill the vectors of existing rows, their pinyin is filled by the pinyin job of the
+-- movie service on start
+update t_movies
+set title = title;
+
+update t_celebrities
+set name = name;
+
+create index t_movies_search_vector_index
+    on t_movies using gin (search_vector);
+
+comment on index t_movies_search_vector_index is 'full text index used to search movies';
+
+create index t_celebrities_search_vector_index
+    on t_celebrities using gin (search_vector);
+
+comment on index t_celebrities_search_vector_index is 'full text index used to search celebrities';
+
+-- pinyin is matched by prefix, i.e. like 'keyword%'
+create index t_movies_pinyin_index
+    on t_movies (pinyin varchar_pattern_ops);
+
+create index t_movies_pinyin_initials_index
+    on t_movies (pinyin_initials varchar_pattern_ops);
+
+create index t_celebrities_pinyin_index
+    on t_celebrities (pinyin varchar_pattern_ops);
+
+create index t_celebrities_pinyin_initials_index
+    on t_celebrities (pinyin_initials varchar_pattern_ops);
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+drop table t_user_ratings_votes;
+
+drop index t_user_ratings_mid_helpful_cnt_index;
+
+alter table t_user_ratings
+    drop column helpful_cnt;
+
+drop table t_movies_reviews;
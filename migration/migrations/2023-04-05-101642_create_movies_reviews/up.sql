@@ -0,0 +1,76 @@
+-- Your SQL goes here
+
+create table t_movies_reviews
+(
+    id          bigserial
+        constraint t_movies_reviews_pk
+            primary key,
+    uid         bigint                  not null
+        constraint t_movies_reviews_t_users_id_fk
+            references t_users,
+    mid         bigint                  not null
+        constraint t_movies_reviews_t_movies_id_fk
+            references t_movies
+            on delete cascade,
+    title       varchar(128)            not null,
+    content     text                    not null,
+    spoiler     boolean   default false not null,
+    helpful_cnt bigint    default 0     not null,
+    created_at  timestamp default now() not null,
+    updated_at  timestamp default now() not null
+);
+
+select diesel_manage_updated_at('t_movies_reviews');
+
+comment on table t_movies_reviews is 'long reviews of movies, one review per user and movie';
+
+comment on column t_movies_reviews.uid is 'fk of users, who writes the review';
+
+comment on column t_movies_reviews.mid is 'fk of movies, which is reviewed';
+
+comment on column t_movies_reviews.spoiler is 'whether the review reveals the plot';
+
+comment on column t_movies_reviews.helpful_cnt is 'count of users finding the review helpful';
+
+create unique index t_movies_reviews_uid_mid_uindex
+    on t_movies_reviews (uid, mid);
+
+create index t_movies_reviews_mid_helpful_cnt_index
+    on t_movies_reviews (mid, helpful_cnt);
+
+create index t_movies_reviews_mid_created_at_index
+    on t_movies_reviews (mid, created_at);
+
+-- short comments are the comments of ratings
+alter table t_user_ratings
+    add helpful_cnt bigint default 0 not null;
+
+comment on column t_user_ratings.helpful_cnt is 'count of users finding the comment helpful';
+
+create index t_user_ratings_mid_helpful_cnt_index
+    on t_user_ratings (mid, helpful_cnt)
+    where comment is not null;
+
+create table t_user_ratings_votes
+(
+    id         bigserial
+        constraint t_user_ratings_votes_pk
+            primary key,
+    uid        bigint                  not null
+        constraint t_user_ratings_votes_t_users_id_fk
+            references t_users,
+    rid        bigint                  not null
+        constraint t_user_ratings_votes_t_user_ratings_id_fk
+            references t_user_ratings
+            on delete cascade,
+    created_at timestamp default now() not null
+);
+
+comment on table t_user_ratings_votes is 'helpful votes of comments by users, one vote per user and comment';
+
+comment on column t_user_ratings_votes.uid is 'fk of users, who finds the comment helpful';
+
+comment on column t_user_ratings_votes.rid is 'fk of ratings, of which the comment is voted';
+
+create unique index t_user_ratings_votes_rid_uid_uindex
+    on t_user_ratings_votes (rid, uid);
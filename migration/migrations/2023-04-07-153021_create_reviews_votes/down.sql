@@ -0,0 +1,15 @@
+-- This file should undo anything in `up.sql`
+drop table t_movies_reviews_reports;
+drop table t_movies_reviews_votes;
+
+drop index t_movies_reviews_mid_helpful_score_index;
+
+create index t_movies_reviews_mid_helpful_cnt_index
+    on t_movies_reviews (mid, helpful_cnt);
+
+alter table t_movies_reviews
+    drop column unhelpful_cnt,
+    drop column helpful_score,
+    drop column removed_at;
+
+drop function douban_wilson_lower_bound(BIGINT, BIGINT);
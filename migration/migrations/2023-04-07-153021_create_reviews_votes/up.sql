@@ -0,0 +1,113 @@
+-- Your SQL goes here
+
+-- lower bound of the Wilson score interval at 95% confidence, so that a review with
+-- few votes is not ranked above one with many mostly helpful votes
+CREATE OR REPLACE FUNCTION douban_wilson_lower_bound(up BIGINT, down BIGINT) RETURNS FLOAT AS
+$$
+DECLARE
+    z CONSTANT FLOAT := 1.96;
+    n FLOAT          := up + down;
+    p FLOAT;
+BEGIN
+    IF n <= 0 THEN
+        RETURN 0;
+    END IF;
+    p := up / n;
+    RETURN (p + z * z / (2 * n) - z * sqrt((p * (1 - p) + z * z / (4 * n)) / n)) / (1 + z * z / n);
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+alter table t_movies_reviews
+    add unhelpful_cnt bigint default 0 not null;
+
+alter table t_movies_reviews
+    add helpful_score float default 0 not null;
+
+alter table t_movies_reviews
+    add removed_at timestamp default null;
+
+comment on column t_movies_reviews.unhelpful_cnt is 'count of users finding the review unhelpful';
+
+comment on column t_movies_reviews.helpful_score is 'douban_wilson_lower_bound(helpful_cnt, unhelpful_cnt), updated with the counts';
+
+comment on column t_movies_reviews.removed_at is 'set when removed by moderation, a removed review is hidden but kept with its reports';
+
+drop index t_movies_reviews_mid_helpful_cnt_index;
+
+create index t_movies_reviews_mid_helpful_score_index
+    on t_movies_reviews (mid, helpful_score);
+
+create table t_movies_reviews_votes
+(
+    id         bigserial
+        constraint t_movies_reviews_votes_pk
+            primary key,
+    uid        bigint                  not null
+        constraint t_movies_reviews_votes_t_users_id_fk
+            references t_users,
+    rid        bigint                  not null
+        constraint t_movies_reviews_votes_t_movies_reviews_id_fk
+            references t_movies_reviews
+            on delete cascade,
+    helpful    boolean                 not null,
+    created_at timestamp default now() not null,
+    updated_at timestamp default now() not null
+);
+
+select diesel_manage_updated_at('t_movies_reviews_votes');
+
+comment on table t_movies_reviews_votes is 'votes of reviews by users, one vote per user and review';
+
+comment on column t_movies_reviews_votes.uid is 'fk of users, who votes';
+
+comment on column t_movies_reviews_votes.rid is 'fk of reviews, which is voted';
+
+comment on column t_movies_reviews_votes.helpful is 'true if voted up, false if voted down';
+
+create unique index t_movies_reviews_votes_rid_uid_uindex
+    on t_movies_reviews_votes (rid, uid);
+
+create table t_movies_reviews_reports
+(
+    id         bigserial
+        constraint t_movies_reviews_reports_pk
+            primary key,
+    uid        bigint                          not null
+        constraint t_movies_reviews_reports_t_users_id_fk
+            references t_users,
+    rid        bigint                          not null
+        constraint t_movies_reviews_reports_t_movies_reviews_id_fk
+            references t_movies_reviews
+            on delete cascade,
+    reason     varchar(32)                     not null,
+    detail     text      default null,
+    status     varchar(32) default 'pending'   not null
+        constraint t_movies_reviews_reports_status_check
+            check (status in ('pending', 'dismissed', 'removed')),
+    handled_by bigint    default null
+        constraint t_movies_reviews_reports_t_users_id_fk_2
+            references t_users,
+    handled_at timestamp default null,
+    created_at timestamp default now()         not null,
+    updated_at timestamp default now()         not null
+);
+
+select diesel_manage_updated_at('t_movies_reviews_reports');
+
+comment on table t_movies_reviews_reports is 'reports of reviews, the pending ones are the moderation queue';
+
+comment on column t_movies_reviews_reports.uid is 'fk of users, who reports';
+
+comment on column t_movies_reviews_reports.rid is 'fk of reviews, which is reported';
+
+comment on column t_movies_reviews_reports.reason is 'one of spam, abuse, spoiler, copyright and other';
+
+comment on column t_movies_reviews_reports.status is 'pending, dismissed or removed along with the review';
+
+comment on column t_movies_reviews_reports.handled_by is 'fk of users, the admin who handled the report';
+
+create unique index t_movies_reviews_reports_rid_uid_uindex
+    on t_movies_reviews_reports (rid, uid);
+
+create index t_movies_reviews_reports_status_created_at_index
+    on t_movies_reviews_reports (status, created_at);
@@ -0,0 +1,67 @@
+-- Your SQL goes here
+
+create table t_user_marks
+(
+    id         bigserial
+        constraint t_user_marks_pk
+            primary key,
+    uid        bigint                  not null
+        constraint t_user_marks_t_users_id_fk
+            references t_users,
+    mid        bigint                  not null
+        constraint t_user_marks_t_movies_id_fk
+            references t_movies
+            on delete cascade,
+    status     varchar(16)             not null
+        constraint t_user_marks_status_check
+            check (status in ('wish', 'watching', 'watched')),
+    tags       text[]    default '{}'  not null,
+    marked_at  timestamp default now() not null,
+    created_at timestamp default now() not null,
+    updated_at timestamp default now() not null
+);
+
+select diesel_manage_updated_at('t_user_marks');
+
+comment on table t_user_marks is 'marks of movies by users, one mark per user and movie';
+
+comment on column t_user_marks.uid is 'fk of users, who marks';
+
+comment on column t_user_marks.mid is 'fk of movies, which is marked';
+
+comment on column t_user_marks.status is 'wish, watching or watched';
+
+comment on column t_user_marks.tags is 'tags attached by the user when marking';
+
+comment on column t_user_marks.marked_at is 'when the status was set, not changed by editing tags';
+
+create unique index t_user_marks_uid_mid_uindex
+    on t_user_marks (uid, mid);
+
+create index t_user_marks_uid_status_marked_at_index
+    on t_user_marks (uid, status, marked_at);
+
+create index t_user_marks_tags_index
+    on t_user_marks using gin (tags);
+
+create table t_movies_marks
+(
+    id           bigserial
+        constraint t_movies_marks_pk
+            primary key,
+    mid          bigint                  not null
+        constraint t_movies_marks_t_movies_id_fk
+            references t_movies
+            on delete cascade
+        constraint t_movies_marks_mid_uk
+            unique,
+    wish_cnt     bigint    default 0     not null,
+    watching_cnt bigint    default 0     not null,
+    watched_cnt  bigint    default 0     not null,
+    created_at   timestamp default now() not null,
+    updated_at   timestamp default now() not null
+);
+
+select diesel_manage_updated_at('t_movies_marks');
+
+comment on table t_movies_marks is 'counts of marks by status of movies, updated along with the marks';
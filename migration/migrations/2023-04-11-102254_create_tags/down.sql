@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+drop table t_user_tags;
+
+drop table t_movies_tags;
@@ -0,0 +1,77 @@
+-- Your SQL goes here
+
+create table t_movies_tags
+(
+    id         bigserial
+        constraint t_movies_tags_pk
+            primary key,
+    mid        bigint                  not null
+        constraint t_movies_tags_t_movies_id_fk
+            references t_movies
+            on delete cascade,
+    tag        varchar(32)             not null,
+    cnt        bigint    default 0     not null,
+    created_at timestamp default now() not null,
+    updated_at timestamp default now() not null
+);
+
+select diesel_manage_updated_at('t_movies_tags');
+
+comment on table t_movies_tags is 'tags of movies aggregated from marks of users, updated along with the marks';
+
+comment on column t_movies_tags.tag is 'normalized tag';
+
+comment on column t_movies_tags.cnt is 'count of users tagging the movie with the tag';
+
+create unique index t_movies_tags_mid_tag_uindex
+    on t_movies_tags (mid, tag);
+
+create index t_movies_tags_mid_cnt_index
+    on t_movies_tags (mid, cnt);
+
+create index t_movies_tags_tag_index
+    on t_movies_tags (tag);
+
+create table t_user_tags
+(
+    id         bigserial
+        constraint t_user_tags_pk
+            primary key,
+    uid        bigint                  not null
+        constraint t_user_tags_t_users_id_fk
+            references t_users,
+    tag        varchar(32)             not null,
+    cnt        bigint    default 0     not null,
+    created_at timestamp default now() not null,
+    updated_at timestamp default now() not null
+);
+
+select diesel_manage_updated_at('t_user_tags');
+
+comment on table t_user_tags is 'tag clouds of users, updated along with the marks';
+
+comment on column t_user_tags.tag is 'normalized tag';
+
+comment on column t_user_tags.cnt is 'count of movies the user tags with the tag';
+
+create unique index t_user_tags_uid_tag_uindex
+    on t_user_tags (uid, tag);
+
+create index t_user_tags_uid_cnt_index
+    on t_user_tags (uid, cnt);
+
+-- tags of existing marks are only lowercased and trimmed here, the rest of normalization
+-- is applied by the tags job of the movie service on start
+update t_user_marks
+set tags = array(select distinct lower(trim(t)) from unnest(tags) as t where trim(t) <> '')
+where tags <> '{}';
+
+insert into t_movies_tags (mid, tag, cnt)
+select mid, tag, count(*)
+from t_user_marks, unnest(tags) as tag
+group by mid, tag;
+
+insert into t_user_tags (uid, tag, cnt)
+select uid, tag, count(*)
+from t_user_marks, unnest(tags) as tag
+group by uid, tag;
@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+
+drop table t_doulists_follows;
+
+drop table t_doulists_items;
+
+drop table t_doulists;
+
+drop function douban_count_doulist_follows();
+
+drop function douban_count_doulist_items();
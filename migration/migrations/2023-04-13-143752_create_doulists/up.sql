@@ -0,0 +1,140 @@
+-- Your SQL goes here
+
+create table t_doulists
+(
+    id           bigserial
+        constraint t_doulists_pk
+            primary key,
+    uid          bigint                  not null
+        constraint t_doulists_t_users_id_fk
+            references t_users,
+    title        varchar(64)             not null,
+    description  text      default ''    not null,
+    public       boolean   default true  not null,
+    item_cnt     bigint    default 0     not null,
+    follower_cnt bigint    default 0     not null,
+    created_at   timestamp default now() not null,
+    updated_at   timestamp default now() not null
+);
+
+select diesel_manage_updated_at('t_doulists');
+
+comment on table t_doulists is 'doulists, ordered lists of movies curated by users';
+
+comment on column t_doulists.uid is 'fk of users, who owns the doulist';
+
+comment on column t_doulists.public is 'private doulists are only visible to the owner';
+
+comment on column t_doulists.item_cnt is 'count of items, maintained by the trigger douban_count_doulist_items';
+
+comment on column t_doulists.follower_cnt is 'count of followers, maintained by the trigger douban_count_doulist_follows';
+
+create index t_doulists_uid_updated_at_index
+    on t_doulists (uid, updated_at);
+
+create table t_doulists_items
+(
+    id         bigserial
+        constraint t_doulists_items_pk
+            primary key,
+    did        bigint                  not null
+        constraint t_doulists_items_t_doulists_id_fk
+            references t_doulists
+            on delete cascade,
+    mid        bigint                  not null
+        constraint t_doulists_items_t_movies_id_fk
+            references t_movies
+            on delete cascade,
+    position   int                     not null,
+    note       text default null,
+    created_at timestamp default now() not null,
+    updated_at timestamp default now() not null
+);
+
+select diesel_manage_updated_at('t_doulists_items');
+
+comment on table t_doulists_items is 'movies in doulists, one item per doulist and movie';
+
+comment on column t_doulists_items.did is 'fk of doulists';
+
+comment on column t_doulists_items.mid is 'fk of movies, which is listed';
+
+comment on column t_doulists_items.position is 'items are ordered by position ascending, positions may have gaps';
+
+comment on column t_doulists_items.note is 'note of the item by the owner';
+
+create unique index t_doulists_items_did_mid_uindex
+    on t_doulists_items (did, mid);
+
+create index t_doulists_items_did_position_index
+    on t_doulists_items (did, position);
+
+create index t_doulists_items_mid_index
+    on t_doulists_items (mid);
+
+create table t_doulists_follows
+(
+    id         bigserial
+        constraint t_doulists_follows_pk
+            primary key,
+    uid        bigint                  not null
+        constraint t_doulists_follows_t_users_id_fk
+            references t_users,
+    did        bigint                  not null
+        constraint t_doulists_follows_t_doulists_id_fk
+            references t_doulists
+            on delete cascade,
+    created_at timestamp default now() not null,
+    updated_at timestamp default now() not null
+);
+
+select diesel_manage_updated_at('t_doulists_follows');
+
+comment on table t_doulists_follows is 'doulists followed by users';
+
+comment on column t_doulists_follows.uid is 'fk of users, who follows';
+
+comment on column t_doulists_follows.did is 'fk of doulists, which is followed';
+
+create unique index t_doulists_follows_did_uid_uindex
+    on t_doulists_follows (did, uid);
+
+create index t_doulists_follows_uid_index
+    on t_doulists_follows (uid);
+
+-- counts are maintained by triggers, so that items deleted along with movies are counted as well
+CREATE OR REPLACE FUNCTION douban_count_doulist_items() RETURNS trigger AS
+$$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE t_doulists SET item_cnt = item_cnt + 1 WHERE id = NEW.did;
+    ELSIF TG_OP = 'DELETE' THEN
+        UPDATE t_doulists SET item_cnt = greatest(item_cnt - 1, 0) WHERE id = OLD.did;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER count_doulist_items
+    AFTER INSERT OR DELETE
+    ON t_doulists_items
+    FOR EACH ROW
+EXECUTE PROCEDURE douban_count_doulist_items();
+
+CREATE OR REPLACE FUNCTION douban_count_doulist_follows() RETURNS trigger AS
+$$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE t_doulists SET follower_cnt = follower_cnt + 1 WHERE id = NEW.did;
+    ELSIF TG_OP = 'DELETE' THEN
+        UPDATE t_doulists SET follower_cnt = greatest(follower_cnt - 1, 0) WHERE id = OLD.did;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER count_doulist_follows
+    AFTER INSERT OR DELETE
+    ON t_doulists_follows
+    FOR EACH ROW
+EXECUTE PROCEDURE douban_count_doulist_follows();
@@ -0,0 +1,35 @@
+-- Your SQL goes here
+
+create table t_movies_similarities
+(
+    id          bigserial
+        constraint t_movies_similarities_pk
+            primary key,
+    mid         bigint                  not null
+        constraint t_movies_similarities_t_movies_id_fk
+            references t_movies
+            on delete cascade,
+    similar_mid bigint                  not null
+        constraint t_movies_similarities_t_movies_id_fk_2
+            references t_movies
+            on delete cascade,
+    similarity  float                   not null,
+    common_cnt  integer                 not null,
+    computed_at timestamp default now() not null
+);
+
+comment on table t_movies_similarities is 'top-k most similar movies of movies, replaced by the similarity job';
+
+comment on column t_movies_similarities.mid is 'fk of movies';
+
+comment on column t_movies_similarities.similar_mid is 'fk of movies, a neighbour of mid';
+
+comment on column t_movies_similarities.similarity is 'adjusted cosine similarity over ratings, in (0, 1]';
+
+comment on column t_movies_similarities.common_cnt is 'count of users rating both movies when computed';
+
+create unique index t_movies_similarities_mid_similar_mid_uindex
+    on t_movies_similarities (mid, similar_mid);
+
+create index t_movies_similarities_mid_similarity_index
+    on t_movies_similarities (mid, similarity);
@@ -11,6 +11,57 @@ diesel::table! {
         info -> Text,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        pinyin -> Varchar,
+        pinyin_initials -> Varchar,
+    }
+}
+
+diesel::table! {
+    t_doulists (id) {
+        id -> Int8,
+        uid -> Int8,
+        title -> Varchar,
+        description -> Text,
+        public -> Bool,
+        item_cnt -> Int8,
+        follower_cnt -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_doulists_follows (id) {
+        id -> Int8,
+        uid -> Int8,
+        did -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_doulists_items (id) {
+        id -> Int8,
+        did -> Int8,
+        mid -> Int8,
+        position -> Int4,
+        note -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_login_events (id) {
+        id -> Int8,
+        uid -> Int8,
+        identifier_kind -> Varchar,
+        ip -> Nullable<Varchar>,
+        user_agent -> Nullable<Varchar>,
+        ua_fingerprint -> Nullable<Varchar>,
+        succeeded -> Bool,
+        created_at -> Timestamp,
     }
 }
 
@@ -28,6 +79,8 @@ diesel::table! {
         plot -> Text,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        pinyin -> Varchar,
+        pinyin_initials -> Varchar,
     }
 }
 
@@ -63,6 +116,74 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    t_movies_marks (id) {
+        id -> Int8,
+        mid -> Int8,
+        wish_cnt -> Int8,
+        watching_cnt -> Int8,
+        watched_cnt -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_movies_rankings (id) {
+        id -> Int8,
+        chart -> Varchar,
+        mid -> Int8,
+        rank -> Int4,
+        prev_rank -> Nullable<Int4>,
+        weighted_score -> Float8,
+        votes -> Int8,
+        computed_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_movies_reviews (id) {
+        id -> Int8,
+        uid -> Int8,
+        mid -> Int8,
+        title -> Varchar,
+        content -> Text,
+        spoiler -> Bool,
+        helpful_cnt -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        unhelpful_cnt -> Int8,
+        helpful_score -> Float8,
+        removed_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    t_movies_reviews_reports (id) {
+        id -> Int8,
+        uid -> Int8,
+        rid -> Int8,
+        reason -> Varchar,
+        detail -> Nullable<Text>,
+        status -> Varchar,
+        handled_by -> Nullable<Int8>,
+        handled_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_movies_reviews_votes (id) {
+        id -> Int8,
+        uid -> Int8,
+        rid -> Int8,
+        helpful -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     t_movies_scores (id) {
         id -> Int8,
@@ -78,6 +199,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    t_movies_similarities (id) {
+        id -> Int8,
+        mid -> Int8,
+        similar_mid -> Int8,
+        similarity -> Float8,
+        common_cnt -> Int4,
+        computed_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_movies_tags (id) {
+        id -> Int8,
+        mid -> Int8,
+        tag -> Varchar,
+        cnt -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     t_movies_writers (id) {
         id -> Int8,
@@ -95,6 +238,70 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    t_user_blocks (id) {
+        id -> Int8,
+        blocker -> Int8,
+        blocked -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_user_follows (id) {
+        id -> Int8,
+        follower -> Int8,
+        followee -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_user_marks (id) {
+        id -> Int8,
+        uid -> Int8,
+        mid -> Int8,
+        status -> Varchar,
+        tags -> Array<Text>,
+        marked_at -> Timestamp,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_user_ratings (id) {
+        id -> Int8,
+        uid -> Int8,
+        mid -> Int8,
+        star -> Int2,
+        comment -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        helpful_cnt -> Int8,
+    }
+}
+
+diesel::table! {
+    t_user_ratings_votes (id) {
+        id -> Int8,
+        uid -> Int8,
+        rid -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_user_tags (id) {
+        id -> Int8,
+        uid -> Int8,
+        tag -> Varchar,
+        cnt -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     t_users (id) {
         id -> Int8,
@@ -108,29 +315,112 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         last_login -> Nullable<Timestamp>,
+        avatar_url -> Nullable<Varchar>,
+        bio -> Nullable<Varchar>,
+        gender -> Nullable<Varchar>,
+        location -> Nullable<Varchar>,
+        deleted_at -> Nullable<Timestamp>,
+        purged_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    t_users_bans (id) {
+        id -> Int8,
+        uid -> Int8,
+        reason -> Varchar,
+        banned_by -> Int8,
+        expires_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_users_recovery_codes (id) {
+        id -> Int8,
+        uid -> Int8,
+        hashed_code -> Varchar,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    t_users_totp (id) {
+        id -> Int8,
+        uid -> Int8,
+        secret -> Varchar,
+        enabled -> Bool,
+        last_step -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
+diesel::joinable!(t_doulists -> t_users (uid));
+diesel::joinable!(t_doulists_follows -> t_doulists (did));
+diesel::joinable!(t_doulists_follows -> t_users (uid));
+diesel::joinable!(t_doulists_items -> t_doulists (did));
+diesel::joinable!(t_doulists_items -> t_movies (mid));
+diesel::joinable!(t_login_events -> t_users (uid));
 diesel::joinable!(t_movies_actors -> t_celebrities (cid));
 diesel::joinable!(t_movies_actors -> t_movies (mid));
 diesel::joinable!(t_movies_categories -> t_movies (mid));
 diesel::joinable!(t_movies_country -> t_movies (mid));
 diesel::joinable!(t_movies_directors -> t_celebrities (cid));
 diesel::joinable!(t_movies_directors -> t_movies (mid));
+diesel::joinable!(t_movies_marks -> t_movies (mid));
+diesel::joinable!(t_movies_rankings -> t_movies (mid));
+diesel::joinable!(t_movies_reviews -> t_movies (mid));
+diesel::joinable!(t_movies_reviews -> t_users (uid));
+diesel::joinable!(t_movies_reviews_reports -> t_movies_reviews (rid));
+diesel::joinable!(t_movies_reviews_votes -> t_movies_reviews (rid));
+diesel::joinable!(t_movies_reviews_votes -> t_users (uid));
 diesel::joinable!(t_movies_scores -> t_movies (mid));
+diesel::joinable!(t_movies_tags -> t_movies (mid));
 diesel::joinable!(t_movies_writers -> t_celebrities (cid));
 diesel::joinable!(t_movies_writers -> t_movies (mid));
+diesel::joinable!(t_user_marks -> t_movies (mid));
+diesel::joinable!(t_user_marks -> t_users (uid));
+diesel::joinable!(t_user_ratings -> t_movies (mid));
+diesel::joinable!(t_user_ratings -> t_users (uid));
+diesel::joinable!(t_user_ratings_votes -> t_user_ratings (rid));
+diesel::joinable!(t_user_ratings_votes -> t_users (uid));
+diesel::joinable!(t_user_tags -> t_users (uid));
 diesel::joinable!(t_users -> t_oauth (oauth_id));
+diesel::joinable!(t_users_recovery_codes -> t_users (uid));
+diesel::joinable!(t_users_totp -> t_users (uid));
 
 diesel::allow_tables_to_appear_in_same_query!(
     t_celebrities,
+    t_doulists,
+    t_doulists_follows,
+    t_doulists_items,
+    t_login_events,
     t_movies,
     t_movies_actors,
     t_movies_categories,
     t_movies_country,
     t_movies_directors,
+    t_movies_marks,
+    t_movies_rankings,
+    t_movies_reviews,
+    t_movies_reviews_reports,
+    t_movies_reviews_votes,
     t_movies_scores,
+    t_movies_similarities,
+    t_movies_tags,
     t_movies_writers,
     t_oauth,
+    t_user_blocks,
+    t_user_follows,
+    t_user_marks,
+    t_user_ratings,
+    t_user_ratings_votes,
+    t_user_tags,
     t_users,
+    t_users_bans,
+    t_users_recovery_codes,
+    t_users_totp,
 );
@@ -21,6 +21,94 @@ fn main() {
             vec![DERIVE_SER_DER, DERIVE_DEFAULT],
         )
         .derive_for("user.sys.v1.LoginRes", vec![DERIVE_SER_DER, DERIVE_DEFAULT])
+        .derive_for(
+            "user.sys.v1.TotpChallenge",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "user.sys.v1.EnrollTotpRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "user.sys.v1.UserProfile",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "user.sys.v1.GetUserRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for("user.sys.v1.GetMeRes", vec![DERIVE_SER_DER, DERIVE_DEFAULT])
+        .derive_for(
+            "user.sys.v1.LoginEvent",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "user.sys.v1.ListLoginHistoryRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "user.sys.v1.ListFollowsRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "user.sys.v1.IsFollowingRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "user.sys.v1.CountFollowsRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "user.sys.v1.SearchUsersRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for("user.sys.v1.Ban", vec![DERIVE_SER_DER, DERIVE_DEFAULT])
+        .derive_for(
+            "user.sys.v1.UserDetail",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "user.sys.v1.ListUsersRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for("movie.mark.v1.Mark", vec![DERIVE_SER_DER, DERIVE_DEFAULT])
+        .derive_for(
+            "movie.mark.v1.ListMyMarksRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "movie.mark.v1.GetMarkCountRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for("movie.tag.v1.TagCount", vec![DERIVE_SER_DER, DERIVE_DEFAULT])
+        .derive_for(
+            "movie.tag.v1.ListMovieTagsRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "movie.tag.v1.ListMyTagsRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "movie.score.v1.MyScore",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "movie.score.v1.GetMyScoreRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "movie.review.v1.PostReviewRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "movie.media.v1.ImageVariant",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
+        .derive_for(
+            "movie.media.v1.UploadImageRes",
+            vec![DERIVE_SER_DER, DERIVE_DEFAULT],
+        )
         .out_dir("src/gen")
         .compile(&protos, &[proto_dir])
         .unwrap();
@@ -29,6 +29,36 @@ pub mod pb {
                 include!("./gen/movie.score.v1.rs");
             }
         }
+        pub mod review {
+            pub mod v1 {
+                include!("./gen/movie.review.v1.rs");
+            }
+        }
+        pub mod mark {
+            pub mod v1 {
+                include!("./gen/movie.mark.v1.rs");
+            }
+        }
+        pub mod tag {
+            pub mod v1 {
+                include!("./gen/movie.tag.v1.rs");
+            }
+        }
+        pub mod doulist {
+            pub mod v1 {
+                include!("./gen/movie.doulist.v1.rs");
+            }
+        }
+        pub mod recommend {
+            pub mod v1 {
+                include!("./gen/movie.recommend.v1.rs");
+            }
+        }
+        pub mod media {
+            pub mod v1 {
+                include!("./gen/movie.media.v1.rs");
+            }
+        }
     }
     pub mod common {
         pub mod v1 {
@@ -83,8 +113,30 @@ impl_args! {
         (GenerateTokenReq, GenerateTokenRes);
         (RefreshTokenReq, RefreshTokenRes);
     }
+    CommandArgs(user, sys, v1) {
+        (EnrollTotpReq, EnrollTotpRes);
+        (VerifyTotpReq, LoginRes);
+    }
+    CommandArgs(movie, review, v1) {
+        (PostReviewReq, PostReviewRes);
+    }
+    CommandArgs(movie, doulist, v1) {
+        (CreateDoulistReq, CreateDoulistRes);
+    }
+    CommandArgs(movie, media, v1) {
+        (UploadImageReq, UploadImageRes);
+    }
     QueryArgs(user, sys, v1) {
         (LoginReq, LoginRes);
+        (GetUserReq, GetUserRes);
+        (GetMeReq, GetMeRes);
+        (ListUsersReq, ListUsersRes);
+        (ExportMyDataReq, ExportMyDataRes);
+        (ListLoginHistoryReq, ListLoginHistoryRes);
+        (ListFollowsReq, ListFollowsRes);
+        (IsFollowingReq, IsFollowingRes);
+        (CountFollowsReq, CountFollowsRes);
+        (SearchUsersReq, SearchUsersRes);
     }
     QueryArgs(auth, token, v1) {
         (ParseTokenReq, ParseTokenRes);
@@ -92,13 +144,43 @@ impl_args! {
     QueryArgs(movie, movie, v1) {
         (GetReq, GetRes);
         (ListReq, ListRes);
+        (ListTopMoviesReq, ListTopMoviesRes);
     }
     QueryArgs(movie, celebrity, v1) {
         (GetReq, GetRes);
         (ListReq, ListRes);
+        (GetFilmographyReq, GetFilmographyRes);
     }
     QueryArgs(movie, score, v1) {
         (GetReq, GetRes);
+        (GetMyScoreReq, GetMyScoreRes);
+    }
+    QueryArgs(movie, review, v1) {
+        (GetReviewReq, GetReviewRes);
+        (ListReviewsReq, ListReviewsRes);
+        (ListCommentsReq, ListCommentsRes);
+        (ListReportsReq, ListReportsRes);
+    }
+    QueryArgs(movie, mark, v1) {
+        (ListMyMarksReq, ListMyMarksRes);
+        (GetMarkCountReq, GetMarkCountRes);
+    }
+    QueryArgs(movie, tag, v1) {
+        (ListMovieTagsReq, ListMovieTagsRes);
+        (ListMyTagsReq, ListMyTagsRes);
+    }
+    QueryArgs(movie, doulist, v1) {
+        (GetDoulistReq, GetDoulistRes);
+        (ListDoulistsReq, ListDoulistsRes);
+        (ListFollowedDoulistsReq, ListFollowedDoulistsRes);
+        (ListItemsReq, ListItemsRes);
+    }
+    QueryArgs(movie, recommend, v1) {
+        (RecommendForUserReq, RecommendForUserRes);
+        (SimilarMoviesReq, SimilarMoviesRes);
+    }
+    QueryArgs(movie, media, v1) {
+        (GetImageReq, GetImageRes);
     }
 }
 
@@ -110,6 +192,18 @@ impl_empty_res! {
     (user, sys, v1) {
         RegisterReq,
         BindReq,
+        ConfirmTotpReq,
+        UnlockReq,
+        UpdateProfileReq,
+        SetRoleGroupReq,
+        BanUserReq,
+        UnbanUserReq,
+        ForceLogoutReq,
+        DeleteAccountReq,
+        FollowReq,
+        UnfollowReq,
+        BlockReq,
+        UnblockReq,
     }
     (movie, movie, v1) {
         PutReq,
@@ -121,5 +215,52 @@ impl_empty_res! {
     }
     (movie, score, v1) {
         ScoreReq,
+        UnscoreReq,
+    }
+    (movie, review, v1) {
+        EditReviewReq,
+        DelReviewReq,
+        PutCommentReq,
+        DelCommentReq,
+        VoteReviewReq,
+        UnvoteReviewReq,
+        ReportReviewReq,
+        HandleReportsReq,
+    }
+    (movie, mark, v1) {
+        MarkReq,
+        UnmarkReq,
+    }
+    (movie, doulist, v1) {
+        EditDoulistReq,
+        DelDoulistReq,
+        AddItemReq,
+        EditItemReq,
+        MoveItemReq,
+        RemoveItemReq,
+        FollowDoulistReq,
+        UnfollowDoulistReq,
+    }
+}
+
+impl pb::common::v1::Slice {
+    /// Returns `(limit, offset)`, `ByLimit` is preferred over `ByPage` if both are set,
+    /// the limit falls back to `default` and is clamped into `[1, max]`.
+    pub fn limit_offset(&self, default: i64, max: i64) -> (i64, i64) {
+        let (limit, offset) = match (&self.limit, &self.page) {
+            (Some(by), _) => (by.limit as i64, by.offset as i64),
+            // page starts from 1
+            (None, Some(by)) => {
+                let limit = if by.per_page > 0 {
+                    by.per_page as i64
+                } else {
+                    default
+                };
+                (limit, (by.page as i64 - 1).max(0) * limit.clamp(1, max))
+            }
+            (None, None) => (default, 0),
+        };
+        let limit = if limit > 0 { limit } else { default };
+        (limit.clamp(1, max), offset.max(0))
     }
 }
@@ -1,3 +1,4 @@
 pub mod clear_cache;
 pub mod generate_token;
 pub mod refresh_token;
+pub mod revoke_token;
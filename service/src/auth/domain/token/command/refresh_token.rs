@@ -4,6 +4,7 @@ use common::infra::Command;
 use common::invalid_argument;
 use common::status::ext::GrpcResult;
 use proto::pb::auth::token::v1 as pb;
+use std::ops::DerefMut;
 use tracing::instrument;
 
 #[instrument(skip_all, err)]
@@ -11,6 +12,7 @@ async fn execute(
     req: pb::RefreshTokenReq,
     key: &jsonwebtoken::DecodingKey,
     algorithm: jsonwebtoken::Algorithm,
+    conn: &mut redis::Connection,
     generate_token: impl Command<pb::GenerateTokenReq>,
 ) -> GrpcResult<pb::RefreshTokenRes> {
     let refresh_token = req.value.as_str();
@@ -25,6 +27,9 @@ async fn execute(
     if !token.validate(key, algorithm)? {
         return Err(invalid_argument!("refresh", "valid signature").into());
     }
+    if token.revoked(conn)? {
+        return Err(invalid_argument!("refresh", "not revoked token").into());
+    }
     generate_token
         .execute(pb::GenerateTokenReq {
             sub: claim.as_sub().to_string(),
@@ -43,7 +48,14 @@ impl TokenResolver {
     pub(in crate::auth) fn create_refresh_token(&self) -> impl Command<pb::RefreshTokenReq> + '_ {
         move |req: pb::RefreshTokenReq| async move {
             let generate_token = self.create_generate_token();
-            execute(req, self.decode_key(), self.algorithm(), generate_token).await
+            execute(
+                req,
+                self.decode_key(),
+                self.algorithm(),
+                self.redis_conn().deref_mut(),
+                generate_token,
+            )
+            .await
         }
     }
 }
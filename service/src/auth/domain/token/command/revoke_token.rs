@@ -0,0 +1,20 @@
+use crate::auth::domain::token::model::token::Token;
+use crate::auth::rpc::TokenResolver;
+use common::infra::*;
+use common::status::ext::GrpcResult;
+use proto::pb::auth::token::v1 as pb;
+use proto::pb::common::v1::EmptyRes;
+use std::ops::DerefMut;
+use tracing::instrument;
+
+#[instrument(skip_all, err)]
+async fn execute(req: pb::RevokeTokenReq, conn: &mut redis::Connection) -> GrpcResult<EmptyRes> {
+    Token::revoke(&req.sub, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl TokenResolver {
+    pub(in crate::auth) fn create_revoke_token(&self) -> impl Command<pb::RevokeTokenReq> + '_ {
+        move |req: pb::RevokeTokenReq| async move { execute(req, self.redis_conn().deref_mut()).await }
+    }
+}
@@ -32,6 +32,9 @@ pub struct Claim<T> {
     #[serde(skip_serializing_if = "String::is_empty")]
     #[serde(default)]
     jti: String, // Optional. JWT ID (a unique identifier for the JWT.)
+    #[serde(skip_serializing_if = "eq_zero")]
+    #[serde(default)]
+    ver: u64, // Private. Version of the tokens of the subject, bumped to revoke them
     #[serde(skip_serializing_if = "Option::is_none")]
     payload: Option<T>, // Optional. other payloads in JWT
 }
@@ -55,6 +58,7 @@ where
             iss: "".to_string(),
             sub: "".to_string(),
             jti: "".to_string(),
+            ver: 0,
             payload: None,
         }
     }
@@ -99,6 +103,11 @@ where
         self
     }
 
+    pub fn version(&mut self, ver: u64) -> &mut Self {
+        self.ver = ver;
+        self
+    }
+
     pub fn as_payload(&self) -> Option<&T> {
         self.payload.as_ref()
     }
@@ -135,6 +144,10 @@ where
         self.nbf
     }
 
+    pub fn as_ver(&self) -> u64 {
+        self.ver
+    }
+
     pub fn is_expired(&self) -> bool {
         let now = jsonwebtoken::get_current_timestamp();
         self.as_exp() < now - LEE_WAY
@@ -177,6 +190,11 @@ impl<T> Builder<T> {
         self
     }
 
+    pub fn version(mut self, ver: u64) -> Self {
+        self.inner.ver = ver;
+        self
+    }
+
     pub fn payload(mut self, payload: T) -> Claim<T> {
         self.inner.payload = Some(payload);
         self.inner
@@ -66,6 +66,33 @@ impl Token {
         Ok(())
     }
 
+    /// Version of the tokens of the subject, tokens of another version are revoked.
+    fn version(sub: &str, conn: &mut redis::Connection) -> GrpcResult<u64> {
+        let key = format!("auth:token:{}:version", sub);
+        let version: Option<u64> = conn
+            .get(&key)
+            .map_err(|e| internal!(format!("Redis error, cannot get key {}, err: {}", key, e)))?;
+        Ok(version.unwrap_or_default())
+    }
+
+    /// Revoke all tokens signed for the subject, cached ones are cleared so that the
+    /// next generated ones are of the new version.
+    pub(in super::super) fn revoke(sub: &str, conn: &mut redis::Connection) -> GrpcResult<()> {
+        let key = format!("auth:token:{}:version", sub);
+        let _: u64 = conn
+            .incr(&key, 1)
+            .map_err(|e| internal!(format!("Redis failed to incr key {}, err: {}", key, e)))?;
+        Self::clear_cache(sub, TokenKind::Access, conn)?;
+        Self::clear_cache(sub, TokenKind::Refresh, conn)?;
+        Ok(())
+    }
+
+    /// Whether the token is of a version before the subject's current one.
+    pub(in super::super) fn revoked(&self, conn: &mut redis::Connection) -> GrpcResult<bool> {
+        let claim = self.claim()?;
+        Ok(claim.as_ver() != Self::version(claim.as_sub(), conn)?)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub(in super::super) fn get_or_generate(
         sub: &str,
@@ -84,15 +111,21 @@ impl Token {
         let token_str: Option<String> = conn
             .get(&key)
             .map_err(|e| internal!(format!("Redis error, cannot get key {}, err: {}", key, e)))?;
+        let version = Self::version(sub, conn)?;
         if let Some(value) = token_str {
-            return Ok(Self {
+            let token = Self {
                 kind,
                 raw_parts: expect_two!(value.rsplitn(2, '.').map(ToOwned::to_owned)),
-            });
+            };
+            // a token cached by a generation racing with a revocation is of the old version
+            if token.claim()?.as_ver() == version {
+                return Ok(token);
+            }
         }
         Self::generate(
             &key,
             sub,
+            version,
             aud,
             kind,
             jti,
@@ -110,6 +143,7 @@ impl Token {
     fn generate(
         key: &str,
         sub: &str,
+        version: u64,
         aud: &str,
         kind: TokenKind,
         jti: bool,
@@ -135,6 +169,7 @@ impl Token {
             .subject(sub)
             .audience(aud)
             .issuer(domain)
+            .version(version)
             .payload(Payload {
                 kind,
                 detail: payload,
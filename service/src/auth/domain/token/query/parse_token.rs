@@ -3,6 +3,7 @@ use crate::auth::rpc::TokenResolver;
 use common::infra::Query;
 use common::status::ext::GrpcResult;
 use proto::pb::auth::token::v1 as pb;
+use std::ops::DerefMut;
 use tracing::instrument;
 
 #[instrument(skip_all, err)]
@@ -10,9 +11,10 @@ async fn execute(
     req: pb::ParseTokenReq,
     key: &jsonwebtoken::DecodingKey,
     algorithm: jsonwebtoken::Algorithm,
+    conn: &mut redis::Connection,
 ) -> GrpcResult<pb::ParseTokenRes> {
     let token: Token = req.value.as_str().parse()?;
-    let checked = token.validate(key, algorithm)?;
+    let checked = token.validate(key, algorithm)? && !token.revoked(conn)?;
     let kind: pb::TokenKind = match token.kind() {
         TokenKind::Access => pb::TokenKind::Access,
         TokenKind::Refresh => pb::TokenKind::Refresh,
@@ -29,7 +31,13 @@ async fn execute(
 impl TokenResolver {
     pub(in crate::auth) fn create_parse_token(&self) -> impl Query<pb::ParseTokenReq> + '_ {
         move |req: pb::ParseTokenReq| async move {
-            execute(req, self.decode_key(), self.algorithm()).await
+            execute(
+                req,
+                self.decode_key(),
+                self.algorithm(),
+                self.redis_conn().deref_mut(),
+            )
+            .await
         }
     }
 }
@@ -119,6 +119,7 @@ pub mod layer {
             }
             Auth {
                 method: self.method,
+                optional: false,
                 _data: Default::default(),
             }
         }
@@ -126,13 +127,25 @@ pub mod layer {
 
     pub struct Auth<I: IdentityProvider, ResBody> {
         method: method::Method,
+        optional: bool,
         _data: PhantomData<(ResBody, I)>,
     }
 
+    impl<I: IdentityProvider, ResBody> Auth<I, ResBody> {
+        /// Let the requests without a cookie or a token pass through without the identity
+        /// extensions, for public routes which respond differently to a signed-in user.
+        /// A request with an invalid cookie or token is still rejected.
+        pub fn optional(mut self) -> Self {
+            self.optional = true;
+            self
+        }
+    }
+
     impl<I: IdentityProvider, ResBody> Clone for Auth<I, ResBody> {
         fn clone(&self) -> Self {
             Self {
                 method: self.method.clone(),
+                optional: self.optional,
                 _data: Default::default(),
             }
         }
@@ -153,6 +166,9 @@ pub mod layer {
 
         fn authorize(&mut self, req: Request<B>) -> Self::Future {
             let method = self.method.clone();
+            if self.optional && !method.carried_by(&req) {
+                return Box::pin(async move { Ok((req, None)) });
+            }
             let client = CLIENT.get().expect("Not connect.").clone();
 
             method.auth::<I, _, _>(client, req)
@@ -369,6 +385,18 @@ pub mod layer {
         }
 
         impl Method {
+            // whether the request carries a cookie or a token of this method
+            pub(super) fn carried_by<B>(&self, req: &Request<B>) -> bool {
+                match self {
+                    Method::CookieAuth { auth_conf, .. } => {
+                        scan_cookies(req).get(&auth_conf.cookie_name).is_some()
+                    }
+                    Method::BearerAuth { header, .. } => {
+                        req.headers().contains_key(header.as_str())
+                    }
+                }
+            }
+
             pub(super) fn auth<I: IdentityProvider, B: Send + 'static, ResBody: Default + Send>(
                 self,
                 client: TokenServiceClient<Channel>,
@@ -41,4 +41,13 @@ impl token_service_server::TokenService for TokenService {
         let res = clear_cache.execute(req.into_inner()).await?;
         Ok(Response::new(res))
     }
+
+    async fn revoke_token(
+        &self,
+        req: Request<RevokeTokenReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let revoke_token = self.0.create_revoke_token();
+        let res = revoke_token.execute(req.into_inner()).await?;
+        Ok(Response::new(res))
+    }
 }
@@ -2,7 +2,9 @@ use axum::Router;
 use common::config::env::require;
 use common::utils::parse_config;
 use futures::FutureExt;
+use service::movie::rest::{RestConfig as MovieConfig, RestResolver as MovieResolver};
 use service::user::rest::{RestConfig as UserConfig, RestResolver as UserResolver};
+use std::net::SocketAddr;
 
 /// douban-rs BFF(Backend for Frontend), merged all routers into one service
 /// and provide only one endpoint to frontend
@@ -21,13 +23,22 @@ async fn main() {
         root_router = root_router.merge(route)
     }
 
+    {
+        // movie router
+        let conf: MovieConfig = parse_config::<MovieResolver>()
+            .await
+            .expect("Cannot parse movie config");
+        let route = MovieResolver::new(conf).await.make_router().await;
+        root_router = root_router.merge(route)
+    }
+
     serve(&require("APP_ADDR"), root_router).await
 }
 
 pub async fn serve(listen_addr: &str, route: Router) {
     let addr = listen_addr.parse().unwrap();
     axum::Server::bind(&addr)
-        .serve(route.into_make_service())
+        .serve(route.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(tokio::signal::ctrl_c().map(|_| ()))
         .await
         .unwrap();
@@ -0,0 +1,33 @@
+use common::utils::{config_tips, parse_config};
+use service::movie::rpc::MovieResolver;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+
+    let config = parse_config::<MovieResolver>()
+        .await
+        .expect("Cannot parse config");
+
+    config_tips(&config);
+
+    let resolver = match MovieResolver::new(config) {
+        Ok(resolver) => resolver,
+        Err(e) => {
+            tracing::error!("Invalid media config, err: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    resolver.register_service().await;
+
+    resolver.spawn_pinyin_job();
+
+    resolver.spawn_tags_job();
+
+    resolver.spawn_ranking_job();
+
+    resolver.spawn_similarity_job();
+
+    resolver.serve().await.expect("Start failed");
+}
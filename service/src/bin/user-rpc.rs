@@ -15,5 +15,7 @@ async fn main() {
 
     resolver.register_service().await;
 
+    resolver.spawn_purge_job();
+
     resolver.serve().await.expect("Start failed");
 }
@@ -1,19 +1,19 @@
+use crate::movie::domain::celebrity::model::celebrity::{Celebrity, CelebrityId};
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
 use diesel::PgConnection;
-use common::infra::Command;
-use common::status::prelude::*;
 use proto::pb::common::v1::EmptyRes;
 use proto::pb::movie::celebrity::v1 as pb;
-use crate::movie::rpc::MovieResolver;
+use std::ops::DerefMut;
 
 #[tracing::instrument(skip_all, err)]
 async fn execute(req: pb::DelReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
-    todo!()
+    Celebrity::del_celebrity(CelebrityId::from(req.id as u64), req.cascade, conn)?;
+    Ok(EmptyRes {})
 }
 
 impl MovieResolver {
     pub(in crate::movie) fn create_del_celebrity(&self) -> impl Command<pb::DelReq> + '_ {
-        move |req: pb::DelReq| async move {
-            execute(req, todo!())
-        }
+        move |req: pb::DelReq| async move { execute(req, self.pg_conn().deref_mut()).await }
     }
-}
\ No newline at end of file
+}
@@ -1,2 +1,2 @@
+pub mod del_celebrity;
 pub mod put_celebrity;
-pub mod del_celebrity;
\ No newline at end of file
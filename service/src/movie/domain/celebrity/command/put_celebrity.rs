@@ -1,19 +1,19 @@
+use crate::movie::domain::celebrity::model::celebrity::Celebrity;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
 use diesel::PgConnection;
-use common::infra::Command;
-use common::status::prelude::*;
 use proto::pb::common::v1::EmptyRes;
 use proto::pb::movie::celebrity::v1 as pb;
-use crate::movie::rpc::MovieResolver;
+use std::ops::DerefMut;
 
 #[tracing::instrument(skip_all, err)]
 async fn execute(req: pb::PutReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
-    todo!()
+    Celebrity::put_celebrity(&req, conn)?;
+    Ok(EmptyRes {})
 }
 
 impl MovieResolver {
     pub(in crate::movie) fn create_put_celebrity(&self) -> impl Command<pb::PutReq> + '_ {
-        move |req: pb::PutReq| async move {
-            execute(req, todo!())
-        }
+        move |req: pb::PutReq| async move { execute(req, self.pg_conn().deref_mut()).await }
     }
-}
\ No newline at end of file
+}
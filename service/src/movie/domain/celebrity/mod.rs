@@ -1,9 +1,11 @@
 pub mod command;
-pub mod query;
 pub mod model;
+pub mod query;
 
+// the role of a celebrity in a movie
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum CelebrityType {
     Actor,
     Director,
     Writer,
-}
\ No newline at end of file
+}
@@ -0,0 +1,295 @@
+use crate::movie::domain::celebrity::CelebrityType;
+use crate::movie::domain::movie::model::movie::Movie;
+use crate::movie::domain::search::{romanize, Keyword};
+use crate::movie::domain::utils::{dedup, map_internal, map_not_found};
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{infra, internal, invalid_argument, not_found};
+use diesel::prelude::*;
+use migration::{t_celebrities, t_movies, t_movies_actors, t_movies_directors, t_movies_writers};
+use proto::pb::movie::celebrity::v1 as pb;
+use std::collections::HashSet;
+use tonic::Status;
+
+pub type CelebrityId = infra::Id<Celebrity>;
+
+#[derive(Queryable)]
+pub struct Celebrity {
+    id: i64,
+    name: String,
+    name_en: Option<String>,
+    pic_url: Option<String>,
+    gender: String,
+    imdb: String,
+    info: String,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+    pinyin: String,
+    pinyin_initials: String,
+}
+
+// a put replaces the whole celebrity, so that none clears an optional field
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = t_celebrities, treat_none_as_null = true)]
+struct PutCelebrity<'a> {
+    name: &'a str,
+    name_en: Option<&'a str>,
+    pic_url: Option<&'a str>,
+    gender: &'a str,
+    imdb: &'a str,
+    info: &'a str,
+    pinyin: String,
+    pinyin_initials: String,
+}
+
+fn check_movies(ids: &[i64], conn: &mut PgConnection) -> GrpcResult<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let found: HashSet<i64> = t_movies::table
+        .filter(t_movies::id.eq_any(ids))
+        .select(t_movies::id)
+        .load::<i64>(conn)
+        .map_err(map_internal)?
+        .into_iter()
+        .collect();
+    match ids.iter().find(|id| !found.contains(id)) {
+        Some(missing) => Err(not_found!(format!("movie({})", missing)).into()),
+        None => Ok(()),
+    }
+}
+
+impl Celebrity {
+    pub fn id(&self) -> CelebrityId {
+        CelebrityId::from(self.id as u64)
+    }
+
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::GetRes {
+        pb::GetRes {
+            id: self.id,
+            payload: Some(pb::CelebrityPayload {
+                name: self.name.clone(),
+                name_en: self.name_en.clone(),
+                pic_url: self.pic_url.clone(),
+                gender: self.gender.clone(),
+                imdb: self.imdb.clone(),
+                info: self.info.clone(),
+            }),
+        }
+    }
+
+    /// A snippet of the name matching the keyword, keyed by the id of the celebrity.
+    /// The name is highlighted as a whole if only its pinyin matches.
+    pub(in crate::movie::domain) fn highlight(&self, keyword: &Keyword) -> Option<(i64, String)> {
+        [Some(&self.name), self.name_en.as_ref()]
+            .into_iter()
+            .flatten()
+            .find_map(|text| keyword.highlight(text))
+            .or_else(|| keyword.highlight_pinyin(&self.name, &self.pinyin, &self.pinyin_initials))
+            .map(|snippet| (self.id, snippet))
+    }
+
+    /// Insert the celebrity or replace the one with the same IMDb number, the movies
+    /// of every role are replaced in the same transaction.
+    pub(in crate::movie::domain) fn put_celebrity(
+        req: &pb::PutReq,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<CelebrityId> {
+        let payload = req
+            .payload
+            .as_ref()
+            .ok_or_else(|| invalid_argument!("payload", "a celebrity payload"))?;
+        if payload.name.trim().is_empty() {
+            return Err(invalid_argument!("name", "a non-empty name").into());
+        }
+        if payload.imdb.trim().is_empty() {
+            return Err(invalid_argument!("imdb", "an IMDb number").into());
+        }
+        let (pinyin, pinyin_initials) = romanize(payload.name.trim());
+        let put = PutCelebrity {
+            name: payload.name.trim(),
+            name_en: payload.name_en.as_deref(),
+            pic_url: payload.pic_url.as_deref(),
+            gender: &payload.gender,
+            imdb: payload.imdb.trim(),
+            info: &payload.info,
+            pinyin,
+            pinyin_initials,
+        };
+        let as_actor = dedup(&req.as_actor_movies_id);
+        let as_director = dedup(&req.as_director_movies_id);
+        let as_writer = dedup(&req.as_writer_movies_id);
+
+        conn.transaction::<CelebrityId, GrpcStatus, _>(|conn| {
+            let movies = [as_actor.as_slice(), &as_director, &as_writer].concat();
+            check_movies(&dedup(&movies), conn)?;
+
+            let celebrity_id: i64 = diesel::insert_into(t_celebrities::table)
+                .values(&put)
+                .on_conflict(t_celebrities::imdb)
+                .do_update()
+                .set(&put)
+                .returning(t_celebrities::id)
+                .get_result(conn)
+                .map_err(|e| internal!(format!("Cannot put celebrity, err: {}", e)))?;
+
+            diesel::delete(t_movies_actors::table.filter(t_movies_actors::cid.eq(celebrity_id)))
+                .execute(conn)
+                .map_err(map_internal)?;
+            diesel::insert_into(t_movies_actors::table)
+                .values(
+                    as_actor
+                        .iter()
+                        .map(|mid| {
+                            (
+                                t_movies_actors::mid.eq(mid),
+                                t_movies_actors::cid.eq(celebrity_id),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .map_err(map_internal)?;
+
+            diesel::delete(
+                t_movies_directors::table.filter(t_movies_directors::cid.eq(celebrity_id)),
+            )
+            .execute(conn)
+            .map_err(map_internal)?;
+            diesel::insert_into(t_movies_directors::table)
+                .values(
+                    as_director
+                        .iter()
+                        .map(|mid| {
+                            (
+                                t_movies_directors::mid.eq(mid),
+                                t_movies_directors::cid.eq(celebrity_id),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .map_err(map_internal)?;
+
+            diesel::delete(t_movies_writers::table.filter(t_movies_writers::cid.eq(celebrity_id)))
+                .execute(conn)
+                .map_err(map_internal)?;
+            diesel::insert_into(t_movies_writers::table)
+                .values(
+                    as_writer
+                        .iter()
+                        .map(|mid| {
+                            (
+                                t_movies_writers::mid.eq(mid),
+                                t_movies_writers::cid.eq(celebrity_id),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .map_err(map_internal)?;
+
+            Ok(CelebrityId::from(celebrity_id as u64))
+        })
+    }
+
+    /// Delete the celebrity, a celebrity credited in any movie is only deleted with
+    /// `cascade`, which removes it from the movies as well.
+    pub(in crate::movie::domain) fn del_celebrity(
+        cid: CelebrityId,
+        cascade: bool,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        let cid = cid.as_i64().expect("Expect an i64 id");
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            if !cascade {
+                let credits = Self::movie_ids(cid, CelebrityType::Actor, conn)?.len()
+                    + Self::movie_ids(cid, CelebrityType::Director, conn)?.len()
+                    + Self::movie_ids(cid, CelebrityType::Writer, conn)?.len();
+                if credits > 0 {
+                    return Err(Status::failed_precondition(format!(
+                        "Celebrity({}) is credited {} times, delete it with cascade",
+                        cid, credits
+                    ))
+                    .into());
+                }
+            }
+            diesel::delete(t_movies_actors::table.filter(t_movies_actors::cid.eq(cid)))
+                .execute(conn)
+                .map_err(map_internal)?;
+            diesel::delete(t_movies_directors::table.filter(t_movies_directors::cid.eq(cid)))
+                .execute(conn)
+                .map_err(map_internal)?;
+            diesel::delete(t_movies_writers::table.filter(t_movies_writers::cid.eq(cid)))
+                .execute(conn)
+                .map_err(map_internal)?;
+            let deleted = diesel::delete(t_celebrities::table.find(cid))
+                .execute(conn)
+                .map_err(map_internal)?;
+            if deleted == 0 {
+                return Err(not_found!(format!("celebrity({})", cid)).into());
+            }
+            Ok(())
+        })
+    }
+
+    fn movie_ids(cid: i64, role: CelebrityType, conn: &mut PgConnection) -> GrpcResult<Vec<i64>> {
+        let ids: QueryResult<Vec<i64>> = match role {
+            CelebrityType::Actor => t_movies_actors::table
+                .select(t_movies_actors::mid)
+                .filter(t_movies_actors::cid.eq(cid))
+                .load(conn),
+            CelebrityType::Director => t_movies_directors::table
+                .select(t_movies_directors::mid)
+                .filter(t_movies_directors::cid.eq(cid))
+                .load(conn),
+            CelebrityType::Writer => t_movies_writers::table
+                .select(t_movies_writers::mid)
+                .filter(t_movies_writers::cid.eq(cid))
+                .load(conn),
+        };
+        Ok(ids.map_err(map_internal)?)
+    }
+
+    /// Movies of the celebrity in the role, latest released first.
+    pub(in crate::movie::domain) fn movies(
+        &self,
+        role: CelebrityType,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Vec<Movie>> {
+        let ids = Self::movie_ids(self.id, role, conn)?;
+        let movies: Vec<Movie> = t_movies::table
+            .filter(t_movies::id.eq_any(ids))
+            .order((t_movies::released_date.desc(), t_movies::id.desc()))
+            .load(conn)
+            .map_err(map_internal)?;
+        Ok(movies)
+    }
+
+    pub(in crate::movie::domain) fn query_id(
+        cid: CelebrityId,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Celebrity> {
+        use migration::t_celebrities::dsl::*;
+
+        let cid = cid.as_i64().expect("Expect an i64 id");
+        let celebrity: Celebrity = t_celebrities
+            .find(cid)
+            .first(conn)
+            .map_err(map_not_found("celebrity", cid))?;
+        Ok(celebrity)
+    }
+
+    pub(in crate::movie::domain) fn query_imdb(
+        imdb_str: &str,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Celebrity> {
+        use migration::t_celebrities::dsl::*;
+
+        let celebrity: Celebrity = t_celebrities
+            .filter(imdb.eq(imdb_str))
+            .first(conn)
+            .map_err(map_not_found("celebrity", imdb_str))?;
+        Ok(celebrity)
+    }
+}
@@ -0,0 +1,112 @@
+use crate::movie::domain::celebrity::model::celebrity::Celebrity;
+use crate::movie::domain::celebrity::CelebrityType;
+use crate::movie::domain::search::Keyword;
+use crate::movie::domain::utils::{DEFAULT_LIMIT, MAX_LIMIT};
+use common::internal;
+use common::status::prelude::*;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use migration::{t_celebrities, t_movies_actors, t_movies_directors, t_movies_writers};
+use proto::pb::movie::celebrity::v1 as pb;
+
+/// Filters of listing celebrities, restricted to a role if any, in which case only
+/// celebrities credited in that role are listed.
+pub struct CelebrityFilter {
+    // matches name and English name, or pinyin of name
+    keyword: Option<Keyword>,
+    movie_id: Option<i64>,
+    role: Option<CelebrityType>,
+    limit: i64,
+    offset: i64,
+}
+
+impl CelebrityFilter {
+    pub(in crate::movie::domain) fn parse(
+        req: pb::ListReq,
+        role: Option<CelebrityType>,
+    ) -> GrpcResult<CelebrityFilter> {
+        let keyword = Keyword::parse(req.keyword)?;
+        let (limit, offset) = req
+            .slice
+            .unwrap_or_default()
+            .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+        Ok(CelebrityFilter {
+            keyword,
+            movie_id: req.movie_id,
+            role,
+            limit,
+            offset,
+        })
+    }
+
+    fn query(&self) -> t_celebrities::BoxedQuery<'_, Pg> {
+        use migration::t_celebrities::dsl::*;
+
+        let mut query = t_celebrities.into_boxed();
+        if let Some(keyword) = &self.keyword {
+            let matches = keyword.matches("t_celebrities");
+            query = match keyword.pinyin() {
+                Some(pattern) => query.filter(
+                    matches
+                        .or(pinyin.like(pattern))
+                        .or(pinyin_initials.like(pattern)),
+                ),
+                None => query.filter(matches),
+            };
+        }
+        let mut actors = t_movies_actors::table
+            .select(t_movies_actors::cid)
+            .into_boxed();
+        let mut directors = t_movies_directors::table
+            .select(t_movies_directors::cid)
+            .into_boxed();
+        let mut writers = t_movies_writers::table
+            .select(t_movies_writers::cid)
+            .into_boxed();
+        if let Some(mid) = self.movie_id {
+            actors = actors.filter(t_movies_actors::mid.eq(mid));
+            directors = directors.filter(t_movies_directors::mid.eq(mid));
+            writers = writers.filter(t_movies_writers::mid.eq(mid));
+        }
+        match self.role {
+            Some(CelebrityType::Actor) => query.filter(id.eq_any(actors)),
+            Some(CelebrityType::Director) => query.filter(id.eq_any(directors)),
+            Some(CelebrityType::Writer) => query.filter(id.eq_any(writers)),
+            // any role of the movie
+            None if self.movie_id.is_some() => query.filter(
+                id.eq_any(actors)
+                    .or(id.eq_any(directors))
+                    .or(id.eq_any(writers)),
+            ),
+            None => query,
+        }
+    }
+
+    pub(in crate::movie::domain) fn keyword(&self) -> Option<&Keyword> {
+        self.keyword.as_ref()
+    }
+
+    pub(in crate::movie::domain) fn list(
+        &self,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<Celebrity>, i64)> {
+        use migration::t_celebrities::dsl::*;
+
+        let map_err = |e| internal!(format!("Database connection error: {}", e));
+        let total: i64 = self.query().count().get_result(conn).map_err(map_err)?;
+        let query = match &self.keyword {
+            Some(keyword) => self
+                .query()
+                .order(keyword.rank("t_celebrities").desc())
+                .then_order_by(name.asc()),
+            None => self.query().order(name.asc()),
+        };
+        let celebrities: Vec<Celebrity> = query
+            .then_order_by(id.asc())
+            .limit(self.limit)
+            .offset(self.offset)
+            .load(conn)
+            .map_err(map_err)?;
+        Ok((celebrities, total))
+    }
+}
@@ -1,3 +1,3 @@
-pub mod movie;
 pub mod celebrity;
-pub mod score;
\ No newline at end of file
+pub mod filter;
+pub mod score;
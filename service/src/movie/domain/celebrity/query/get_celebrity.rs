@@ -0,0 +1,24 @@
+use crate::movie::domain::celebrity::model::celebrity::{Celebrity, CelebrityId};
+use crate::movie::rpc::MovieResolver;
+use common::invalid_argument;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::celebrity::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::GetReq, conn: &mut PgConnection) -> GrpcResult<pb::GetRes> {
+    // id is preferred if both are set
+    let celebrity = match (req.id, req.imdb) {
+        (Some(id), _) => Celebrity::query_id(CelebrityId::from(id as u64), conn)?,
+        (None, Some(imdb)) => Celebrity::query_imdb(&imdb, conn)?,
+        (None, None) => return Err(invalid_argument!("id", "id or imdb of the celebrity").into()),
+    };
+    Ok(celebrity.to_pb())
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_get_celebrity(&self) -> impl Query<pb::GetReq> + '_ {
+        move |req: pb::GetReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
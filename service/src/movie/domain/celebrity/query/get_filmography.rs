@@ -0,0 +1,37 @@
+use crate::movie::domain::celebrity::model::celebrity::{Celebrity, CelebrityId};
+use crate::movie::domain::celebrity::CelebrityType;
+use crate::movie::domain::movie::model::movie::Movie;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::celebrity::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::GetFilmographyReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::GetFilmographyRes> {
+    let celebrity = Celebrity::query_id(CelebrityId::from(req.id as u64), conn)?;
+    let mut movies = |role| -> GrpcResult<_> {
+        Ok(celebrity
+            .movies(role, conn)?
+            .iter()
+            .map(Movie::to_pb)
+            .collect())
+    };
+    Ok(pb::GetFilmographyRes {
+        id: req.id,
+        as_actor: movies(CelebrityType::Actor)?,
+        as_director: movies(CelebrityType::Director)?,
+        as_writer: movies(CelebrityType::Writer)?,
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_get_filmography(
+        &self,
+    ) -> impl Query<pb::GetFilmographyReq> + '_ {
+        move |req: pb::GetFilmographyReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
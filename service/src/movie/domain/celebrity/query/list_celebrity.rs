@@ -0,0 +1,59 @@
+use crate::movie::domain::celebrity::model::celebrity::Celebrity;
+use crate::movie::domain::celebrity::model::filter::CelebrityFilter;
+use crate::movie::domain::celebrity::CelebrityType;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::celebrity::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListReq,
+    role: Option<CelebrityType>,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListRes> {
+    let filter = CelebrityFilter::parse(req, role)?;
+    let (celebrities, total) = filter.list(conn)?;
+    let highlights = match filter.keyword() {
+        Some(keyword) => celebrities
+            .iter()
+            .filter_map(|celebrity| celebrity.highlight(keyword))
+            .collect(),
+        None => Default::default(),
+    };
+    Ok(pb::ListRes {
+        gets: celebrities.iter().map(Celebrity::to_pb).collect(),
+        total,
+        highlights,
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_celebrities(&self) -> impl Query<pb::ListReq> + '_ {
+        move |req: pb::ListReq| async move { execute(req, None, self.pg_conn().deref_mut()).await }
+    }
+
+    pub(in crate::movie) fn create_list_actors(&self) -> impl Query<pb::ListReq> + '_ {
+        move |req: pb::ListReq| async move {
+            execute(req, Some(CelebrityType::Actor), self.pg_conn().deref_mut()).await
+        }
+    }
+
+    pub(in crate::movie) fn create_list_directors(&self) -> impl Query<pb::ListReq> + '_ {
+        move |req: pb::ListReq| async move {
+            execute(
+                req,
+                Some(CelebrityType::Director),
+                self.pg_conn().deref_mut(),
+            )
+            .await
+        }
+    }
+
+    pub(in crate::movie) fn create_list_writers(&self) -> impl Query<pb::ListReq> + '_ {
+        move |req: pb::ListReq| async move {
+            execute(req, Some(CelebrityType::Writer), self.pg_conn().deref_mut()).await
+        }
+    }
+}
@@ -1,2 +1,3 @@
 pub mod get_celebrity;
-pub mod list_celebrity;
\ No newline at end of file
+pub mod get_filmography;
+pub mod list_celebrity;
@@ -0,0 +1,23 @@
+use crate::movie::domain::doulist::model::doulist::Doulist;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::doulist::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::CreateDoulistReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::CreateDoulistRes> {
+    let id = Doulist::create(req.uid, &req.title, &req.description, req.public, conn)?;
+    Ok(pb::CreateDoulistRes { id })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_create_doulist(
+        &self,
+    ) -> impl Command<pb::CreateDoulistReq> + '_ {
+        move |req: pb::CreateDoulistReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
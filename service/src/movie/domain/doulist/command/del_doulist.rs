@@ -0,0 +1,19 @@
+use crate::movie::domain::doulist::model::doulist::Doulist;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::doulist::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::DelDoulistReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Doulist::delete(req.id, req.uid, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_del_doulist(&self) -> impl Command<pb::DelDoulistReq> + '_ {
+        move |req: pb::DelDoulistReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
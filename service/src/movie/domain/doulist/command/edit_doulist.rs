@@ -0,0 +1,26 @@
+use crate::movie::domain::doulist::model::doulist::Doulist;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::doulist::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::EditDoulistReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Doulist::edit(
+        req.id,
+        req.uid,
+        &req.title,
+        &req.description,
+        req.public,
+        conn,
+    )?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_edit_doulist(&self) -> impl Command<pb::EditDoulistReq> + '_ {
+        move |req: pb::EditDoulistReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod add_item;
+pub mod create_doulist;
+pub mod del_doulist;
+pub mod edit_doulist;
+pub mod edit_item;
+pub mod follow_doulist;
+pub mod move_item;
+pub mod remove_item;
+pub mod unfollow_doulist;
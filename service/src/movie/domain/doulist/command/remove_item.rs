@@ -0,0 +1,19 @@
+use crate::movie::domain::doulist::model::item::Item;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::doulist::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::RemoveItemReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Item::remove(req.id, req.uid, req.movie_id, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_remove_item(&self) -> impl Command<pb::RemoveItemReq> + '_ {
+        move |req: pb::RemoveItemReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
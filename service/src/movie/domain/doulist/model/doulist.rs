@@ -0,0 +1,258 @@
+use crate::movie::domain::score::model::rating::check_user;
+use crate::movie::domain::utils::map_internal;
+use crate::user::domain::follow::model::block::Block;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{already_exists, internal, invalid_argument, not_found};
+use diesel::prelude::*;
+use migration::t_doulists;
+use proto::pb::movie::doulist::v1 as pb;
+use tonic::Status;
+
+const MAX_TITLE_LEN: usize = 64;
+const MAX_DESCRIPTION_LEN: usize = 2000;
+
+/// A doulist, `item_cnt` and `follower_cnt` are maintained by triggers.
+#[derive(Queryable)]
+pub struct Doulist {
+    id: i64,
+    uid: i64,
+    title: String,
+    description: String,
+    public: bool,
+    item_cnt: i64,
+    follower_cnt: i64,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = t_doulists)]
+struct PutDoulist<'a> {
+    title: &'a str,
+    description: &'a str,
+    public: bool,
+}
+
+// returns the trimmed title and description
+fn check_doulist<'a>(title: &'a str, description: &'a str) -> GrpcResult<(&'a str, &'a str)> {
+    let (title, description) = (title.trim(), description.trim());
+    if title.is_empty() || title.chars().count() > MAX_TITLE_LEN {
+        return Err(invalid_argument!("title", "1 to 64 characters").into());
+    }
+    if description.chars().count() > MAX_DESCRIPTION_LEN {
+        return Err(invalid_argument!("description", "no more than 2000 characters").into());
+    }
+    Ok((title, description))
+}
+
+impl Doulist {
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::Doulist {
+        pb::Doulist {
+            id: self.id,
+            uid: self.uid,
+            title: self.title.clone(),
+            description: self.description.clone(),
+            public: self.public,
+            item_cnt: self.item_cnt,
+            follower_cnt: self.follower_cnt,
+            created_at: self.created_at.timestamp(),
+            updated_at: self.updated_at.timestamp(),
+        }
+    }
+
+    /// Private doulists are only visible to the owner, and are not found by others.
+    pub(in crate::movie::domain) fn query_visible(
+        doulist_id: i64,
+        viewer: Option<i64>,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Doulist> {
+        use migration::t_doulists::dsl::*;
+
+        let doulist: Option<Doulist> = t_doulists
+            .find(doulist_id)
+            .first(conn)
+            .optional()
+            .map_err(map_internal)?;
+        let doulist = doulist
+            .filter(|doulist| doulist.public || Some(doulist.uid) == viewer)
+            .ok_or_else(|| not_found!(format!("doulist({})", doulist_id)))?;
+        // doulists of users blocked by or blocking the viewer are not found either
+        if let Some(viewer) = viewer {
+            if doulist.uid != viewer && Block::between(doulist.uid, viewer, conn)? {
+                return Err(not_found!(format!("doulist({})", doulist_id)).into());
+            }
+        }
+        Ok(doulist)
+    }
+
+    /// Lock the doulist owned by the user, so that changes of items are serialized.
+    pub(in crate::movie::domain) fn lock_owned(
+        doulist_id: i64,
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_doulists::dsl::*;
+
+        check_user(user_id, conn)?;
+        let owner: Option<i64> = t_doulists
+            .select(uid)
+            .find(doulist_id)
+            .for_update()
+            .first(conn)
+            .optional()
+            .map_err(map_internal)?;
+        match owner {
+            Some(owner) if owner == user_id => Ok(()),
+            Some(_) => Err(Status::permission_denied(format!(
+                "Doulist({}) is not owned by user({})",
+                doulist_id, user_id
+            ))
+            .into()),
+            None => Err(not_found!(format!("doulist({})", doulist_id)).into()),
+        }
+    }
+
+    /// Mark the doulist updated, i.e. when its items are changed.
+    pub(in crate::movie::domain) fn touch(
+        doulist_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_doulists::dsl::*;
+
+        diesel::update(t_doulists.find(doulist_id))
+            .set(updated_at.eq(diesel::dsl::now))
+            .execute(conn)
+            .map_err(map_internal)?;
+        Ok(())
+    }
+
+    pub(in crate::movie::domain) fn create(
+        user_id: i64,
+        title: &str,
+        description: &str,
+        public: bool,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<i64> {
+        use migration::t_doulists::dsl;
+
+        let (title, description) = check_doulist(title, description)?;
+        check_user(user_id, conn)?;
+        let doulist_id: i64 = diesel::insert_into(dsl::t_doulists)
+            .values((
+                dsl::uid.eq(user_id),
+                PutDoulist {
+                    title,
+                    description,
+                    public,
+                },
+            ))
+            .returning(dsl::id)
+            .get_result(conn)
+            .map_err(|e| internal!(format!("Cannot create doulist, err: {}", e)))?;
+        Ok(doulist_id)
+    }
+
+    pub(in crate::movie::domain) fn edit(
+        doulist_id: i64,
+        user_id: i64,
+        title: &str,
+        description: &str,
+        public: bool,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        let (title, description) = check_doulist(title, description)?;
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            Self::lock_owned(doulist_id, user_id, conn)?;
+            diesel::update(t_doulists::table.find(doulist_id))
+                .set(PutDoulist {
+                    title,
+                    description,
+                    public,
+                })
+                .execute(conn)
+                .map_err(map_internal)?;
+            Ok(())
+        })
+    }
+
+    /// Delete the doulist, items and follows are deleted by the cascading foreign keys.
+    pub(in crate::movie::domain) fn delete(
+        doulist_id: i64,
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            Self::lock_owned(doulist_id, user_id, conn)?;
+            diesel::delete(t_doulists::table.find(doulist_id))
+                .execute(conn)
+                .map_err(map_internal)?;
+            Ok(())
+        })
+    }
+
+    /// Follow a visible doulist of another user.
+    pub(in crate::movie::domain) fn follow(
+        doulist_id: i64,
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_doulists_follows::dsl::*;
+
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            let doulist = Self::query_visible(doulist_id, Some(user_id), conn)?;
+            if doulist.uid == user_id {
+                return Err(Status::failed_precondition(format!(
+                    "User({}) cannot follow own doulist({})",
+                    user_id, doulist_id
+                ))
+                .into());
+            }
+            let existed: Option<i64> = t_doulists_follows
+                .select(id)
+                .filter(did.eq(doulist_id))
+                .filter(uid.eq(user_id))
+                .first(conn)
+                .optional()
+                .map_err(map_internal)?;
+            if existed.is_some() {
+                return Err(already_exists!(format!(
+                    "follow of doulist({}) by user({})",
+                    doulist_id, user_id
+                ))
+                .into());
+            }
+            diesel::insert_into(t_doulists_follows)
+                .values((did.eq(doulist_id), uid.eq(user_id)))
+                .execute(conn)
+                .map_err(|e| internal!(format!("Cannot follow doulist, err: {}", e)))?;
+            Ok(())
+        })
+    }
+
+    pub(in crate::movie::domain) fn unfollow(
+        doulist_id: i64,
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_doulists_follows::dsl::*;
+
+        check_user(user_id, conn)?;
+        let deleted = diesel::delete(
+            t_doulists_follows
+                .filter(did.eq(doulist_id))
+                .filter(uid.eq(user_id)),
+        )
+        .execute(conn)
+        .map_err(map_internal)?;
+        if deleted == 0 {
+            return Err(not_found!(format!(
+                "follow of doulist({}) by user({})",
+                doulist_id, user_id
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::movie::domain::doulist::model::doulist::Doulist;
+use crate::movie::domain::score::model::rating::check_user;
+use crate::movie::domain::utils::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::user::domain::follow::model::block::Block;
+use common::status::prelude::*;
+use common::{internal, invalid_argument};
+use diesel::dsl::not;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use migration::{t_doulists, t_doulists_follows, t_doulists_items};
+use proto::pb::common::v1::Slice;
+
+/// Filters of listing doulists, of an owner, of a movie, or both. Private doulists are
+/// only listed to their owners, doulists of owners in `hidden` are left out.
+pub struct DoulistFilter {
+    uid: Option<i64>,
+    movie_id: Option<i64>,
+    viewer: Option<i64>,
+    hidden: Vec<i64>,
+    limit: i64,
+    offset: i64,
+}
+
+impl DoulistFilter {
+    pub(in crate::movie::domain) fn parse(
+        uid: Option<i64>,
+        movie_id: Option<i64>,
+        viewer: Option<i64>,
+        hidden: Vec<i64>,
+        slice: Option<Slice>,
+    ) -> GrpcResult<DoulistFilter> {
+        if uid.is_none() && movie_id.is_none() {
+            return Err(invalid_argument!("uid", "an owner id or a movie id").into());
+        }
+        let (limit, offset) = slice
+            .unwrap_or_default()
+            .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+        Ok(DoulistFilter {
+            uid,
+            movie_id,
+            viewer,
+            hidden,
+            limit,
+            offset,
+        })
+    }
+
+    fn query(&self) -> t_doulists::BoxedQuery<'_, Pg> {
+        use migration::t_doulists::dsl::*;
+
+        let mut query = t_doulists.into_boxed();
+        if let Some(owner) = self.uid {
+            query = query.filter(uid.eq(owner));
+        }
+        if let Some(movie_id) = self.movie_id {
+            query = query.filter(
+                id.eq_any(
+                    t_doulists_items::table
+                        .select(t_doulists_items::did)
+                        .filter(t_doulists_items::mid.eq(movie_id)),
+                ),
+            );
+        }
+        query = match self.viewer {
+            Some(viewer) => query.filter(public.or(uid.eq(viewer))),
+            None => query.filter(public),
+        };
+        query.filter(not(uid.eq_any(&self.hidden)))
+    }
+
+    pub(in crate::movie::domain) fn list(
+        &self,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<Doulist>, i64)> {
+        use migration::t_doulists::dsl::*;
+
+        let map_err = |e| internal!(format!("Database connection error: {}", e));
+        let total: i64 = self.query().count().get_result(conn).map_err(map_err)?;
+        let doulists: Vec<Doulist> = self
+            .query()
+            .order((updated_at.desc(), id.desc()))
+            .limit(self.limit)
+            .offset(self.offset)
+            .load(conn)
+            .map_err(map_err)?;
+        Ok((doulists, total))
+    }
+
+    /// Doulists followed by the user, the latest followed first.
+    pub(in crate::movie::domain) fn list_followed(
+        user_id: i64,
+        slice: Option<Slice>,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<Doulist>, i64)> {
+        let map_err = |e| internal!(format!("Database connection error: {}", e));
+        let (limit, offset) = slice
+            .unwrap_or_default()
+            .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+        check_user(user_id, conn)?;
+        let hidden = Block::hidden_from(user_id, conn)?;
+        let query = || {
+            t_doulists::table
+                .inner_join(t_doulists_follows::table)
+                .filter(t_doulists_follows::uid.eq(user_id))
+                .filter(t_doulists::public)
+                .filter(not(t_doulists::uid.eq_any(&hidden)))
+        };
+        let total: i64 = query().count().get_result(conn).map_err(map_err)?;
+        let doulists: Vec<Doulist> = query()
+            .select(t_doulists::all_columns)
+            .order((
+                t_doulists_follows::created_at.desc(),
+                t_doulists_follows::id.desc(),
+            ))
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+            .map_err(map_err)?;
+        Ok((doulists, total))
+    }
+}
@@ -0,0 +1,230 @@
+use crate::movie::domain::doulist::model::doulist::Doulist;
+use crate::movie::domain::movie::model::movie::{Movie, MovieId};
+use crate::movie::domain::utils::map_internal;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{already_exists, internal, invalid_argument, not_found};
+use diesel::dsl::max;
+use diesel::prelude::*;
+use proto::pb::movie::doulist::v1 as pb;
+use tonic::Status;
+
+const MAX_ITEMS: i64 = 1000;
+const MAX_NOTE_LEN: usize = 350;
+
+/// A movie in a doulist.
+#[derive(Queryable)]
+pub struct Item {
+    id: i64,
+    did: i64,
+    mid: i64,
+    position: i32,
+    note: Option<String>,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+// returns the trimmed note, a blank note is no note
+fn check_note(note: Option<&str>) -> GrpcResult<Option<&str>> {
+    let note = note.map(str::trim).filter(|note| !note.is_empty());
+    if note.map_or(false, |note| note.chars().count() > MAX_NOTE_LEN) {
+        return Err(invalid_argument!("note", "no more than 350 characters").into());
+    }
+    Ok(note)
+}
+
+impl Item {
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::DoulistItem {
+        pb::DoulistItem {
+            movie_id: self.mid,
+            position: self.position,
+            note: self.note.clone(),
+            added_at: self.created_at.timestamp(),
+        }
+    }
+
+    /// Items of a visible doulist in order.
+    pub(in crate::movie::domain) fn list(
+        doulist_id: i64,
+        viewer: Option<i64>,
+        limit: i64,
+        offset: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<Item>, i64)> {
+        use migration::t_doulists_items::dsl::*;
+
+        Doulist::query_visible(doulist_id, viewer, conn)?;
+        let total: i64 = t_doulists_items
+            .filter(did.eq(doulist_id))
+            .count()
+            .get_result(conn)
+            .map_err(map_internal)?;
+        let items: Vec<Item> = t_doulists_items
+            .filter(did.eq(doulist_id))
+            .order((position.asc(), id.asc()))
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+            .map_err(map_internal)?;
+        Ok((items, total))
+    }
+
+    /// Append the movie to the doulist.
+    pub(in crate::movie::domain) fn add(
+        doulist_id: i64,
+        user_id: i64,
+        movie_id: i64,
+        new_note: Option<&str>,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_doulists_items::dsl::*;
+
+        let new_note = check_note(new_note)?;
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            Doulist::lock_owned(doulist_id, user_id, conn)?;
+            Movie::query_id(MovieId::from(movie_id as u64), conn)?;
+            let existed: Option<i64> = t_doulists_items
+                .select(id)
+                .filter(did.eq(doulist_id))
+                .filter(mid.eq(movie_id))
+                .first(conn)
+                .optional()
+                .map_err(map_internal)?;
+            if existed.is_some() {
+                return Err(already_exists!(format!(
+                    "movie({}) in doulist({})",
+                    movie_id, doulist_id
+                ))
+                .into());
+            }
+            let items: i64 = t_doulists_items
+                .filter(did.eq(doulist_id))
+                .count()
+                .get_result(conn)
+                .map_err(map_internal)?;
+            if items >= MAX_ITEMS {
+                return Err(Status::failed_precondition(format!(
+                    "Doulist({}) is full of {} movies",
+                    doulist_id, MAX_ITEMS
+                ))
+                .into());
+            }
+            let last: Option<i32> = t_doulists_items
+                .select(max(position))
+                .filter(did.eq(doulist_id))
+                .first(conn)
+                .map_err(map_internal)?;
+            diesel::insert_into(t_doulists_items)
+                .values((
+                    did.eq(doulist_id),
+                    mid.eq(movie_id),
+                    position.eq(last.map_or(0, |last| last + 1)),
+                    note.eq(new_note),
+                ))
+                .execute(conn)
+                .map_err(|e| internal!(format!("Cannot add item, err: {}", e)))?;
+            Doulist::touch(doulist_id, conn)
+        })
+    }
+
+    /// Replace the note of the item.
+    pub(in crate::movie::domain) fn edit(
+        doulist_id: i64,
+        user_id: i64,
+        movie_id: i64,
+        new_note: Option<&str>,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_doulists_items::dsl::*;
+
+        let new_note = check_note(new_note)?;
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            Doulist::lock_owned(doulist_id, user_id, conn)?;
+            let updated = diesel::update(
+                t_doulists_items
+                    .filter(did.eq(doulist_id))
+                    .filter(mid.eq(movie_id)),
+            )
+            .set(note.eq(new_note))
+            .execute(conn)
+            .map_err(map_internal)?;
+            if updated == 0 {
+                return Err(
+                    not_found!(format!("movie({}) in doulist({})", movie_id, doulist_id)).into(),
+                );
+            }
+            Doulist::touch(doulist_id, conn)
+        })
+    }
+
+    /// Move the item to the position, the items are renumbered from 0 on the way, so
+    /// that gaps left by removed items are closed.
+    pub(in crate::movie::domain) fn move_to(
+        doulist_id: i64,
+        user_id: i64,
+        movie_id: i64,
+        new_position: i32,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_doulists_items::dsl::*;
+
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            Doulist::lock_owned(doulist_id, user_id, conn)?;
+            let mut items: Vec<(i64, i32)> = t_doulists_items
+                .select((mid, position))
+                .filter(did.eq(doulist_id))
+                .order((position.asc(), id.asc()))
+                .load(conn)
+                .map_err(map_internal)?;
+            let index = items
+                .iter()
+                .position(|(item, _)| *item == movie_id)
+                .ok_or_else(|| {
+                    not_found!(format!("movie({}) in doulist({})", movie_id, doulist_id))
+                })?;
+            let item = items.remove(index);
+            let new_position = (new_position.max(0) as usize).min(items.len());
+            items.insert(new_position, item);
+            for (index, (item, old_position)) in items.into_iter().enumerate() {
+                if old_position == index as i32 {
+                    continue;
+                }
+                diesel::update(
+                    t_doulists_items
+                        .filter(did.eq(doulist_id))
+                        .filter(mid.eq(item)),
+                )
+                .set(position.eq(index as i32))
+                .execute(conn)
+                .map_err(map_internal)?;
+            }
+            Doulist::touch(doulist_id, conn)
+        })
+    }
+
+    pub(in crate::movie::domain) fn remove(
+        doulist_id: i64,
+        user_id: i64,
+        movie_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_doulists_items::dsl::*;
+
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            Doulist::lock_owned(doulist_id, user_id, conn)?;
+            let deleted = diesel::delete(
+                t_doulists_items
+                    .filter(did.eq(doulist_id))
+                    .filter(mid.eq(movie_id)),
+            )
+            .execute(conn)
+            .map_err(map_internal)?;
+            if deleted == 0 {
+                return Err(
+                    not_found!(format!("movie({}) in doulist({})", movie_id, doulist_id)).into(),
+                );
+            }
+            Doulist::touch(doulist_id, conn)
+        })
+    }
+}
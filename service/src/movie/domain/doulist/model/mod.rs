@@ -0,0 +1,3 @@
+pub mod doulist;
+pub mod filter;
+pub mod item;
@@ -0,0 +1,20 @@
+use crate::movie::domain::doulist::model::doulist::Doulist;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::doulist::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::GetDoulistReq, conn: &mut PgConnection) -> GrpcResult<pb::GetDoulistRes> {
+    let doulist = Doulist::query_visible(req.id, req.viewer, conn)?;
+    Ok(pb::GetDoulistRes {
+        doulist: Some(doulist.to_pb()),
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_get_doulist(&self) -> impl Query<pb::GetDoulistReq> + '_ {
+        move |req: pb::GetDoulistReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::movie::domain::doulist::model::doulist::Doulist;
+use crate::movie::domain::doulist::model::filter::DoulistFilter;
+use crate::movie::rpc::MovieResolver;
+use crate::user::domain::follow::model::block::Block;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::doulist::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListDoulistsReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListDoulistsRes> {
+    let hidden = match req.viewer {
+        Some(viewer) => Block::hidden_from(viewer, conn)?,
+        None => vec![],
+    };
+    let filter = DoulistFilter::parse(req.uid, req.movie_id, req.viewer, hidden, req.slice)?;
+    let (doulists, total) = filter.list(conn)?;
+    Ok(pb::ListDoulistsRes {
+        doulists: doulists.iter().map(Doulist::to_pb).collect(),
+        total,
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_doulists(&self) -> impl Query<pb::ListDoulistsReq> + '_ {
+        move |req: pb::ListDoulistsReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
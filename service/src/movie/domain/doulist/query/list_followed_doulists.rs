@@ -0,0 +1,29 @@
+use crate::movie::domain::doulist::model::doulist::Doulist;
+use crate::movie::domain::doulist::model::filter::DoulistFilter;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::doulist::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListFollowedDoulistsReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListFollowedDoulistsRes> {
+    let (doulists, total) = DoulistFilter::list_followed(req.uid, req.slice, conn)?;
+    Ok(pb::ListFollowedDoulistsRes {
+        doulists: doulists.iter().map(Doulist::to_pb).collect(),
+        total,
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_followed_doulists(
+        &self,
+    ) -> impl Query<pb::ListFollowedDoulistsReq> + '_ {
+        move |req: pb::ListFollowedDoulistsReq| async move {
+            execute(req, self.pg_conn().deref_mut()).await
+        }
+    }
+}
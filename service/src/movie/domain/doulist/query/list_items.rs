@@ -0,0 +1,28 @@
+use crate::movie::domain::doulist::model::item::Item;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::doulist::v1 as pb;
+use std::ops::DerefMut;
+
+const DEFAULT_LIMIT: i64 = 25;
+const MAX_LIMIT: i64 = 100;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::ListItemsReq, conn: &mut PgConnection) -> GrpcResult<pb::ListItemsRes> {
+    let (limit, offset) = req
+        .slice
+        .unwrap_or_default()
+        .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+    let (items, total) = Item::list(req.id, req.viewer, limit, offset, conn)?;
+    Ok(pb::ListItemsRes {
+        items: items.iter().map(Item::to_pb).collect(),
+        total,
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_items(&self) -> impl Query<pb::ListItemsReq> + '_ {
+        move |req: pb::ListItemsReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
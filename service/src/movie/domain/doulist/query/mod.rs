@@ -0,0 +1,4 @@
+pub mod get_doulist;
+pub mod list_doulists;
+pub mod list_followed_doulists;
+pub mod list_items;
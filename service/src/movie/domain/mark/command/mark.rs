@@ -0,0 +1,27 @@
+use crate::movie::domain::mark::model::mark::Mark;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::mark::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::MarkReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Mark::mark(
+        req.uid,
+        req.movie_id,
+        req.status,
+        &req.tags,
+        req.star,
+        req.comment.as_deref(),
+        conn,
+    )?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_mark(&self) -> impl Command<pb::MarkReq> + '_ {
+        move |req: pb::MarkReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,19 @@
+use crate::movie::domain::mark::model::mark::Mark;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::mark::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::UnmarkReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Mark::unmark(req.uid, req.movie_id, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_unmark(&self) -> impl Command<pb::UnmarkReq> + '_ {
+        move |req: pb::UnmarkReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::NaiveDateTime;
+use common::internal;
+use common::status::prelude::*;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer};
+use proto::pb::movie::mark::v1 as pb;
+
+/// Counts of marks of a movie by status, i.e. how many people want to watch it.
+#[derive(Queryable)]
+pub struct MarkCount {
+    id: i64,
+    mid: i64,
+    wish_cnt: i64,
+    watching_cnt: i64,
+    watched_cnt: i64,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+impl MarkCount {
+    pub(in crate::movie::domain) fn query_mid(
+        movie_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Option<MarkCount>> {
+        use migration::t_movies_marks::dsl::*;
+
+        let count: Option<MarkCount> = t_movies_marks
+            .filter(mid.eq(movie_id))
+            .first(conn)
+            .optional()
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        Ok(count)
+    }
+
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::GetMarkCountRes {
+        pb::GetMarkCountRes {
+            wish_cnt: self.wish_cnt,
+            watching_cnt: self.watching_cnt,
+            watched_cnt: self.watched_cnt,
+        }
+    }
+
+    /// Add `delta` to the count of the status, the row is created on the first mark.
+    pub(in crate::movie::domain) fn bump(
+        movie_id: i64,
+        status: pb::MarkStatus,
+        delta: i32,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        let column = match status {
+            pb::MarkStatus::Wish => "wish_cnt",
+            pb::MarkStatus::Watching => "watching_cnt",
+            pb::MarkStatus::Watched => "watched_cnt",
+        };
+        diesel::sql_query(format!(
+            "INSERT INTO t_movies_marks (mid, {column}) VALUES ($1, greatest($2, 0)) \
+             ON CONFLICT (mid) DO UPDATE SET {column} = greatest(t_movies_marks.{column} + $2, 0)",
+            column = column
+        ))
+        .bind::<BigInt, _>(movie_id)
+        .bind::<Integer, _>(delta)
+        .execute(conn)
+        .map_err(|e| {
+            internal!(format!(
+                "Cannot update marks of movie({}), err: {}",
+                movie_id, e
+            ))
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,265 @@
+use crate::movie::domain::mark::model::count::MarkCount;
+use crate::movie::domain::movie::model::movie::{Movie, MovieId};
+use crate::movie::domain::score::model::rating::{check_user, Rating};
+use crate::movie::domain::tag::model::tag::{check_tags, normalize, TagCount};
+use crate::movie::domain::utils::map_internal;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{internal, invalid_argument, not_found};
+use diesel::dsl::now;
+use diesel::prelude::*;
+use proto::pb::movie::mark::v1 as pb;
+
+#[derive(Queryable)]
+pub struct Mark {
+    id: i64,
+    uid: i64,
+    mid: i64,
+    status: String,
+    tags: Vec<String>,
+    marked_at: NaiveDateTime,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+#[inline]
+fn status_name(status: pb::MarkStatus) -> &'static str {
+    match status {
+        pb::MarkStatus::Wish => "wish",
+        pb::MarkStatus::Watching => "watching",
+        pb::MarkStatus::Watched => "watched",
+    }
+}
+
+#[inline]
+fn status_of(name: &str) -> pb::MarkStatus {
+    match name {
+        "watching" => pb::MarkStatus::Watching,
+        "watched" => pb::MarkStatus::Watched,
+        _ => pb::MarkStatus::Wish,
+    }
+}
+
+#[inline]
+fn parse_status(status: i32) -> GrpcResult<pb::MarkStatus> {
+    pb::MarkStatus::from_i32(status)
+        .ok_or_else(|| invalid_argument!("status", "WISH, WATCHING or WATCHED").into())
+}
+
+impl Mark {
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::Mark {
+        pb::Mark {
+            movie_id: self.mid,
+            status: status_of(&self.status) as i32,
+            tags: self.tags.clone(),
+            marked_at: self.marked_at.timestamp(),
+        }
+    }
+
+    /// Mark the movie, the previous mark of the user is replaced and the counts of the
+    /// movie are updated in the same transaction. A watched movie may be rated along
+    /// with the mark, the existing comment of the rating is kept without a new one.
+    pub(in crate::movie::domain) fn mark(
+        user_id: i64,
+        movie_id: i64,
+        new_status: i32,
+        new_tags: &[String],
+        star: Option<i32>,
+        comment: Option<&str>,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        let new_status = parse_status(new_status)?;
+        if new_status != pb::MarkStatus::Watched && (star.is_some() || comment.is_some()) {
+            return Err(invalid_argument!("star", "only for WATCHED").into());
+        }
+        if star.is_none() && comment.is_some() {
+            return Err(invalid_argument!("star", "required along with comment").into());
+        }
+        let new_tags = check_tags(new_tags)?;
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            Movie::query_id(MovieId::from(movie_id as u64), conn)?;
+            if !Self::insert(user_id, movie_id, new_status, &new_tags, conn)? {
+                Self::remark(user_id, movie_id, new_status, &new_tags, conn)?;
+            }
+            if let Some(star) = star {
+                // the existing comment is kept when the mark comes with stars only
+                let comment = match comment {
+                    Some(comment) => Some(comment.to_string()),
+                    None => Rating::query(user_id, movie_id, conn)?
+                        .and_then(|rating| rating.comment().map(ToString::to_string)),
+                };
+                Rating::rate(user_id, movie_id, star, comment.as_deref(), conn)?;
+            }
+            Ok(())
+        })
+    }
+
+    // the previous mark of the user is locked and replaced
+    fn remark(
+        user_id: i64,
+        movie_id: i64,
+        new_status: pb::MarkStatus,
+        new_tags: &[String],
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_marks::dsl::*;
+
+        let (old_status, old_tags): (String, Vec<String>) = t_user_marks
+            .select((status, tags))
+            .filter(uid.eq(user_id))
+            .filter(mid.eq(movie_id))
+            .for_update()
+            .first(conn)
+            .map_err(map_internal)?;
+        let query = t_user_marks
+            .filter(uid.eq(user_id))
+            .filter(mid.eq(movie_id));
+        // only tags are changed, the movie is not marked again
+        if status_of(&old_status) == new_status {
+            diesel::update(query)
+                .set(tags.eq(new_tags))
+                .execute(conn)
+                .map_err(map_internal)?;
+        } else {
+            diesel::update(query)
+                .set((
+                    status.eq(status_name(new_status)),
+                    tags.eq(new_tags),
+                    marked_at.eq(now),
+                ))
+                .execute(conn)
+                .map_err(map_internal)?;
+            MarkCount::bump(movie_id, status_of(&old_status), -1, conn)?;
+            MarkCount::bump(movie_id, new_status, 1, conn)?;
+        }
+        TagCount::retag(user_id, movie_id, &old_tags, new_tags, conn)
+    }
+
+    // returns false when the movie is already marked by the user, a concurrent first
+    // mark conflicts on the unique index as well
+    fn insert(
+        user_id: i64,
+        movie_id: i64,
+        new_status: pb::MarkStatus,
+        new_tags: &[String],
+        conn: &mut PgConnection,
+    ) -> GrpcResult<bool> {
+        use migration::t_user_marks::dsl::*;
+
+        let inserted = diesel::insert_into(t_user_marks)
+            .values((
+                uid.eq(user_id),
+                mid.eq(movie_id),
+                status.eq(status_name(new_status)),
+                tags.eq(new_tags),
+            ))
+            .on_conflict((uid, mid))
+            .do_nothing()
+            .execute(conn)
+            .map_err(|e| internal!(format!("Cannot mark movie, err: {}", e)))?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+        MarkCount::bump(movie_id, new_status, 1, conn)?;
+        TagCount::retag(user_id, movie_id, &[], new_tags, conn)?;
+        Ok(true)
+    }
+
+    /// Replace tags of the mark, a movie not marked yet is marked as watched, since
+    /// tags are attached when rating as well.
+    pub(in crate::movie::domain) fn retag(
+        user_id: i64,
+        movie_id: i64,
+        new_tags: &[String],
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_marks::dsl::*;
+
+        let new_tags = check_tags(new_tags)?;
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            Movie::query_id(MovieId::from(movie_id as u64), conn)?;
+            if Self::insert(user_id, movie_id, pb::MarkStatus::Watched, &new_tags, conn)? {
+                return Ok(());
+            }
+            let old_tags: Vec<String> = t_user_marks
+                .select(tags)
+                .filter(uid.eq(user_id))
+                .filter(mid.eq(movie_id))
+                .for_update()
+                .first(conn)
+                .map_err(map_internal)?;
+            diesel::update(
+                t_user_marks
+                    .filter(uid.eq(user_id))
+                    .filter(mid.eq(movie_id)),
+            )
+            .set(tags.eq(&new_tags))
+            .execute(conn)
+            .map_err(map_internal)?;
+            TagCount::retag(user_id, movie_id, &old_tags, &new_tags, conn)
+        })
+    }
+
+    /// Unmark the movie, the rating of the movie is kept.
+    pub(in crate::movie::domain) fn unmark(
+        user_id: i64,
+        movie_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_marks::dsl::*;
+
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            let previous: Option<(String, Vec<String>)> = diesel::delete(
+                t_user_marks
+                    .filter(uid.eq(user_id))
+                    .filter(mid.eq(movie_id)),
+            )
+            .returning((status, tags))
+            .get_result(conn)
+            .optional()
+            .map_err(map_internal)?;
+            let (old_status, old_tags) = previous.ok_or_else(|| {
+                not_found!(format!("mark of movie({}) by user({})", movie_id, user_id))
+            })?;
+            MarkCount::bump(movie_id, status_of(&old_status), -1, conn)?;
+            TagCount::retag(user_id, movie_id, &old_tags, &[], conn)
+        })
+    }
+
+    /// Marks of the user, the latest marked first.
+    pub(in crate::movie::domain) fn list(
+        user_id: i64,
+        by_status: Option<i32>,
+        by_tag: Option<&str>,
+        limit: i64,
+        offset: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<Mark>, i64)> {
+        use migration::t_user_marks::dsl::*;
+
+        let by_status = by_status.map(parse_status).transpose()?;
+        let by_tag = by_tag.map(normalize).filter(|tag| !tag.is_empty());
+        check_user(user_id, conn)?;
+        let filtered = || {
+            let mut query = t_user_marks.filter(uid.eq(user_id)).into_boxed();
+            if let Some(by_status) = by_status {
+                query = query.filter(status.eq(status_name(by_status)));
+            }
+            if let Some(by_tag) = by_tag {
+                query = query.filter(tags.contains(vec![by_tag.clone()]));
+            }
+            query
+        };
+        let total: i64 = filtered().count().get_result(conn).map_err(map_internal)?;
+        let marks: Vec<Mark> = filtered()
+            .order((marked_at.desc(), id.desc()))
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+            .map_err(map_internal)?;
+        Ok((marks, total))
+    }
+}
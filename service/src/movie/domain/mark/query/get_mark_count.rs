@@ -0,0 +1,29 @@
+use crate::movie::domain::mark::model::count::MarkCount;
+use crate::movie::domain::movie::model::movie::{Movie, MovieId};
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::mark::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::GetMarkCountReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::GetMarkCountRes> {
+    let count = match MarkCount::query_mid(req.movie_id, conn)? {
+        Some(count) => count.to_pb(),
+        None => {
+            // a movie nobody has marked yet
+            Movie::query_id(MovieId::from(req.movie_id as u64), conn)?;
+            pb::GetMarkCountRes::default()
+        }
+    };
+    Ok(count)
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_get_mark_count(&self) -> impl Query<pb::GetMarkCountReq> + '_ {
+        move |req: pb::GetMarkCountReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
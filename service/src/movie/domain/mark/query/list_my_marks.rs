@@ -0,0 +1,29 @@
+use crate::movie::domain::mark::model::mark::Mark;
+use crate::movie::domain::utils::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::mark::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListMyMarksReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListMyMarksRes> {
+    let (limit, offset) = req
+        .slice
+        .unwrap_or_default()
+        .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+    let (marks, total) = Mark::list(req.uid, req.status, req.tag.as_deref(), limit, offset, conn)?;
+    Ok(pb::ListMyMarksRes {
+        marks: marks.iter().map(Mark::to_pb).collect(),
+        total,
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_my_marks(&self) -> impl Query<pb::ListMyMarksReq> + '_ {
+        move |req: pb::ListMyMarksReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
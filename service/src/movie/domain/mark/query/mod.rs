@@ -0,0 +1,2 @@
+pub mod get_mark_count;
+pub mod list_my_marks;
@@ -0,0 +1 @@
+pub mod upload_image;
@@ -0,0 +1,181 @@
+use crate::movie::domain::media::model::blob::BlobStore;
+use crate::movie::domain::media::model::image::{content_type, Image};
+use crate::movie::rpc::{MediaConfig, MovieResolver};
+use common::internal;
+use common::{infra::Command, status::prelude::*};
+use proto::pb::movie::media::v1 as pb;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::UploadImageReq,
+    conf: &MediaConfig,
+    store: &dyn BlobStore,
+) -> GrpcResult<pb::UploadImageRes> {
+    let image = Image::parse(req.kind(), req.chunk, conf)?;
+    let variants = image.variants(conf);
+    let original_key = image.original_key();
+    // the original is put after its variants, so that a stored original has them all
+    if store.exists(&original_key).await? {
+        return Ok(image.to_pb(&variants, conf));
+    }
+
+    let quality = conf.quality;
+    let (image, variants, rendered) = tokio::task::spawn_blocking(move || {
+        let rendered = image.render(&variants, quality);
+        (image, variants, rendered)
+    })
+    .await
+    .map_err(|e| internal!(format!("Rendering image panicked, err: {}", e)))?;
+    for (variant, content) in variants.iter().zip(rendered?) {
+        let key = variant.key();
+        store
+            .put(key, content_type(key).unwrap_or_default(), content)
+            .await?;
+    }
+    store
+        .put(
+            &original_key,
+            content_type(&original_key).unwrap_or_default(),
+            image.content().to_vec(),
+        )
+        .await?;
+    Ok(image.to_pb(&variants, conf))
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_upload_image(&self) -> impl Command<pb::UploadImageReq> + '_ {
+        move |req: pb::UploadImageReq| async move {
+            execute(req, self.media_conf(), self.blob_store()).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movie::domain::media::model::blob::MemoryBlobStore;
+    use image::{DynamicImage, ImageOutputFormat, RgbImage};
+    use std::io::Cursor;
+
+    fn encode(width: u32, height: u32, format: ImageOutputFormat) -> Vec<u8> {
+        let mut buf = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(width, height))
+            .write_to(&mut Cursor::new(&mut buf), format)
+            .unwrap();
+        buf
+    }
+
+    fn req(chunk: Vec<u8>) -> pb::UploadImageReq {
+        pb::UploadImageReq {
+            kind: pb::ImageKind::Poster as i32,
+            chunk,
+        }
+    }
+
+    fn key<'a>(url: &'a str, conf: &MediaConfig) -> &'a str {
+        url.strip_prefix(&format!("{}/", conf.public_url)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn variants() {
+        let conf = MediaConfig::default();
+        let store = MemoryBlobStore::default();
+        let res = execute(req(encode(800, 600, ImageOutputFormat::Png)), &conf, &store)
+            .await
+            .unwrap();
+        assert_eq!((res.width, res.height), (800, 600));
+        assert_eq!(res.content_type, "image/png");
+        let original = key(&res.url, &conf);
+        assert!(original.starts_with("poster/") && original.ends_with(".png"));
+        assert!(store.exists(original).await.unwrap());
+
+        let variants: Vec<_> = res
+            .variants
+            .iter()
+            .map(|v| (v.name.as_str(), v.width, v.height, v.content_type.as_str()))
+            .collect();
+        assert_eq!(
+            variants,
+            [
+                ("webp", 800, 600, "image/webp"),
+                ("160", 160, 120, "image/png"),
+                ("160.webp", 160, 120, "image/webp"),
+                ("320", 320, 240, "image/png"),
+                ("320.webp", 320, 240, "image/webp"),
+                ("640", 640, 480, "image/png"),
+                ("640.webp", 640, 480, "image/webp"),
+            ]
+        );
+        for variant in &res.variants {
+            assert!(store.exists(key(&variant.url, &conf)).await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn thumbnails_of_jpeg() {
+        let conf = MediaConfig::default();
+        let store = MemoryBlobStore::default();
+        let res = execute(
+            req(encode(400, 200, ImageOutputFormat::Jpeg(80))),
+            &conf,
+            &store,
+        )
+        .await
+        .unwrap();
+        assert!(res.url.ends_with(".jpg"));
+        let names: Vec<_> = res.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["webp", "160", "160.webp", "320", "320.webp"]);
+        assert!(res.variants[1].url.ends_with("_160.jpg"));
+        assert!(res.variants[2].url.ends_with("_160.webp"));
+    }
+
+    #[tokio::test]
+    async fn no_thumbnails_of_small_images() {
+        let conf = MediaConfig::default();
+        let store = MemoryBlobStore::default();
+        let res = execute(req(encode(160, 90, ImageOutputFormat::Png)), &conf, &store)
+            .await
+            .unwrap();
+        let names: Vec<_> = res.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["webp"]);
+    }
+
+    #[tokio::test]
+    async fn dedup_by_hash() {
+        let conf = MediaConfig::default();
+        let store = MemoryBlobStore::default();
+        let chunk = encode(800, 600, ImageOutputFormat::Png);
+        let first = execute(req(chunk.clone()), &conf, &store).await.unwrap();
+
+        // a stored original is not rendered again
+        let thumbnail = key(&first.variants[1].url, &conf);
+        store.put(thumbnail, "image/png", vec![0]).await.unwrap();
+        let second = execute(req(chunk), &conf, &store).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(store.get(thumbnail).await.unwrap(), Some(vec![0]));
+
+        // the same content is keyed the same, another one is not
+        let other = execute(req(encode(800, 601, ImageOutputFormat::Png)), &conf, &store)
+            .await
+            .unwrap();
+        assert_ne!(first.url, other.url);
+    }
+
+    #[tokio::test]
+    async fn invalid_images() {
+        let conf = MediaConfig::default();
+        let store = MemoryBlobStore::default();
+        for chunk in [vec![], b"not an image".to_vec()] {
+            let err = execute(req(chunk), &conf, &store).await.unwrap_err();
+            assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        }
+        let large = MediaConfig {
+            max_dimension: 100,
+            ..MediaConfig::default()
+        };
+        let err = execute(req(encode(101, 10, ImageOutputFormat::Png)), &large, &store)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,324 @@
+use crate::movie::rpc::S3Config;
+use common::internal;
+use common::status::prelude::*;
+use parking_lot::Mutex;
+use s3::bucket::Bucket;
+use s3::creds::Credentials;
+use s3::error::S3Error;
+use s3::region::Region;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Storage of media blobs by key. Keys are relative paths made of lowercase letters,
+/// digits, `_`, `.` and `/`, see [`check_key`], so that a key is a valid path of the
+/// local filesystem and a valid object name of S3.
+#[tonic::async_trait]
+pub trait BlobStore: Send + Sync {
+    async fn put(&self, key: &str, content_type: &str, content: Vec<u8>) -> GrpcResult<()>;
+
+    // none if the key does not exist
+    async fn get(&self, key: &str) -> GrpcResult<Option<Vec<u8>>>;
+
+    async fn exists(&self, key: &str) -> GrpcResult<bool>;
+}
+
+/// Check a key from the outside, keys never escape the root of a store.
+pub(in crate::movie) fn check_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 256
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '.' | '/'))
+        && key
+            .split('/')
+            .all(|part| !part.is_empty() && !part.starts_with('.'))
+}
+
+/// Blobs as files under a root directory, used for a single instance deployment or
+/// local development.
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: &str) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[tonic::async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, _content_type: &str, content: Vec<u8>) -> GrpcResult<()> {
+        let path = self.root.join(key);
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .map_err(|e| internal!(format!("Cannot create dir for {}, err: {}", key, e)))?;
+        }
+        // written aside and renamed, a reader never sees a partial blob
+        let tmp = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4().simple()));
+        tokio::fs::write(&tmp, content)
+            .await
+            .map_err(|e| internal!(format!("Cannot write blob {}, err: {}", key, e)))?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .map_err(|e| internal!(format!("Cannot write blob {}, err: {}", key, e)))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> GrpcResult<Option<Vec<u8>>> {
+        match tokio::fs::read(self.root.join(key)).await {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(internal!(format!("Cannot read blob {}, err: {}", key, e)).into()),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> GrpcResult<bool> {
+        match tokio::fs::metadata(self.root.join(key)).await {
+            Ok(_) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(internal!(format!("Cannot stat blob {}, err: {}", key, e)).into()),
+        }
+    }
+}
+
+/// In-process store, blobs are lost on restart, used for trying out the service or tests.
+#[derive(Default)]
+pub struct MemoryBlobStore {
+    blobs: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+#[tonic::async_trait]
+impl BlobStore for MemoryBlobStore {
+    async fn put(&self, key: &str, _content_type: &str, content: Vec<u8>) -> GrpcResult<()> {
+        self.blobs.lock().insert(key.to_string(), content);
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> GrpcResult<Option<Vec<u8>>> {
+        Ok(self.blobs.lock().get(key).cloned())
+    }
+
+    async fn exists(&self, key: &str) -> GrpcResult<bool> {
+        Ok(self.blobs.lock().contains_key(key))
+    }
+}
+
+/// Blobs as objects of a bucket of S3 or an S3 compatible storage, i.e. MinIO, which
+/// can be started locally to try it out.
+pub struct S3BlobStore {
+    bucket: Bucket,
+}
+
+impl S3BlobStore {
+    /// Fails on invalid credentials or bucket, nothing is requested to the storage.
+    pub fn new(conf: &S3Config) -> Result<Self, S3Error> {
+        let region = Region::Custom {
+            region: conf.region.clone(),
+            endpoint: conf.endpoint.clone(),
+        };
+        let credentials = Credentials::new(
+            Some(&conf.access_key),
+            Some(&conf.secret_key),
+            None,
+            None,
+            None,
+        )?;
+        let bucket = Bucket::new(&conf.bucket, region, credentials)?;
+        Ok(Self {
+            bucket: if conf.path_style {
+                bucket.with_path_style()
+            } else {
+                bucket
+            },
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, content_type: &str, content: Vec<u8>) -> GrpcResult<()> {
+        let resp = self
+            .bucket
+            .put_object_with_content_type(key, &content, content_type)
+            .await
+            .map_err(|e| internal!(format!("Cannot put object {}, err: {}", key, e)))?;
+        match resp.status_code() {
+            200..=299 => Ok(()),
+            code => Err(internal!(format!("Cannot put object {}, status: {}", key, code)).into()),
+        }
+    }
+
+    async fn get(&self, key: &str) -> GrpcResult<Option<Vec<u8>>> {
+        let resp = self
+            .bucket
+            .get_object(key)
+            .await
+            .map_err(|e| internal!(format!("Cannot get object {}, err: {}", key, e)))?;
+        match resp.status_code() {
+            200..=299 => Ok(Some(resp.bytes().to_vec())),
+            404 => Ok(None),
+            code => Err(internal!(format!("Cannot get object {}, status: {}", key, code)).into()),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> GrpcResult<bool> {
+        let (_, code) = self
+            .bucket
+            .head_object(key)
+            .await
+            .map_err(|e| internal!(format!("Cannot head object {}, err: {}", key, e)))?;
+        match code {
+            200..=299 => Ok(true),
+            404 => Ok(false),
+            code => Err(internal!(format!("Cannot head object {}, status: {}", key, code)).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Bytes;
+    use axum::extract::{Path, State};
+    use axum::routing::get;
+    use axum::Router;
+    use http::StatusCode;
+    use std::sync::Arc;
+
+    // objects of the bucket `douban`, keys under `broken/` fail with 500
+    type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    async fn put_object(
+        State(objects): State<Objects>,
+        Path(key): Path<String>,
+        body: Bytes,
+    ) -> StatusCode {
+        let key = key.trim_start_matches('/');
+        if key.starts_with("broken/") {
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+        objects.lock().insert(key.to_string(), body.to_vec());
+        StatusCode::OK
+    }
+
+    // serves HEAD as well, without the body
+    async fn get_object(
+        State(objects): State<Objects>,
+        Path(key): Path<String>,
+    ) -> Result<Vec<u8>, StatusCode> {
+        let key = key.trim_start_matches('/');
+        if key.starts_with("broken/") {
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        objects
+            .lock()
+            .get(key)
+            .cloned()
+            .ok_or(StatusCode::NOT_FOUND)
+    }
+
+    // a local stub of the S3 API addressed by path
+    fn s3_stub() -> S3Config {
+        let app = Router::new()
+            .route("/douban/*key", get(get_object).put(put_object))
+            .with_state(Objects::default());
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service()),
+        );
+        S3Config {
+            endpoint: format!("http://{}", addr),
+            bucket: "douban".to_string(),
+            access_key: "stub".to_string(),
+            secret_key: "stub".to_string(),
+            path_style: true,
+            ..S3Config::default()
+        }
+    }
+
+    #[test]
+    fn keys_in_the_store() {
+        assert!(check_key("poster/0a1b2c.jpg"));
+        assert!(check_key("photo/0a1b2c_160.webp"));
+        assert!(check_key("a"));
+    }
+
+    #[test]
+    fn keys_escaping_the_store() {
+        let escaping = [
+            "",
+            "..",
+            "../etc/passwd",
+            "poster/../../etc/passwd",
+            "poster/./0a1b2c.jpg",
+            "/etc/passwd",
+            "poster/",
+            "poster//0a1b2c.jpg",
+            ".hidden",
+            "poster/.0a1b2c.jpg",
+            "poster\\..\\0a1b2c.jpg",
+            "Poster/0a1b2c.jpg",
+            "poster/0a1b2c.jpg?x=1",
+            "poster/0a 1b2c.jpg",
+            "poster/0a1b2c.jpg\0",
+            "poster/%2e%2e/0a1b2c.jpg",
+        ];
+        for key in escaping {
+            assert!(!check_key(key), "{:?}", key);
+        }
+        assert!(!check_key(&"a".repeat(257)));
+        assert!(check_key(&"a".repeat(256)));
+    }
+
+    #[tokio::test]
+    async fn memory_store() {
+        let store = MemoryBlobStore::default();
+        assert!(!store.exists("poster/a.png").await.unwrap());
+        assert_eq!(store.get("poster/a.png").await.unwrap(), None);
+        store
+            .put("poster/a.png", "image/png", vec![1, 2, 3])
+            .await
+            .unwrap();
+        assert!(store.exists("poster/a.png").await.unwrap());
+        assert_eq!(
+            store.get("poster/a.png").await.unwrap(),
+            Some(vec![1, 2, 3])
+        );
+    }
+
+    #[tokio::test]
+    async fn s3_store() {
+        let store = S3BlobStore::new(&s3_stub()).unwrap();
+        assert!(!store.exists("poster/a.png").await.unwrap());
+        assert_eq!(store.get("poster/a.png").await.unwrap(), None);
+        store
+            .put("poster/a.png", "image/png", vec![1, 2, 3])
+            .await
+            .unwrap();
+        assert!(store.exists("poster/a.png").await.unwrap());
+        assert_eq!(
+            store.get("poster/a.png").await.unwrap(),
+            Some(vec![1, 2, 3])
+        );
+    }
+
+    #[tokio::test]
+    async fn s3_store_failures() {
+        let store = S3BlobStore::new(&s3_stub()).unwrap();
+        let err = store
+            .put("broken/a.png", "image/png", vec![1, 2, 3])
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Internal);
+        let err = store.get("broken/a.png").await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Internal);
+        let err = store.exists("broken/a.png").await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Internal);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::movie::rpc::MediaConfig;
+use common::status::prelude::*;
+use common::{internal, invalid_argument};
+use image::codecs::webp::{WebPEncoder, WebPQuality};
+use image::imageops::FilterType;
+use image::io::Reader;
+use image::{ColorType, DynamicImage, ImageFormat, ImageOutputFormat};
+use proto::pb::movie::media::v1 as pb;
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
+
+/// Content type of a key by its extension.
+pub(in crate::movie) fn content_type(key: &str) -> Option<&'static str> {
+    match key.rsplit_once('.')?.1 {
+        "jpg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+#[inline]
+fn extension(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Jpeg => "jpg",
+        ImageFormat::Png => "png",
+        ImageFormat::Gif => "gif",
+        _ => "webp",
+    }
+}
+
+/// A variant of an image to be rendered, thumbnails of JPEG are in JPEG and the others
+/// in PNG, every size has a WebP one as well.
+pub struct Variant {
+    name: String,
+    key: String,
+    width: u32,
+    height: u32,
+}
+
+/// An uploaded image, validated by its header without decoding. Images are keyed by
+/// the SHA-256 of their content, so that the urls of an image never change and
+/// uploading it again stores nothing.
+pub struct Image {
+    kind: pb::ImageKind,
+    format: ImageFormat,
+    hash: String,
+    width: u32,
+    height: u32,
+    content: Vec<u8>,
+}
+
+impl Image {
+    pub(in crate::movie::domain) fn parse(
+        kind: pb::ImageKind,
+        content: Vec<u8>,
+        conf: &MediaConfig,
+    ) -> GrpcResult<Image> {
+        if content.is_empty() || content.len() > conf.max_size {
+            return Err(invalid_argument!("chunk", "an image within the size limit").into());
+        }
+        let format = match image::guess_format(&content) {
+            Ok(
+                format @ (ImageFormat::Jpeg
+                | ImageFormat::Png
+                | ImageFormat::Gif
+                | ImageFormat::WebP),
+            ) => format,
+            _ => {
+                return Err(invalid_argument!("chunk", "an image in JPEG, PNG, GIF or WebP").into())
+            }
+        };
+        let (width, height) = Reader::with_format(Cursor::new(&content), format)
+            .into_dimensions()
+            .map_err(|_| invalid_argument!("chunk", "a valid image"))?;
+        if width == 0 || height == 0 || width > conf.max_dimension || height > conf.max_dimension {
+            return Err(invalid_argument!("chunk", "an image within the dimension limit").into());
+        }
+        let hash = Sha256::digest(&content)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        Ok(Image {
+            kind,
+            format,
+            hash,
+            width,
+            height,
+            content,
+        })
+    }
+
+    fn key(&self, suffix: &str, ext: &str) -> String {
+        format!(
+            "{}/{}{}.{}",
+            self.kind.as_str_name().to_lowercase(),
+            self.hash,
+            suffix,
+            ext
+        )
+    }
+
+    /// Key of the original.
+    pub(in crate::movie::domain) fn original_key(&self) -> String {
+        self.key("", extension(self.format))
+    }
+
+    /// Variants of the image, decided by the header, so that they are known without
+    /// rendering if the image has been stored.
+    pub(in crate::movie::domain) fn variants(&self, conf: &MediaConfig) -> Vec<Variant> {
+        let mut variants = Vec::new();
+        // the original is already a WebP one
+        if self.format != ImageFormat::WebP {
+            variants.push(Variant {
+                name: "webp".to_string(),
+                key: self.key("", "webp"),
+                width: self.width,
+                height: self.height,
+            });
+        }
+        // thumbnails of PNG and GIF keep the transparency
+        let thumbnail_ext = match self.format {
+            ImageFormat::Jpeg => "jpg",
+            _ => "png",
+        };
+        let mut widths: Vec<u32> = conf
+            .thumbnail_widths
+            .iter()
+            .copied()
+            .filter(|width| *width > 0 && *width < self.width)
+            .collect();
+        widths.sort_unstable();
+        widths.dedup();
+        for width in widths {
+            let height = ((self.height as u64 * width as u64) / self.width as u64).max(1) as u32;
+            let suffix = format!("_{}", width);
+            variants.push(Variant {
+                name: width.to_string(),
+                key: self.key(&suffix, thumbnail_ext),
+                width,
+                height,
+            });
+            variants.push(Variant {
+                name: format!("{}.webp", width),
+                key: self.key(&suffix, "webp"),
+                width,
+                height,
+            });
+        }
+        variants
+    }
+
+    /// Decode the image and encode the variants, CPU bound.
+    pub(in crate::movie::domain) fn render(
+        &self,
+        variants: &[Variant],
+        quality: u8,
+    ) -> GrpcResult<Vec<Vec<u8>>> {
+        let quality = quality.clamp(1, 100);
+        let decoded = Reader::with_format(Cursor::new(&self.content), self.format)
+            .decode()
+            .map_err(|_| invalid_argument!("chunk", "a valid image"))?;
+        let mut rendered = Vec::with_capacity(variants.len());
+        for variant in variants {
+            let resized = if variant.width == self.width {
+                decoded.clone()
+            } else {
+                decoded.resize_exact(variant.width, variant.height, FilterType::Lanczos3)
+            };
+            let mut buf = Vec::new();
+            let encoded = if variant.key.ends_with(".webp") {
+                let rgba = resized.to_rgba8();
+                WebPEncoder::new_with_quality(&mut buf, WebPQuality::lossy(quality)).encode(
+                    rgba.as_raw(),
+                    variant.width,
+                    variant.height,
+                    ColorType::Rgba8,
+                )
+            } else if variant.key.ends_with(".jpg") {
+                DynamicImage::ImageRgb8(resized.to_rgb8())
+                    .write_to(&mut Cursor::new(&mut buf), ImageOutputFormat::Jpeg(quality))
+            } else {
+                resized.write_to(&mut Cursor::new(&mut buf), ImageOutputFormat::Png)
+            };
+            encoded.map_err(|e| {
+                internal!(format!("Cannot encode variant {}, err: {}", variant.key, e))
+            })?;
+            rendered.push(buf);
+        }
+        Ok(rendered)
+    }
+
+    pub(in crate::movie::domain) fn to_pb(
+        &self,
+        variants: &[Variant],
+        conf: &MediaConfig,
+    ) -> pb::UploadImageRes {
+        let url = |key: &str| format!("{}/{}", conf.public_url.trim_end_matches('/'), key);
+        let original_key = self.original_key();
+        pb::UploadImageRes {
+            url: url(&original_key),
+            content_type: content_type(&original_key).unwrap_or_default().to_string(),
+            width: self.width,
+            height: self.height,
+            variants: variants
+                .iter()
+                .map(|variant| pb::ImageVariant {
+                    name: variant.name.clone(),
+                    url: url(&variant.key),
+                    content_type: content_type(&variant.key).unwrap_or_default().to_string(),
+                    width: variant.width,
+                    height: variant.height,
+                })
+                .collect(),
+        }
+    }
+
+    pub(in crate::movie::domain) fn content(&self) -> &[u8] {
+        &self.content
+    }
+}
+
+impl Variant {
+    pub(in crate::movie::domain) fn key(&self) -> &str {
+        &self.key
+    }
+}
@@ -0,0 +1,28 @@
+use crate::movie::domain::media::model::blob::{check_key, BlobStore};
+use crate::movie::domain::media::model::image::content_type;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use common::{invalid_argument, not_found};
+use proto::pb::movie::media::v1 as pb;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::GetImageReq, store: &dyn BlobStore) -> GrpcResult<pb::GetImageRes> {
+    let content_type = match content_type(&req.key) {
+        Some(content_type) if check_key(&req.key) => content_type,
+        _ => return Err(invalid_argument!("key", "a key of an image").into()),
+    };
+    let content = store
+        .get(&req.key)
+        .await?
+        .ok_or_else(|| not_found!(format!("image({})", req.key)).into())?;
+    Ok(pb::GetImageRes {
+        content,
+        content_type: content_type.to_string(),
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_get_image(&self) -> impl Query<pb::GetImageReq> + '_ {
+        move |req: pb::GetImageReq| async move { execute(req, self.blob_store()).await }
+    }
+}
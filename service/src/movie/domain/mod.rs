@@ -1,3 +1,11 @@
 pub mod movie;
 pub mod celebrity;
 pub mod score;
+pub mod search;
+pub mod review;
+pub mod mark;
+pub mod tag;
+pub mod doulist;
+pub mod recommend;
+pub mod media;
+pub mod utils;
@@ -0,0 +1,19 @@
+use crate::movie::domain::movie::model::movie::{Movie, MovieId};
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::movie::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::DelReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Movie::del_movie(MovieId::from(req.id as u64), conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_del_movie(&self) -> impl Command<pb::DelReq> + '_ {
+        move |req: pb::DelReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
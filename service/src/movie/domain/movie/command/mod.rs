@@ -0,0 +1,2 @@
+pub mod del_movie;
+pub mod put_movie;
@@ -0,0 +1,19 @@
+use crate::movie::domain::movie::model::movie::Movie;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::movie::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::PutReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Movie::put_movie(&req, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_put_movie(&self) -> impl Command<pb::PutReq> + '_ {
+        move |req: pb::PutReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,619 @@
+use crate::movie::domain::movie::model::movie::Movie;
+use crate::movie::domain::search::Keyword;
+use crate::movie::domain::tag::model::tag::normalize;
+use crate::movie::domain::utils::{DEFAULT_LIMIT, MAX_LIMIT};
+use chrono::NaiveDate;
+use common::status::prelude::*;
+use common::{internal, invalid_argument};
+use diesel::dsl::sql;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Double};
+use migration::{
+    t_movies, t_movies_actors, t_movies_categories, t_movies_country, t_movies_directors,
+    t_movies_scores, t_movies_tags, t_movies_writers,
+};
+use proto::pb::movie::movie::v1 as pb;
+
+#[inline]
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Filters of listing movies, a relation table is only queried when its filter is present.
+///
+/// Relations are matched with `IN` subqueries rather than joins, so that a movie
+/// is listed once even if it matches several rows of a relation.
+pub struct MovieFilter {
+    // matches title, name, alias name and plot, or pinyin of title
+    keyword: Option<Keyword>,
+    language: Option<String>,
+    // unit (minute), inclusive
+    time_length: Option<(i32, i32)>,
+    // inclusive
+    released: Option<(NaiveDate, NaiveDate)>,
+    actor_id: Option<i64>,
+    director_id: Option<i64>,
+    writer_id: Option<i64>,
+    category: Option<String>,
+    country: Option<String>,
+    // score_avg is stored in 0-10, inclusive
+    score: Option<(f64, f64)>,
+    // normalized
+    tag: Option<String>,
+    order: pb::MovieOrder,
+    limit: i64,
+    offset: i64,
+}
+
+impl MovieFilter {
+    pub(in crate::movie::domain) fn parse(req: pb::ListReq) -> GrpcResult<MovieFilter> {
+        let keyword = Keyword::parse(req.keyword)?;
+        let time_length = match req.time_range {
+            Some(range) if range.start < 0 || range.start > range.end => {
+                return Err(invalid_argument!("time_range", "0 <= start <= end").into());
+            }
+            // hour to minute
+            Some(range) => Some((range.start.saturating_mul(60), range.end.saturating_mul(60))),
+            None => None,
+        };
+        let released = match req.released_years {
+            Some(year) => {
+                let range = NaiveDate::from_ymd_opt(year, 1, 1)
+                    .zip(NaiveDate::from_ymd_opt(year, 12, 31))
+                    .ok_or_else(|| invalid_argument!("released_years", "a valid year"))?;
+                Some(range)
+            }
+            None => None,
+        };
+        let score = match req.score_range {
+            Some(range) if range.start < 1 || range.end > 5 || range.start > range.end => {
+                return Err(invalid_argument!("score_range", "1 <= start <= end <= 5").into());
+            }
+            // a star is worth 2 points
+            Some(range) => Some((range.start as f64 * 2.0, range.end as f64 * 2.0)),
+            None => None,
+        };
+        let order = pb::MovieOrder::from_i32(req.order).ok_or_else(|| {
+            invalid_argument!("order", "RELEASED, SCORE, POPULARITY or RELEVANCE")
+        })?;
+        let (limit, offset) = req
+            .slice
+            .unwrap_or_default()
+            .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+        Ok(MovieFilter {
+            keyword,
+            language: trimmed(req.language),
+            time_length,
+            released,
+            actor_id: req.actor_id,
+            director_id: req.director_id,
+            writer_id: req.writer_id,
+            category: trimmed(req.category),
+            country: trimmed(req.country),
+            score,
+            tag: req
+                .tag
+                .map(|tag| normalize(&tag))
+                .filter(|tag| !tag.is_empty()),
+            order,
+            limit,
+            offset,
+        })
+    }
+
+    fn query(&self) -> t_movies::BoxedQuery<'_, Pg> {
+        use migration::t_movies::dsl::*;
+
+        let mut query = t_movies.into_boxed();
+        if let Some(keyword) = &self.keyword {
+            let matches = keyword.matches("t_movies");
+            query = match keyword.pinyin() {
+                Some(pattern) => query.filter(
+                    matches
+                        .or(pinyin.like(pattern))
+                        .or(pinyin_initials.like(pattern)),
+                ),
+                None => query.filter(matches),
+            };
+        }
+        if let Some(lang) = &self.language {
+            query = query.filter(language.eq(lang));
+        }
+        if let Some((start, end)) = self.time_length {
+            query = query.filter(time_length.between(start, end));
+        }
+        if let Some((start, end)) = self.released {
+            query = query.filter(released_date.between(start, end));
+        }
+        if let Some(cid) = self.actor_id {
+            query = query.filter(
+                id.eq_any(
+                    t_movies_actors::table
+                        .select(t_movies_actors::mid)
+                        .filter(t_movies_actors::cid.eq(cid)),
+                ),
+            );
+        }
+        if let Some(cid) = self.director_id {
+            query = query.filter(
+                id.eq_any(
+                    t_movies_directors::table
+                        .select(t_movies_directors::mid)
+                        .filter(t_movies_directors::cid.eq(cid)),
+                ),
+            );
+        }
+        if let Some(cid) = self.writer_id {
+            query = query.filter(
+                id.eq_any(
+                    t_movies_writers::table
+                        .select(t_movies_writers::mid)
+                        .filter(t_movies_writers::cid.eq(cid)),
+                ),
+            );
+        }
+        if let Some(value) = &self.category {
+            query = query.filter(
+                id.eq_any(
+                    t_movies_categories::table
+                        .select(t_movies_categories::mid)
+                        .filter(t_movies_categories::category.eq(value)),
+                ),
+            );
+        }
+        if let Some(value) = &self.country {
+            query = query.filter(
+                id.eq_any(
+                    t_movies_country::table
+                        .select(t_movies_country::mid)
+                        .filter(t_movies_country::country.eq(value)),
+                ),
+            );
+        }
+        if let Some((start, end)) = self.score {
+            query = query.filter(
+                id.eq_any(
+                    t_movies_scores::table
+                        .select(t_movies_scores::mid)
+                        .filter(t_movies_scores::score_avg.between(start, end)),
+                ),
+            );
+        }
+        if let Some(value) = &self.tag {
+            query = query.filter(
+                id.eq_any(
+                    t_movies_tags::table
+                        .select(t_movies_tags::mid)
+                        .filter(t_movies_tags::tag.eq(value))
+                        .filter(t_movies_tags::cnt.gt(0)),
+                ),
+            );
+        }
+        query
+    }
+
+    pub(in crate::movie::domain) fn keyword(&self) -> Option<&Keyword> {
+        self.keyword.as_ref()
+    }
+
+    // ordered and sliced
+    fn ordered(&self) -> t_movies::BoxedQuery<'_, Pg> {
+        use migration::t_movies::dsl::*;
+
+        let query = match self.order {
+            pb::MovieOrder::Released => self.query().order(released_date.desc()),
+            // movies without scores come last
+            pb::MovieOrder::Score => self.query().order(
+                sql::<Double>(
+                    "(SELECT coalesce(max(score_avg), 0) FROM t_movies_scores WHERE t_movies_scores.mid = t_movies.id)",
+                )
+                .desc(),
+            ),
+            pb::MovieOrder::Popularity => self.query().order(
+                sql::<BigInt>(
+                    "(SELECT coalesce(sum(cnt_1 + cnt_2 + cnt_3 + cnt_4 + cnt_5), 0)::bigint FROM t_movies_scores WHERE t_movies_scores.mid = t_movies.id)",
+                )
+                .desc(),
+            ),
+            pb::MovieOrder::Relevance => match &self.keyword {
+                Some(keyword) => self.query().order(keyword.rank("t_movies").desc()),
+                // nothing to be relevant to
+                None => self.query().order(released_date.desc()),
+            },
+        };
+        query
+            .then_order_by(id.desc())
+            .limit(self.limit)
+            .offset(self.offset)
+    }
+
+    pub(in crate::movie::domain) fn list(
+        &self,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<Movie>, i64)> {
+        let map_err = |e| internal!(format!("Database connection error: {}", e));
+        let total: i64 = self.query().count().get_result(conn).map_err(map_err)?;
+        let movies: Vec<Movie> = self.ordered().load(conn).map_err(map_err)?;
+        Ok((movies, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::debug_query;
+    use proto::pb::common::v1::{ByPage, Slice};
+    use proto::pb::movie::movie::v1::{ScoreRange, TimeRange};
+
+    const RELATIONS: [&str; 7] = [
+        "t_movies_actors",
+        "t_movies_directors",
+        "t_movies_writers",
+        "t_movies_categories",
+        "t_movies_country",
+        "t_movies_scores",
+        "t_movies_tags",
+    ];
+
+    fn filter(req: pb::ListReq) -> MovieFilter {
+        MovieFilter::parse(req).unwrap()
+    }
+
+    fn render(req: pb::ListReq) -> String {
+        debug_query::<Pg, _>(&filter(req).query()).to_string()
+    }
+
+    fn render_ordered(req: pb::ListReq) -> String {
+        debug_query::<Pg, _>(&filter(req).ordered()).to_string()
+    }
+
+    // relation tables queried by subqueries, the order clauses are not quoted
+    fn relations(sql: &str) -> Vec<&'static str> {
+        RELATIONS
+            .iter()
+            .copied()
+            .filter(|table| sql.contains(&format!("FROM \"{}\"", table)))
+            .collect()
+    }
+
+    #[test]
+    fn no_filter() {
+        let sql = render(pb::ListReq::default());
+        assert!(!sql.contains("WHERE"), "{}", sql);
+        assert!(relations(&sql).is_empty(), "{}", sql);
+    }
+
+    #[test]
+    fn blank_filters_are_no_filters() {
+        let sql = render(pb::ListReq {
+            keyword: Some("  ".to_string()),
+            language: Some(" ".to_string()),
+            category: Some("".to_string()),
+            country: Some(" ".to_string()),
+            tag: Some(" ".to_string()),
+            ..Default::default()
+        });
+        assert!(!sql.contains("WHERE"), "{}", sql);
+    }
+
+    #[test]
+    fn keyword() {
+        let sql = render(pb::ListReq {
+            keyword: Some("Hua Yang".to_string()),
+            ..Default::default()
+        });
+        assert!(
+            sql.contains("t_movies.search_vector @@ douban_search_query($1)"),
+            "{}",
+            sql
+        );
+        assert!(sql.contains(r#""t_movies"."pinyin" LIKE $2"#), "{}", sql);
+        assert!(
+            sql.contains(r#""t_movies"."pinyin_initials" LIKE $3"#),
+            "{}",
+            sql
+        );
+        assert!(sql.contains(r#""huayang%""#), "{}", sql);
+        assert!(relations(&sql).is_empty(), "{}", sql);
+
+        // not in latin letters, no pinyin
+        let sql = render(pb::ListReq {
+            keyword: Some("花样年华".to_string()),
+            ..Default::default()
+        });
+        assert!(sql.contains("douban_search_query($1)"), "{}", sql);
+        assert!(!sql.contains("pinyin"), "{}", sql);
+    }
+
+    #[test]
+    fn columns() {
+        let sql = render(pb::ListReq {
+            language: Some(" 汉语普通话 ".to_string()),
+            ..Default::default()
+        });
+        assert!(sql.contains(r#""t_movies"."language" = $1"#), "{}", sql);
+        assert!(sql.contains(r#""汉语普通话""#), "{}", sql);
+
+        let sql = render(pb::ListReq {
+            time_range: Some(TimeRange { start: 1, end: 2 }),
+            ..Default::default()
+        });
+        assert!(
+            sql.contains(r#""t_movies"."time_length" BETWEEN $1 AND $2"#),
+            "{}",
+            sql
+        );
+        assert!(sql.contains("[60, 120]"), "{}", sql);
+
+        let sql = render(pb::ListReq {
+            released_years: Some(2000),
+            ..Default::default()
+        });
+        assert!(
+            sql.contains(r#""t_movies"."released_date" BETWEEN $1 AND $2"#),
+            "{}",
+            sql
+        );
+        assert!(
+            sql.contains("2000-01-01") && sql.contains("2000-12-31"),
+            "{}",
+            sql
+        );
+
+        for sql in [
+            render(pb::ListReq {
+                language: Some("English".to_string()),
+                ..Default::default()
+            }),
+            render(pb::ListReq {
+                time_range: Some(TimeRange { start: 1, end: 2 }),
+                ..Default::default()
+            }),
+            render(pb::ListReq {
+                released_years: Some(2000),
+                ..Default::default()
+            }),
+        ] {
+            assert!(relations(&sql).is_empty(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn relation_alone() {
+        let cases = [
+            (
+                pb::ListReq {
+                    actor_id: Some(1),
+                    ..Default::default()
+                },
+                "t_movies_actors",
+            ),
+            (
+                pb::ListReq {
+                    director_id: Some(1),
+                    ..Default::default()
+                },
+                "t_movies_directors",
+            ),
+            (
+                pb::ListReq {
+                    writer_id: Some(1),
+                    ..Default::default()
+                },
+                "t_movies_writers",
+            ),
+            (
+                pb::ListReq {
+                    category: Some("剧情".to_string()),
+                    ..Default::default()
+                },
+                "t_movies_categories",
+            ),
+            (
+                pb::ListReq {
+                    country: Some("中国香港".to_string()),
+                    ..Default::default()
+                },
+                "t_movies_country",
+            ),
+            (
+                pb::ListReq {
+                    score_range: Some(ScoreRange { start: 4, end: 5 }),
+                    ..Default::default()
+                },
+                "t_movies_scores",
+            ),
+            (
+                pb::ListReq {
+                    tag: Some("Cult".to_string()),
+                    ..Default::default()
+                },
+                "t_movies_tags",
+            ),
+        ];
+        for (req, table) in cases {
+            let sql = render(req);
+            assert_eq!(relations(&sql), vec![table], "{}", sql);
+            assert!(
+                sql.contains(&format!(r#""t_movies"."id" IN (SELECT "{}"."mid""#, table)),
+                "{}",
+                sql
+            );
+        }
+    }
+
+    #[test]
+    fn relation_conditions() {
+        let sql = render(pb::ListReq {
+            score_range: Some(ScoreRange { start: 4, end: 5 }),
+            ..Default::default()
+        });
+        assert!(
+            sql.contains(r#""t_movies_scores"."score_avg" BETWEEN $1 AND $2"#),
+            "{}",
+            sql
+        );
+        assert!(sql.contains("[8.0, 10.0]"), "{}", sql);
+
+        // tags are normalized and only the ones still in use match
+        let sql = render(pb::ListReq {
+            tag: Some(" Cult ".to_string()),
+            ..Default::default()
+        });
+        assert!(sql.contains(r#""t_movies_tags"."cnt" > $2"#), "{}", sql);
+        assert!(
+            sql.contains(&format!("{:?}", normalize(" Cult "))),
+            "{}",
+            sql
+        );
+    }
+
+    #[test]
+    fn combinations() {
+        let sql = render(pb::ListReq {
+            actor_id: Some(1),
+            category: Some("剧情".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            relations(&sql),
+            vec!["t_movies_actors", "t_movies_categories"]
+        );
+
+        let sql = render(pb::ListReq {
+            keyword: Some("花样年华".to_string()),
+            language: Some("粤语".to_string()),
+            director_id: Some(2),
+            score_range: Some(ScoreRange { start: 3, end: 5 }),
+            ..Default::default()
+        });
+        assert!(sql.contains("douban_search_query($1)"), "{}", sql);
+        assert!(sql.contains(r#""t_movies"."language" = $2"#), "{}", sql);
+        assert_eq!(
+            relations(&sql),
+            vec!["t_movies_directors", "t_movies_scores"]
+        );
+
+        let sql = render(pb::ListReq {
+            keyword: Some("matrix".to_string()),
+            language: Some("English".to_string()),
+            time_range: Some(TimeRange { start: 1, end: 3 }),
+            released_years: Some(1999),
+            actor_id: Some(1),
+            director_id: Some(2),
+            writer_id: Some(3),
+            category: Some("科幻".to_string()),
+            country: Some("美国".to_string()),
+            score_range: Some(ScoreRange { start: 1, end: 5 }),
+            tag: Some("cyberpunk".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(relations(&sql), RELATIONS.to_vec(), "{}", sql);
+        assert_eq!(
+            sql.matches(" IN (SELECT ").count(),
+            RELATIONS.len(),
+            "{}",
+            sql
+        );
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        let cases = [
+            pb::ListReq {
+                time_range: Some(TimeRange { start: 2, end: 1 }),
+                ..Default::default()
+            },
+            pb::ListReq {
+                time_range: Some(TimeRange { start: -1, end: 1 }),
+                ..Default::default()
+            },
+            pb::ListReq {
+                score_range: Some(ScoreRange { start: 0, end: 5 }),
+                ..Default::default()
+            },
+            pb::ListReq {
+                score_range: Some(ScoreRange { start: 4, end: 6 }),
+                ..Default::default()
+            },
+            pb::ListReq {
+                order: 42,
+                ..Default::default()
+            },
+        ];
+        for req in cases {
+            assert!(MovieFilter::parse(req).is_err());
+        }
+    }
+
+    #[test]
+    fn orders() {
+        let ordered = |order: pb::MovieOrder, keyword: Option<&str>| {
+            render_ordered(pb::ListReq {
+                keyword: keyword.map(ToString::to_string),
+                order: order as i32,
+                ..Default::default()
+            })
+        };
+        let released = r#"ORDER BY "t_movies"."released_date" DESC, "t_movies"."id" DESC"#;
+
+        let sql = ordered(pb::MovieOrder::Released, None);
+        assert!(sql.contains(released), "{}", sql);
+
+        let sql = ordered(pb::MovieOrder::Score, None);
+        assert!(
+            sql.contains("ORDER BY (SELECT coalesce(max(score_avg), 0) FROM t_movies_scores"),
+            "{}",
+            sql
+        );
+        assert!(sql.contains(r#") DESC, "t_movies"."id" DESC"#), "{}", sql);
+
+        let sql = ordered(pb::MovieOrder::Popularity, None);
+        assert!(
+            sql.contains(
+                "ORDER BY (SELECT coalesce(sum(cnt_1 + cnt_2 + cnt_3 + cnt_4 + cnt_5), 0)::bigint"
+            ),
+            "{}",
+            sql
+        );
+        assert!(sql.contains(r#") DESC, "t_movies"."id" DESC"#), "{}", sql);
+
+        let sql = ordered(pb::MovieOrder::Relevance, Some("花样年华"));
+        assert!(sql.contains("ORDER BY ts_rank(t_movies.search_vector, douban_search_query($2)) DESC, \"t_movies\".\"id\" DESC"), "{}", sql);
+
+        // nothing to be relevant to
+        let sql = ordered(pb::MovieOrder::Relevance, None);
+        assert!(sql.contains(released), "{}", sql);
+
+        // ordering never joins a relation
+        for order in [
+            pb::MovieOrder::Released,
+            pb::MovieOrder::Score,
+            pb::MovieOrder::Popularity,
+            pb::MovieOrder::Relevance,
+        ] {
+            let sql = ordered(order, None);
+            assert!(relations(&sql).is_empty(), "{}", sql);
+            assert!(sql.contains("LIMIT $1 OFFSET $2"), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn slice() {
+        let sql = render_ordered(pb::ListReq {
+            slice: Some(Slice {
+                limit: None,
+                page: Some(ByPage {
+                    page: 3,
+                    per_page: 10,
+                }),
+            }),
+            ..Default::default()
+        });
+        assert!(sql.contains("LIMIT $1 OFFSET $2"), "{}", sql);
+        assert!(sql.ends_with("[10, 20]"), "{}", sql);
+    }
+}
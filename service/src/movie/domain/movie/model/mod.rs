@@ -1 +1,3 @@
-
+pub mod filter;
+pub mod movie;
+pub mod ranking;
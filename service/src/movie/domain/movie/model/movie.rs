@@ -0,0 +1,319 @@
+use crate::movie::domain::search::{romanize, Keyword};
+use crate::movie::domain::utils::{dedup, map_internal, map_not_found};
+use chrono::{NaiveDate, NaiveDateTime};
+use common::status::prelude::*;
+use common::{infra, internal, invalid_argument, not_found};
+use diesel::prelude::*;
+use migration::{
+    t_celebrities, t_movies, t_movies_actors, t_movies_categories, t_movies_country,
+    t_movies_directors, t_movies_writers,
+};
+use proto::pb::movie::movie::v1 as pb;
+use std::collections::HashSet;
+
+pub type MovieId = infra::Id<Movie>;
+
+#[derive(Queryable)]
+pub struct Movie {
+    id: i64,
+    title: String,
+    pic_url: Option<String>,
+    name: String,
+    alias_name: Option<String>,
+    language: String,
+    time_length: i32,
+    released_date: NaiveDate,
+    imdb: String,
+    plot: String,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+    pinyin: String,
+    pinyin_initials: String,
+}
+
+// a put replaces the whole movie, so that none clears an optional field
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = t_movies, treat_none_as_null = true)]
+struct PutMovie<'a> {
+    title: &'a str,
+    pic_url: Option<&'a str>,
+    name: &'a str,
+    alias_name: Option<&'a str>,
+    language: &'a str,
+    time_length: i32,
+    released_date: NaiveDate,
+    imdb: &'a str,
+    plot: &'a str,
+    pinyin: String,
+    pinyin_initials: String,
+}
+
+#[inline]
+fn dedup_names(names: &[String]) -> Vec<String> {
+    dedup(
+        &names
+            .iter()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn check_celebrities(ids: &[i64], conn: &mut PgConnection) -> GrpcResult<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let found: HashSet<i64> = t_celebrities::table
+        .filter(t_celebrities::id.eq_any(ids))
+        .select(t_celebrities::id)
+        .load::<i64>(conn)
+        .map_err(map_internal)?
+        .into_iter()
+        .collect();
+    match ids.iter().find(|id| !found.contains(id)) {
+        Some(missing) => Err(not_found!(format!("celebrity({})", missing)).into()),
+        None => Ok(()),
+    }
+}
+
+impl Movie {
+    pub fn id(&self) -> MovieId {
+        MovieId::from(self.id as u64)
+    }
+
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::GetRes {
+        pb::GetRes {
+            id: self.id,
+            payload: Some(pb::MoviePayload {
+                title: self.title.clone(),
+                pic_url: self.pic_url.clone(),
+                name: self.name.clone(),
+                alias_name: self.alias_name.clone(),
+                language: self.language.clone(),
+                time_length: self.time_length,
+                release_date: Some(prost_types::Timestamp {
+                    seconds: self
+                        .released_date
+                        .and_hms_opt(0, 0, 0)
+                        .expect("midnight is always valid")
+                        .timestamp(),
+                    nanos: 0,
+                }),
+                imdb: self.imdb.clone(),
+                plot: self.plot.clone(),
+            }),
+        }
+    }
+
+    /// A snippet of the first field matching the keyword, keyed by the id of the movie.
+    /// The title is highlighted as a whole if only its pinyin matches.
+    pub(in crate::movie::domain) fn highlight(&self, keyword: &Keyword) -> Option<(i64, String)> {
+        [
+            Some(&self.title),
+            Some(&self.name),
+            self.alias_name.as_ref(),
+            Some(&self.plot),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|text| keyword.highlight(text))
+        .or_else(|| keyword.highlight_pinyin(&self.title, &self.pinyin, &self.pinyin_initials))
+        .map(|snippet| (self.id, snippet))
+    }
+
+    /// Insert the movie or replace the one with the same IMDb number, the celebrities,
+    /// categories and countries of the movie are replaced in the same transaction.
+    pub(in crate::movie::domain) fn put_movie(
+        req: &pb::PutReq,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<MovieId> {
+        let payload = req
+            .payload
+            .as_ref()
+            .ok_or_else(|| invalid_argument!("payload", "a movie payload"))?;
+        if payload.imdb.trim().is_empty() {
+            return Err(invalid_argument!("imdb", "an IMDb number").into());
+        }
+        if payload.time_length <= 0 {
+            return Err(invalid_argument!("time_length", "a positive number of minutes").into());
+        }
+        let released_date = payload
+            .release_date
+            .as_ref()
+            .and_then(|ts| NaiveDateTime::from_timestamp_opt(ts.seconds, 0))
+            .map(|datetime| datetime.date())
+            .ok_or_else(|| invalid_argument!("release_date", "a valid timestamp"))?;
+        let (pinyin, pinyin_initials) = romanize(&payload.title);
+        let put = PutMovie {
+            title: &payload.title,
+            pic_url: payload.pic_url.as_deref(),
+            name: &payload.name,
+            alias_name: payload.alias_name.as_deref(),
+            language: &payload.language,
+            time_length: payload.time_length,
+            released_date,
+            imdb: payload.imdb.trim(),
+            plot: &payload.plot,
+            pinyin,
+            pinyin_initials,
+        };
+        let actors = dedup(&req.actors_id);
+        let directors = dedup(&req.directors_id);
+        let writers = dedup(&req.writers_id);
+        let categories = dedup_names(&req.categories);
+        let countries = dedup_names(&req.countries);
+
+        conn.transaction::<MovieId, GrpcStatus, _>(|conn| {
+            let celebrities = [actors.as_slice(), &directors, &writers].concat();
+            check_celebrities(&dedup(&celebrities), conn)?;
+
+            let movie_id: i64 = diesel::insert_into(t_movies::table)
+                .values(&put)
+                .on_conflict(t_movies::imdb)
+                .do_update()
+                .set(&put)
+                .returning(t_movies::id)
+                .get_result(conn)
+                .map_err(|e| internal!(format!("Cannot put movie, err: {}", e)))?;
+
+            diesel::delete(t_movies_actors::table.filter(t_movies_actors::mid.eq(movie_id)))
+                .execute(conn)
+                .map_err(map_internal)?;
+            diesel::insert_into(t_movies_actors::table)
+                .values(
+                    actors
+                        .iter()
+                        .map(|cid| {
+                            (
+                                t_movies_actors::mid.eq(movie_id),
+                                t_movies_actors::cid.eq(cid),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .map_err(map_internal)?;
+
+            diesel::delete(t_movies_directors::table.filter(t_movies_directors::mid.eq(movie_id)))
+                .execute(conn)
+                .map_err(map_internal)?;
+            diesel::insert_into(t_movies_directors::table)
+                .values(
+                    directors
+                        .iter()
+                        .map(|cid| {
+                            (
+                                t_movies_directors::mid.eq(movie_id),
+                                t_movies_directors::cid.eq(cid),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .map_err(map_internal)?;
+
+            diesel::delete(t_movies_writers::table.filter(t_movies_writers::mid.eq(movie_id)))
+                .execute(conn)
+                .map_err(map_internal)?;
+            diesel::insert_into(t_movies_writers::table)
+                .values(
+                    writers
+                        .iter()
+                        .map(|cid| {
+                            (
+                                t_movies_writers::mid.eq(movie_id),
+                                t_movies_writers::cid.eq(cid),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .map_err(map_internal)?;
+
+            diesel::delete(
+                t_movies_categories::table.filter(t_movies_categories::mid.eq(movie_id)),
+            )
+            .execute(conn)
+            .map_err(map_internal)?;
+            diesel::insert_into(t_movies_categories::table)
+                .values(
+                    categories
+                        .iter()
+                        .map(|name| {
+                            (
+                                t_movies_categories::mid.eq(movie_id),
+                                t_movies_categories::category.eq(name),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .map_err(map_internal)?;
+
+            diesel::delete(t_movies_country::table.filter(t_movies_country::mid.eq(movie_id)))
+                .execute(conn)
+                .map_err(map_internal)?;
+            diesel::insert_into(t_movies_country::table)
+                .values(
+                    countries
+                        .iter()
+                        .map(|name| {
+                            (
+                                t_movies_country::mid.eq(movie_id),
+                                t_movies_country::country.eq(name),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .map_err(map_internal)?;
+
+            Ok(MovieId::from(movie_id as u64))
+        })
+    }
+
+    /// Delete the movie, its celebrities, categories, countries and scores
+    /// are deleted by the cascading foreign keys.
+    pub(in crate::movie::domain) fn del_movie(
+        mid: MovieId,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_movies::dsl::*;
+
+        let mid = mid.as_i64().expect("Expect an i64 id");
+        let deleted = diesel::delete(t_movies.find(mid))
+            .execute(conn)
+            .map_err(map_internal)?;
+        if deleted == 0 {
+            return Err(not_found!(format!("movie({})", mid)).into());
+        }
+        Ok(())
+    }
+
+    pub(in crate::movie::domain) fn query_id(
+        mid: MovieId,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Movie> {
+        use migration::t_movies::dsl::*;
+
+        let mid = mid.as_i64().expect("Expect an i64 id");
+        let movie: Movie = t_movies
+            .find(mid)
+            .first(conn)
+            .map_err(map_not_found("movie", mid))?;
+        Ok(movie)
+    }
+
+    pub(in crate::movie::domain) fn query_imdb(
+        imdb_str: &str,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Movie> {
+        use migration::t_movies::dsl::*;
+
+        let movie: Movie = t_movies
+            .filter(imdb.eq(imdb_str))
+            .first(conn)
+            .map_err(map_not_found("movie", imdb_str))?;
+        Ok(movie)
+    }
+}
@@ -0,0 +1,164 @@
+use crate::movie::domain::movie::model::movie::Movie;
+use crate::movie::domain::utils::map_internal;
+use crate::movie::rpc::RankingConfig;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{internal, invalid_argument};
+use diesel::dsl::{count_star, max, sql};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Bool, Double, Nullable, Timestamp};
+use migration::{t_movies, t_movies_rankings, t_movies_scores};
+use proto::pb::movie::movie::v1 as pb;
+
+// key of the advisory lock held while computing charts
+const RANKING_LOCK: i64 = 0x7261_6e6b;
+
+/// A movie of a materialised top chart, replaced as a whole whenever the charts are
+/// computed.
+///
+/// Movies are ranked by the IMDb-style weighted score `(v * R + m * C) / (v + m)`,
+/// where `v` is the count of ratings, `R` the mean score of the movie, `m` the
+/// minimum count of ratings and `C` the global mean, so that a movie with only a
+/// few ratings is pulled towards the global mean.
+#[derive(Queryable)]
+pub struct Ranking {
+    id: i64,
+    chart: String,
+    mid: i64,
+    rank: i32,
+    prev_rank: Option<i32>,
+    weighted_score: f64,
+    votes: i64,
+    computed_at: NaiveDateTime,
+}
+
+impl Ranking {
+    /// Key of the chart, the overall chart if neither category nor country is given.
+    pub(in crate::movie::domain) fn chart(
+        category: Option<&str>,
+        country: Option<&str>,
+    ) -> GrpcResult<String> {
+        let category = category.map(str::trim).filter(|value| !value.is_empty());
+        let country = country.map(str::trim).filter(|value| !value.is_empty());
+        match (category, country) {
+            (Some(_), Some(_)) => {
+                Err(invalid_argument!("category", "either a category or a country").into())
+            }
+            (Some(category), None) => Ok(format!("category:{}", category)),
+            (None, Some(country)) => Ok(format!("country:{}", country)),
+            (None, None) => Ok(String::new()),
+        }
+    }
+
+    pub(in crate::movie::domain) fn to_pb(&self, movie: &Movie) -> pb::RankedMovie {
+        pb::RankedMovie {
+            rank: self.rank,
+            prev_rank: self.prev_rank,
+            rank_delta: self.prev_rank.map(|prev| prev - self.rank),
+            weighted_score: self.weighted_score,
+            votes: self.votes,
+            movie: Some(movie.to_pb()),
+        }
+    }
+
+    /// Compute every chart, ranks of the previous computation are kept in `prev_rank`.
+    /// Returns none without computing if another instance is computing the charts.
+    pub(in crate::movie) fn refresh(
+        conf: &RankingConfig,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Option<usize>> {
+        conn.transaction::<Option<usize>, GrpcStatus, _>(|conn| {
+            let locked: bool = diesel::select(sql::<Bool>(&format!(
+                "pg_try_advisory_xact_lock({})",
+                RANKING_LOCK
+            )))
+            .get_result(conn)
+            .map_err(map_internal)?;
+            if !locked {
+                return Ok(None);
+            }
+
+            let global_mean = match conf.global_mean {
+                Some(mean) => mean,
+                // a star is worth 2 points
+                None => t_movies_scores::table
+                    .select(sql::<Nullable<Double>>(
+                        "sum(2 * cnt_1 + 4 * cnt_2 + 6 * cnt_3 + 8 * cnt_4 + 10 * cnt_5)::float8 \
+                         / nullif(sum(cnt_1 + cnt_2 + cnt_3 + cnt_4 + cnt_5), 0)",
+                    ))
+                    .first::<Option<f64>>(conn)
+                    .map_err(map_internal)?
+                    .unwrap_or_default(),
+            };
+            let min_votes = conf.min_votes.max(1);
+
+            // now() is the start of the transaction, rows not touched by the upsert
+            // dropped out of their charts
+            let ranked = diesel::sql_query(
+                "WITH scored AS (
+                     SELECT mid, votes, (votes * score_avg + $1 * $2) / (votes + $1) AS weighted_score
+                     FROM (SELECT mid, score_avg, (cnt_1 + cnt_2 + cnt_3 + cnt_4 + cnt_5)::float8 AS votes
+                           FROM t_movies_scores) s
+                     WHERE votes >= $1
+                 ), charts AS (
+                     SELECT '' AS chart, mid, votes, weighted_score FROM scored
+                     UNION ALL
+                     SELECT DISTINCT 'category:' || c.category, s.mid, s.votes, s.weighted_score
+                     FROM scored s JOIN t_movies_categories c ON c.mid = s.mid
+                     UNION ALL
+                     SELECT DISTINCT 'country:' || c.country, s.mid, s.votes, s.weighted_score
+                     FROM scored s JOIN t_movies_country c ON c.mid = s.mid
+                 ), ranked AS (
+                     SELECT chart, mid, votes, weighted_score,
+                            row_number() OVER (PARTITION BY chart ORDER BY weighted_score DESC, votes DESC, mid) AS rank
+                     FROM charts
+                 )
+                 INSERT INTO t_movies_rankings (chart, mid, rank, weighted_score, votes, computed_at)
+                 SELECT chart, mid, rank::int, weighted_score, votes::bigint, now() FROM ranked WHERE rank <= $3
+                 ON CONFLICT (chart, mid) DO UPDATE SET
+                     prev_rank = t_movies_rankings.rank,
+                     rank = excluded.rank,
+                     weighted_score = excluded.weighted_score,
+                     votes = excluded.votes,
+                     computed_at = excluded.computed_at",
+            )
+            .bind::<Double, _>(min_votes as f64)
+            .bind::<Double, _>(global_mean)
+            .bind::<BigInt, _>(conf.chart_size)
+            .execute(conn)
+            .map_err(|e| internal!(format!("Cannot compute charts, err: {}", e)))?;
+
+            let now = sql::<Timestamp>("now()");
+            diesel::delete(t_movies_rankings::table.filter(t_movies_rankings::computed_at.lt(now)))
+                .execute(conn)
+                .map_err(map_internal)?;
+
+            Ok(Some(ranked))
+        })
+    }
+
+    /// Movies of the chart, highest rank first, with the count of movies in the chart
+    /// and when the chart was computed.
+    pub(in crate::movie::domain) fn list(
+        chart_key: &str,
+        limit: i64,
+        offset: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<(Ranking, Movie)>, i64, Option<NaiveDateTime>)> {
+        let (total, computed): (i64, Option<NaiveDateTime>) = t_movies_rankings::table
+            .filter(t_movies_rankings::chart.eq(chart_key))
+            .select((count_star(), max(t_movies_rankings::computed_at)))
+            .first(conn)
+            .map_err(map_internal)?;
+        let rankings: Vec<(Ranking, Movie)> = t_movies_rankings::table
+            .inner_join(t_movies::table)
+            .filter(t_movies_rankings::chart.eq(chart_key))
+            .select((t_movies_rankings::all_columns, t_movies::all_columns))
+            .order(t_movies_rankings::rank.asc())
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+            .map_err(map_internal)?;
+        Ok((rankings, total, computed))
+    }
+}
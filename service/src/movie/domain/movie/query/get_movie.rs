@@ -0,0 +1,24 @@
+use crate::movie::domain::movie::model::movie::{Movie, MovieId};
+use crate::movie::rpc::MovieResolver;
+use common::invalid_argument;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::movie::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::GetReq, conn: &mut PgConnection) -> GrpcResult<pb::GetRes> {
+    // id is preferred if both are set
+    let movie = match (req.id, req.imdb) {
+        (Some(id), _) => Movie::query_id(MovieId::from(id as u64), conn)?,
+        (None, Some(imdb)) => Movie::query_imdb(&imdb, conn)?,
+        (None, None) => return Err(invalid_argument!("id", "id or imdb of the movie").into()),
+    };
+    Ok(movie.to_pb())
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_get_movie(&self) -> impl Query<pb::GetReq> + '_ {
+        move |req: pb::GetReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -1,13 +0,0 @@
-use common::infra::Query;
-use crate::movie::rpc::MovieResolver;
-
-async fn execute() {
-
-}
-
-
-impl MovieResolver {
-    pub fn create_list(&self) {
-
-    }
-}
\ No newline at end of file
@@ -0,0 +1,31 @@
+use crate::movie::domain::movie::model::filter::MovieFilter;
+use crate::movie::domain::movie::model::movie::Movie;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::movie::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::ListReq, conn: &mut PgConnection) -> GrpcResult<pb::ListRes> {
+    let filter = MovieFilter::parse(req)?;
+    let (movies, total) = filter.list(conn)?;
+    let highlights = match filter.keyword() {
+        Some(keyword) => movies
+            .iter()
+            .filter_map(|movie| movie.highlight(keyword))
+            .collect(),
+        None => Default::default(),
+    };
+    Ok(pb::ListRes {
+        gets: movies.iter().map(Movie::to_pb).collect(),
+        total,
+        highlights,
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_movies(&self) -> impl Query<pb::ListReq> + '_ {
+        move |req: pb::ListReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
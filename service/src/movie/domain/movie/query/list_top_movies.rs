@@ -0,0 +1,36 @@
+use crate::movie::domain::movie::model::ranking::Ranking;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::movie::v1 as pb;
+use std::ops::DerefMut;
+
+const DEFAULT_LIMIT: i64 = 25;
+const MAX_LIMIT: i64 = 250;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListTopMoviesReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListTopMoviesRes> {
+    let chart = Ranking::chart(req.category.as_deref(), req.country.as_deref())?;
+    let (limit, offset) = req
+        .slice
+        .unwrap_or_default()
+        .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+    let (rankings, total, computed_at) = Ranking::list(&chart, limit, offset, conn)?;
+    Ok(pb::ListTopMoviesRes {
+        movies: rankings
+            .iter()
+            .map(|(ranking, movie)| ranking.to_pb(movie))
+            .collect(),
+        total,
+        computed_at: computed_at.map(|datetime| datetime.timestamp()),
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_top_movies(&self) -> impl Query<pb::ListTopMoviesReq> + '_ {
+        move |req: pb::ListTopMoviesReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
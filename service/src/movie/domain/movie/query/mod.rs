@@ -1 +1,3 @@
-pub mod list;
\ No newline at end of file
+pub mod get_movie;
+pub mod list_movies;
+pub mod list_top_movies;
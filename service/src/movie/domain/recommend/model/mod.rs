@@ -0,0 +1,2 @@
+pub mod recommendation;
+pub mod similarity;
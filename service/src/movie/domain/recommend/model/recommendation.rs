@@ -0,0 +1,80 @@
+use crate::movie::domain::movie::model::movie::Movie;
+use crate::movie::domain::score::model::rating::check_user;
+use crate::movie::domain::utils::map_internal;
+use common::internal;
+use common::status::prelude::*;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Double, SmallInt};
+use migration::t_movies;
+use proto::pb::movie::recommend::v1 as pb;
+use std::collections::HashMap;
+
+// ratings with fewer stars do not recommend their neighbours
+const MIN_SEED_STAR: i16 = 4;
+
+/// A movie recommended to a user, scored by the similarities to the movies the user
+/// rated high, each weighted by the stars above [`MIN_SEED_STAR`].
+#[derive(QueryableByName)]
+pub struct Recommendation {
+    #[diesel(sql_type = BigInt)]
+    mid: i64,
+    #[diesel(sql_type = Double)]
+    score: f64,
+    // the rated movie contributing the most to the score
+    #[diesel(sql_type = BigInt)]
+    because_of: i64,
+}
+
+impl Recommendation {
+    pub(in crate::movie::domain) fn to_pb(&self, movie: &Movie) -> pb::RecommendedMovie {
+        pb::RecommendedMovie {
+            movie: Some(movie.to_pb()),
+            score: self.score,
+            because_of: self.because_of,
+        }
+    }
+
+    /// Movies for the user, the highest score first, leaving out the movies the user
+    /// rated or marked.
+    pub(in crate::movie::domain) fn for_user(
+        user_id: i64,
+        limit: i64,
+        offset: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Vec<(Recommendation, Movie)>> {
+        check_user(user_id, conn)?;
+        let recommended: Vec<Recommendation> = diesel::sql_query(
+            "WITH weighted AS (
+                 SELECT s.mid, s.similar_mid, s.similarity * (r.star - $2 + 1) AS weight
+                 FROM t_user_ratings r JOIN t_movies_similarities s ON s.mid = r.mid
+                 WHERE r.uid = $1 AND r.star >= $2
+             )
+             SELECT w.similar_mid AS mid, sum(w.weight)::float8 AS score,
+                    (array_agg(w.mid ORDER BY w.weight DESC, w.mid))[1] AS because_of
+             FROM weighted w
+             WHERE NOT EXISTS (SELECT 1 FROM t_user_ratings r WHERE r.uid = $1 AND r.mid = w.similar_mid)
+               AND NOT EXISTS (SELECT 1 FROM t_user_marks m WHERE m.uid = $1 AND m.mid = w.similar_mid)
+             GROUP BY w.similar_mid
+             ORDER BY score DESC, w.similar_mid
+             LIMIT $3 OFFSET $4",
+        )
+        .bind::<BigInt, _>(user_id)
+        .bind::<SmallInt, _>(MIN_SEED_STAR)
+        .bind::<BigInt, _>(limit)
+        .bind::<BigInt, _>(offset)
+        .load(conn)
+        .map_err(|e| internal!(format!("Cannot recommend movies, err: {}", e)))?;
+
+        let mut movies: HashMap<i64, Movie> = t_movies::table
+            .filter(t_movies::id.eq_any(recommended.iter().map(|r| r.mid)))
+            .select((t_movies::id, t_movies::all_columns))
+            .load::<(i64, Movie)>(conn)
+            .map_err(map_internal)?
+            .into_iter()
+            .collect();
+        Ok(recommended
+            .into_iter()
+            .filter_map(|r| movies.remove(&r.mid).map(|movie| (r, movie)))
+            .collect())
+    }
+}
@@ -0,0 +1,349 @@
+use crate::movie::domain::movie::model::movie::Movie;
+use crate::movie::domain::utils::map_internal;
+use crate::movie::rpc::RecommendConfig;
+use chrono::NaiveDateTime;
+use common::internal;
+use common::status::prelude::*;
+use diesel::dsl::{max, now, sql};
+use diesel::pg::upsert::excluded;
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Timestamp};
+use migration::{t_movies, t_movies_similarities, t_user_ratings};
+use proto::pb::movie::recommend::v1 as pb;
+use std::collections::HashMap;
+
+// key of the advisory lock held while computing similarities
+const SIMILARITY_LOCK: i64 = 0x7369_6d69;
+// rows of an insert statement
+const BATCH_SIZE: usize = 1000;
+
+/// A neighbour of a movie, replaced as a whole whenever the similarities are computed.
+///
+/// Similarities are adjusted cosine over ratings, i.e. the cosine of the stars of
+/// users rating both movies, each star minus the mean star of its user, so that
+/// users rating everything high or low count as much as the others.
+#[derive(Queryable)]
+pub struct Similarity {
+    id: i64,
+    mid: i64,
+    similar_mid: i64,
+    similarity: f64,
+    common_cnt: i32,
+    computed_at: NaiveDateTime,
+}
+
+#[derive(Default)]
+struct Pair {
+    dot: f64,
+    // squares of deviations of the smaller and the larger movie id
+    squares: (f64, f64),
+    common: u32,
+}
+
+/// Neighbours of every movie as `(mid, similar_mid, similarity, common_cnt)`,
+/// `ratings` are `(uid, mid, star)` grouped by user, the latest first.
+fn compute(ratings: &[(i64, i64, i16)], conf: &RecommendConfig) -> Vec<(i64, i64, f64, i32)> {
+    let mut pairs: HashMap<(i64, i64), Pair> = HashMap::new();
+    let mut start = 0;
+    while start < ratings.len() {
+        let user = ratings[start].0;
+        let end = ratings[start..]
+            .iter()
+            .position(|(uid, _, _)| *uid != user)
+            .map_or(ratings.len(), |len| start + len);
+        let rated = &ratings[start..end];
+        start = end;
+        // a single rating is similar to nothing
+        if rated.len() < 2 {
+            continue;
+        }
+        let mean = rated.iter().map(|(_, _, star)| *star as f64).sum::<f64>() / rated.len() as f64;
+        // the pairs of a user grow quadratically with the ratings
+        let mut deviations: Vec<(i64, f64)> = rated
+            .iter()
+            .take(conf.max_user_ratings)
+            .map(|(_, mid, star)| (*mid, *star as f64 - mean))
+            .collect();
+        deviations.sort_unstable_by_key(|(mid, _)| *mid);
+        for (i, (a, dev_a)) in deviations.iter().enumerate() {
+            for (b, dev_b) in &deviations[i + 1..] {
+                let pair = pairs.entry((*a, *b)).or_default();
+                pair.dot += dev_a * dev_b;
+                pair.squares.0 += dev_a * dev_a;
+                pair.squares.1 += dev_b * dev_b;
+                pair.common += 1;
+            }
+        }
+    }
+
+    let mut neighbours: HashMap<i64, Vec<(i64, f64, u32)>> = HashMap::new();
+    for ((a, b), pair) in pairs {
+        let norm = pair.squares.0.sqrt() * pair.squares.1.sqrt();
+        if pair.common < conf.min_common || norm <= f64::EPSILON {
+            continue;
+        }
+        let similarity = (pair.dot / norm).min(1.0);
+        // dissimilar movies are no neighbours
+        if similarity <= 0.0 {
+            continue;
+        }
+        neighbours
+            .entry(a)
+            .or_default()
+            .push((b, similarity, pair.common));
+        neighbours
+            .entry(b)
+            .or_default()
+            .push((a, similarity, pair.common));
+    }
+    let mut similar = Vec::new();
+    for (mid, mut candidates) in neighbours {
+        candidates
+            .sort_unstable_by(|x, y| y.1.total_cmp(&x.1).then(y.2.cmp(&x.2)).then(x.0.cmp(&y.0)));
+        candidates.truncate(conf.neighbours);
+        similar.extend(
+            candidates
+                .into_iter()
+                .map(|(similar_mid, similarity, common)| {
+                    (mid, similar_mid, similarity, common as i32)
+                }),
+        );
+    }
+    similar
+}
+
+impl Similarity {
+    pub(in crate::movie::domain) fn to_pb(&self, movie: &Movie) -> pb::SimilarMovie {
+        pb::SimilarMovie {
+            movie: Some(movie.to_pb()),
+            similarity: self.similarity,
+        }
+    }
+
+    /// Compute the neighbours of every movie from all ratings in memory. Returns none
+    /// without computing if another instance is computing the similarities.
+    pub(in crate::movie) fn refresh(
+        conf: &RecommendConfig,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Option<usize>> {
+        use migration::t_movies_similarities::dsl::*;
+
+        conn.transaction::<Option<usize>, GrpcStatus, _>(|conn| {
+            let locked: bool = diesel::select(sql::<Bool>(&format!(
+                "pg_try_advisory_xact_lock({})",
+                SIMILARITY_LOCK
+            )))
+            .get_result(conn)
+            .map_err(map_internal)?;
+            if !locked {
+                return Ok(None);
+            }
+
+            let ratings: Vec<(i64, i64, i16)> = t_user_ratings::table
+                .select((
+                    t_user_ratings::uid,
+                    t_user_ratings::mid,
+                    t_user_ratings::star,
+                ))
+                .order((t_user_ratings::uid.asc(), t_user_ratings::updated_at.desc()))
+                .load(conn)
+                .map_err(map_internal)?;
+            let similar = compute(&ratings, conf);
+
+            // computed_at defaults to now(), the start of the transaction, rows not
+            // touched by the upsert are no neighbours any more
+            for batch in similar.chunks(BATCH_SIZE) {
+                let rows: Vec<_> = batch
+                    .iter()
+                    .map(|(movie_id, neighbour, value, common)| {
+                        (
+                            mid.eq(*movie_id),
+                            similar_mid.eq(*neighbour),
+                            similarity.eq(*value),
+                            common_cnt.eq(*common),
+                        )
+                    })
+                    .collect();
+                diesel::insert_into(t_movies_similarities)
+                    .values(rows)
+                    .on_conflict((mid, similar_mid))
+                    .do_update()
+                    .set((
+                        similarity.eq(excluded(similarity)),
+                        common_cnt.eq(excluded(common_cnt)),
+                        computed_at.eq(now),
+                    ))
+                    .execute(conn)
+                    .map_err(|e| internal!(format!("Cannot store similarities, err: {}", e)))?;
+            }
+
+            let started = sql::<Timestamp>("now()");
+            diesel::delete(t_movies_similarities.filter(computed_at.lt(started)))
+                .execute(conn)
+                .map_err(map_internal)?;
+
+            Ok(Some(similar.len()))
+        })
+    }
+
+    /// Neighbours of the movie, the most similar first, with when they were computed.
+    pub(in crate::movie::domain) fn list(
+        movie_id: i64,
+        limit: i64,
+        offset: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<(Similarity, Movie)>, Option<NaiveDateTime>)> {
+        let computed: Option<NaiveDateTime> = t_movies_similarities::table
+            .select(max(t_movies_similarities::computed_at))
+            .filter(t_movies_similarities::mid.eq(movie_id))
+            .first(conn)
+            .map_err(map_internal)?;
+        let similar: Vec<(Similarity, Movie)> = t_movies_similarities::table
+            .inner_join(t_movies::table.on(t_movies::id.eq(t_movies_similarities::similar_mid)))
+            .filter(t_movies_similarities::mid.eq(movie_id))
+            .select((t_movies_similarities::all_columns, t_movies::all_columns))
+            .order((
+                t_movies_similarities::similarity.desc(),
+                t_movies_similarities::similar_mid.asc(),
+            ))
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+            .map_err(map_internal)?;
+        Ok((similar, computed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conf(neighbours: usize, min_common: u32) -> RecommendConfig {
+        RecommendConfig {
+            neighbours,
+            min_common,
+            ..Default::default()
+        }
+    }
+
+    // sorted by (mid, similar_mid), the order of neighbours of different movies is arbitrary
+    fn sorted(mut similar: Vec<(i64, i64, f64, i32)>) -> Vec<(i64, i64, f64, i32)> {
+        similar.sort_unstable_by_key(|(mid, similar_mid, _, _)| (*mid, *similar_mid));
+        similar
+    }
+
+    fn assert_similar(actual: &[(i64, i64, f64, i32)], expected: &[(i64, i64, f64, i32)]) {
+        assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+        for (a, e) in actual.iter().zip(expected) {
+            assert_eq!((a.0, a.1, a.3), (e.0, e.1, e.3), "{:?}", actual);
+            assert!((a.2 - e.2).abs() < 1e-9, "{:?}", actual);
+        }
+    }
+
+    #[test]
+    fn centered_by_mean_of_user() {
+        // deviations of user 1 (mean 4) are (1, 0, -1), of user 2 (mean 4) are (1, 1, -2),
+        // user 3 rates a single movie and counts for nothing
+        let ratings = [
+            (1, 1, 5),
+            (1, 2, 4),
+            (1, 3, 3),
+            (2, 1, 5),
+            (2, 2, 5),
+            (2, 3, 2),
+            (3, 1, 1),
+        ];
+        let similar = sorted(compute(&ratings, &conf(20, 2)));
+        // (1, 2): 1 / (sqrt(2) * sqrt(1)), (1, 3) and (2, 3) are negative
+        let expected = 1.0 / 2f64.sqrt();
+        assert_similar(&similar, &[(1, 2, expected, 2), (2, 1, expected, 2)]);
+    }
+
+    #[test]
+    fn high_and_low_raters_count_the_same() {
+        // the raw stars are all alike, but both users like movie 1 more than movie 2
+        // and movie 3 the least, relative to their own means
+        let ratings = [
+            (1, 1, 5),
+            (1, 2, 5),
+            (1, 3, 4),
+            (2, 1, 2),
+            (2, 2, 2),
+            (2, 3, 1),
+        ];
+        let similar = sorted(compute(&ratings, &conf(20, 2)));
+        // deviations are (1/3, 1/3, -2/3) for both users, (1, 3) and (2, 3) are negative
+        assert_similar(&similar, &[(1, 2, 1.0, 2), (2, 1, 1.0, 2)]);
+    }
+
+    #[test]
+    fn dissimilar_dropped() {
+        // user 2 likes what user 1 dislikes, every similarity is -1
+        let ratings = [(1, 1, 5), (1, 2, 1), (2, 1, 1), (2, 2, 5)];
+        assert!(compute(&ratings, &conf(20, 1)).is_empty());
+        // a similarity of 0 is no neighbour either, deviations are (2, 0, -2) and
+        // (0, 2, -2), then (1, 2) is 0 and the others are negative
+        let ratings = [
+            (1, 1, 5),
+            (1, 2, 3),
+            (1, 3, 1),
+            (2, 1, 3),
+            (2, 2, 5),
+            (2, 3, 1),
+        ];
+        assert!(compute(&ratings, &conf(20, 1)).is_empty());
+    }
+
+    #[test]
+    fn min_common_required() {
+        let ratings = [
+            (1, 1, 5),
+            (1, 2, 5),
+            (1, 3, 2),
+            (2, 1, 4),
+            (2, 2, 4),
+            (2, 3, 1),
+            (3, 1, 2),
+            (3, 2, 2),
+            (3, 3, 5),
+        ];
+        // (1, 2) is rated by 3 users with deviations (1, 1), (1, 1) and (-1, -1)
+        let similar = sorted(compute(&ratings, &conf(20, 3)));
+        assert_similar(&similar, &[(1, 2, 1.0, 3), (2, 1, 1.0, 3)]);
+        assert!(compute(&ratings, &conf(20, 4)).is_empty());
+    }
+
+    #[test]
+    fn neighbours_truncated() {
+        // deviations of user 1 (mean 3.5) are (1.5, 1.5, -0.5, -2.5), of user 2 (mean 3)
+        // are (1, 0, 1, -2), movie 1 is similar to movie 2 and then movie 3
+        let ratings = [
+            (1, 1, 5),
+            (1, 2, 5),
+            (1, 3, 3),
+            (1, 4, 1),
+            (2, 1, 4),
+            (2, 2, 3),
+            (2, 3, 4),
+            (2, 4, 1),
+        ];
+        let one_two = 2.25 / (3.25f64.sqrt() * 2.25f64.sqrt());
+        let one_three = 0.25 / (3.25f64.sqrt() * 1.25f64.sqrt());
+        let similar = sorted(compute(&ratings, &conf(20, 2)));
+        assert_similar(
+            &similar,
+            &[
+                (1, 2, one_two, 2),
+                (1, 3, one_three, 2),
+                (2, 1, one_two, 2),
+                (3, 1, one_three, 2),
+            ],
+        );
+        // only the most similar is kept for movie 1
+        let similar = sorted(compute(&ratings, &conf(1, 2)));
+        assert_similar(
+            &similar,
+            &[(1, 2, one_two, 2), (2, 1, one_two, 2), (3, 1, one_three, 2)],
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod recommend_for_user;
+pub mod similar_movies;
@@ -0,0 +1,33 @@
+use crate::movie::domain::recommend::model::recommendation::Recommendation;
+use crate::movie::domain::utils::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::recommend::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::RecommendForUserReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::RecommendForUserRes> {
+    let (limit, offset) = req
+        .slice
+        .unwrap_or_default()
+        .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+    let recommended = Recommendation::for_user(req.uid, limit, offset, conn)?;
+    Ok(pb::RecommendForUserRes {
+        movies: recommended
+            .iter()
+            .map(|(recommendation, movie)| recommendation.to_pb(movie))
+            .collect(),
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_recommend_for_user(
+        &self,
+    ) -> impl Query<pb::RecommendForUserReq> + '_ {
+        move |req: pb::RecommendForUserReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
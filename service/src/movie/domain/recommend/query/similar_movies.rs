@@ -0,0 +1,37 @@
+use crate::movie::domain::movie::model::movie::{Movie, MovieId};
+use crate::movie::domain::recommend::model::similarity::Similarity;
+use crate::movie::domain::utils::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::recommend::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::SimilarMoviesReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::SimilarMoviesRes> {
+    let (limit, offset) = req
+        .slice
+        .unwrap_or_default()
+        .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+    let (similar, computed_at) = Similarity::list(req.movie_id, limit, offset, conn)?;
+    // a movie without neighbours has no similarities yet, unless it does not exist
+    if similar.is_empty() {
+        Movie::query_id(MovieId::from(req.movie_id as u64), conn)?;
+    }
+    Ok(pb::SimilarMoviesRes {
+        movies: similar
+            .iter()
+            .map(|(similarity, movie)| similarity.to_pb(movie))
+            .collect(),
+        computed_at: computed_at.map(|datetime| datetime.timestamp()),
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_similar_movies(&self) -> impl Query<pb::SimilarMoviesReq> + '_ {
+        move |req: pb::SimilarMoviesReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,19 @@
+use crate::movie::domain::review::model::review::Review;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::review::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::EditReviewReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Review::edit(req.id, req.uid, &req.title, &req.content, req.spoiler, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_edit_review(&self) -> impl Command<pb::EditReviewReq> + '_ {
+        move |req: pb::EditReviewReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
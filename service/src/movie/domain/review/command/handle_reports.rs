@@ -0,0 +1,21 @@
+use crate::movie::domain::review::model::report::Report;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::review::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::HandleReportsReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Report::handle(req.review_id, req.admin_id, req.action, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_handle_reports(
+        &self,
+    ) -> impl Command<pb::HandleReportsReq> + '_ {
+        move |req: pb::HandleReportsReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod del_comment;
+pub mod del_review;
+pub mod edit_review;
+pub mod handle_reports;
+pub mod post_review;
+pub mod put_comment;
+pub mod report_review;
+pub mod unvote_comment;
+pub mod unvote_review;
+pub mod vote_comment;
+pub mod vote_review;
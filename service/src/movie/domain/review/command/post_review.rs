@@ -0,0 +1,25 @@
+use crate::movie::domain::review::model::review::Review;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::review::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::PostReviewReq, conn: &mut PgConnection) -> GrpcResult<pb::PostReviewRes> {
+    let id = Review::post(
+        req.uid,
+        req.movie_id,
+        &req.title,
+        &req.content,
+        req.spoiler,
+        conn,
+    )?;
+    Ok(pb::PostReviewRes { id })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_post_review(&self) -> impl Command<pb::PostReviewReq> + '_ {
+        move |req: pb::PostReviewReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,19 @@
+use crate::movie::domain::review::model::report::Report;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::review::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::ReportReviewReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Report::report(req.id, req.uid, req.reason, req.detail.as_deref(), conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_report_review(&self) -> impl Command<pb::ReportReviewReq> + '_ {
+        move |req: pb::ReportReviewReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
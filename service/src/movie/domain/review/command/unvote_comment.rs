@@ -0,0 +1,21 @@
+use crate::movie::domain::review::model::comment::Comment;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::review::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::UnvoteCommentReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Comment::unvote(req.id, req.uid, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_unvote_comment(
+        &self,
+    ) -> impl Command<pb::UnvoteCommentReq> + '_ {
+        move |req: pb::UnvoteCommentReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,19 @@
+use crate::movie::domain::review::model::review::Review;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::review::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::UnvoteReviewReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Review::unvote(req.id, req.uid, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_unvote_review(&self) -> impl Command<pb::UnvoteReviewReq> + '_ {
+        move |req: pb::UnvoteReviewReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::movie::domain::score::model::rating::{check_user, MAX_COMMENT_LEN};
+use crate::movie::domain::utils::map_internal;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{internal, invalid_argument, not_found};
+use diesel::prelude::*;
+use migration::{t_user_ratings, t_user_ratings_votes};
+use proto::pb::movie::review::v1 as pb;
+use tonic::Status;
+
+/// A short comment, which is the comment of a rating, so that a comment always comes
+/// with the stars of its author.
+#[derive(Queryable)]
+pub struct Comment {
+    id: i64,
+    uid: i64,
+    mid: i64,
+    star: i16,
+    comment: Option<String>,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+    helpful_cnt: i64,
+}
+
+impl Comment {
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::Comment {
+        pb::Comment {
+            id: self.id,
+            movie_id: self.mid,
+            uid: self.uid,
+            star: self.star as i32,
+            content: self.comment.clone().unwrap_or_default(),
+            helpful_cnt: self.helpful_cnt,
+            created_at: self.created_at.timestamp(),
+            updated_at: self.updated_at.timestamp(),
+        }
+    }
+
+    /// Comment on a movie the user has rated, the previous comment is replaced.
+    pub(in crate::movie::domain) fn put(
+        user_id: i64,
+        movie_id: i64,
+        content: &str,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_ratings::dsl::*;
+
+        let content = content.trim();
+        if content.is_empty() || content.chars().count() > MAX_COMMENT_LEN {
+            return Err(invalid_argument!("content", "1 to 350 characters").into());
+        }
+        check_user(user_id, conn)?;
+        let updated = diesel::update(
+            t_user_ratings
+                .filter(uid.eq(user_id))
+                .filter(mid.eq(movie_id)),
+        )
+        .set(comment.eq(content))
+        .execute(conn)
+        .map_err(map_internal)?;
+        if updated == 0 {
+            return Err(Status::failed_precondition(format!(
+                "Movie({}) is not rated by user({}), rate it before commenting",
+                movie_id, user_id
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Delete the comment but keep the rating, the votes of the comment are deleted with it.
+    pub(in crate::movie::domain) fn delete(
+        user_id: i64,
+        movie_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_ratings::dsl::*;
+
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            let rating_id: Option<i64> = diesel::update(
+                t_user_ratings
+                    .filter(uid.eq(user_id))
+                    .filter(mid.eq(movie_id))
+                    .filter(comment.is_not_null()),
+            )
+            .set((comment.eq(None::<String>), helpful_cnt.eq(0)))
+            .returning(id)
+            .get_result(conn)
+            .optional()
+            .map_err(map_internal)?;
+            let rating_id = rating_id.ok_or_else(|| {
+                not_found!(format!(
+                    "comment of movie({}) by user({})",
+                    movie_id, user_id
+                ))
+            })?;
+            diesel::delete(
+                t_user_ratings_votes::table.filter(t_user_ratings_votes::rid.eq(rating_id)),
+            )
+            .execute(conn)
+            .map_err(map_internal)?;
+            Ok(())
+        })
+    }
+
+    // returns the author of the locked comment
+    fn lock(comment_id: i64, conn: &mut PgConnection) -> GrpcResult<i64> {
+        let author: Option<i64> = t_user_ratings::table
+            .select(t_user_ratings::uid)
+            .find(comment_id)
+            .filter(t_user_ratings::comment.is_not_null())
+            .for_update()
+            .first(conn)
+            .optional()
+            .map_err(map_internal)?;
+        author.ok_or_else(|| not_found!(format!("comment({})", comment_id)).into())
+    }
+
+    fn bump_votes(comment_id: i64, delta: i64, conn: &mut PgConnection) -> GrpcResult<()> {
+        use migration::t_user_ratings::dsl::*;
+
+        diesel::update(t_user_ratings.find(comment_id))
+            .set(helpful_cnt.eq(helpful_cnt + delta))
+            .execute(conn)
+            .map_err(map_internal)?;
+        Ok(())
+    }
+
+    /// Find the comment helpful, voting again changes nothing. Authors cannot vote their
+    /// own comments.
+    pub(in crate::movie::domain) fn vote(
+        comment_id: i64,
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_ratings_votes::dsl::*;
+
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            if Self::lock(comment_id, conn)? == user_id {
+                return Err(Status::failed_precondition(format!(
+                    "User({}) cannot vote own comment({})",
+                    user_id, comment_id
+                ))
+                .into());
+            }
+            let inserted = diesel::insert_into(t_user_ratings_votes)
+                .values((rid.eq(comment_id), uid.eq(user_id)))
+                .on_conflict((rid, uid))
+                .do_nothing()
+                .execute(conn)
+                .map_err(|e| internal!(format!("Cannot vote comment, err: {}", e)))?;
+            if inserted == 0 {
+                return Ok(());
+            }
+            Self::bump_votes(comment_id, 1, conn)
+        })
+    }
+
+    pub(in crate::movie::domain) fn unvote(
+        comment_id: i64,
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_ratings_votes::dsl::*;
+
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            Self::lock(comment_id, conn)?;
+            let deleted = diesel::delete(
+                t_user_ratings_votes
+                    .filter(rid.eq(comment_id))
+                    .filter(uid.eq(user_id)),
+            )
+            .execute(conn)
+            .map_err(map_internal)?;
+            if deleted == 0 {
+                return Err(not_found!(format!(
+                    "vote of comment({}) by user({})",
+                    comment_id, user_id
+                ))
+                .into());
+            }
+            Self::bump_votes(comment_id, -1, conn)
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use crate::movie::domain::review::model::comment::Comment;
+use crate::movie::domain::review::model::review::Review;
+use crate::movie::domain::utils::{DEFAULT_LIMIT, MAX_LIMIT};
+use common::status::prelude::*;
+use common::{internal, invalid_argument};
+use diesel::dsl::not;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use migration::{t_movies_reviews, t_user_ratings};
+use proto::pb::common::v1::Slice;
+use proto::pb::movie::review::v1 as pb;
+
+/// Filters of listing reviews or comments, of a movie, of an author, or both. Authors
+/// in `hidden` are left out.
+pub struct ReviewFilter {
+    movie_id: Option<i64>,
+    uid: Option<i64>,
+    hidden: Vec<i64>,
+    order: pb::ReviewOrder,
+    limit: i64,
+    offset: i64,
+}
+
+impl ReviewFilter {
+    pub(in crate::movie::domain) fn parse(
+        movie_id: Option<i64>,
+        uid: Option<i64>,
+        hidden: Vec<i64>,
+        order: i32,
+        slice: Option<Slice>,
+    ) -> GrpcResult<ReviewFilter> {
+        if movie_id.is_none() && uid.is_none() {
+            return Err(invalid_argument!("movie_id", "a movie id or an author id").into());
+        }
+        let order = pb::ReviewOrder::from_i32(order)
+            .ok_or_else(|| invalid_argument!("order", "HELPFUL or NEWEST"))?;
+        let (limit, offset) = slice
+            .unwrap_or_default()
+            .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+        Ok(ReviewFilter {
+            movie_id,
+            uid,
+            hidden,
+            order,
+            limit,
+            offset,
+        })
+    }
+
+    fn reviews(&self) -> t_movies_reviews::BoxedQuery<'_, Pg> {
+        use migration::t_movies_reviews::dsl::*;
+
+        let mut query = t_movies_reviews.filter(removed_at.is_null()).into_boxed();
+        if let Some(movie_id) = self.movie_id {
+            query = query.filter(mid.eq(movie_id));
+        }
+        if let Some(user_id) = self.uid {
+            query = query.filter(uid.eq(user_id));
+        }
+        query.filter(not(uid.eq_any(&self.hidden)))
+    }
+
+    // ratings without a comment are not comments
+    fn comments(&self) -> t_user_ratings::BoxedQuery<'_, Pg> {
+        use migration::t_user_ratings::dsl::*;
+
+        let mut query = t_user_ratings.filter(comment.is_not_null()).into_boxed();
+        if let Some(movie_id) = self.movie_id {
+            query = query.filter(mid.eq(movie_id));
+        }
+        if let Some(user_id) = self.uid {
+            query = query.filter(uid.eq(user_id));
+        }
+        query.filter(not(uid.eq_any(&self.hidden)))
+    }
+
+    pub(in crate::movie::domain) fn list_reviews(
+        &self,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<Review>, i64)> {
+        use migration::t_movies_reviews::dsl::*;
+
+        let map_err = |e| internal!(format!("Database connection error: {}", e));
+        let total: i64 = self.reviews().count().get_result(conn).map_err(map_err)?;
+        let query = match self.order {
+            pb::ReviewOrder::Helpful => self
+                .reviews()
+                .order(helpful_score.desc())
+                .then_order_by(helpful_cnt.desc())
+                .then_order_by(created_at.desc()),
+            pb::ReviewOrder::Newest => self.reviews().order(created_at.desc()),
+        };
+        let reviews: Vec<Review> = query
+            .then_order_by(id.desc())
+            .limit(self.limit)
+            .offset(self.offset)
+            .load(conn)
+            .map_err(map_err)?;
+        Ok((reviews, total))
+    }
+
+    pub(in crate::movie::domain) fn list_comments(
+        &self,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<Comment>, i64)> {
+        use migration::t_user_ratings::dsl::*;
+
+        let map_err = |e| internal!(format!("Database connection error: {}", e));
+        let total: i64 = self.comments().count().get_result(conn).map_err(map_err)?;
+        let query = match self.order {
+            pb::ReviewOrder::Helpful => self
+                .comments()
+                .order(helpful_cnt.desc())
+                .then_order_by(updated_at.desc()),
+            pb::ReviewOrder::Newest => self.comments().order(updated_at.desc()),
+        };
+        let comments: Vec<Comment> = query
+            .then_order_by(id.desc())
+            .limit(self.limit)
+            .offset(self.offset)
+            .load(conn)
+            .map_err(map_err)?;
+        Ok((comments, total))
+    }
+}
@@ -0,0 +1,4 @@
+pub mod comment;
+pub mod filter;
+pub mod report;
+pub mod review;
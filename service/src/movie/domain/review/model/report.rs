@@ -0,0 +1,193 @@
+use crate::movie::domain::review::model::review::Review;
+use crate::movie::domain::score::model::rating::check_user;
+use crate::movie::domain::utils::map_internal;
+use crate::user::domain::user::model::admin::Admin;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{already_exists, internal, invalid_argument, not_found};
+use diesel::dsl::now;
+use diesel::prelude::*;
+use migration::{t_movies_reviews, t_movies_reviews_reports};
+use proto::pb::movie::review::v1 as pb;
+
+const MAX_DETAIL_LEN: usize = 500;
+const PENDING: &str = "pending";
+const DISMISSED: &str = "dismissed";
+const REMOVED: &str = "removed";
+
+/// A report of a review, pending reports are the moderation queue. Handling reports
+/// either dismisses them or removes the review, a removed review is hidden but kept
+/// along with its handled reports.
+#[derive(Queryable)]
+pub struct Report {
+    id: i64,
+    uid: i64,
+    rid: i64,
+    reason: String,
+    detail: Option<String>,
+    status: String,
+    handled_by: Option<i64>,
+    handled_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+#[inline]
+fn reason_name(reason: pb::ReportReason) -> &'static str {
+    match reason {
+        pb::ReportReason::Spam => "spam",
+        pb::ReportReason::Abuse => "abuse",
+        pb::ReportReason::Spoiler => "spoiler",
+        pb::ReportReason::Copyright => "copyright",
+        pb::ReportReason::Other => "other",
+    }
+}
+
+#[inline]
+fn reason_of(name: &str) -> pb::ReportReason {
+    match name {
+        "spam" => pb::ReportReason::Spam,
+        "abuse" => pb::ReportReason::Abuse,
+        "spoiler" => pb::ReportReason::Spoiler,
+        "copyright" => pb::ReportReason::Copyright,
+        _ => pb::ReportReason::Other,
+    }
+}
+
+impl Report {
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::Report {
+        pb::Report {
+            id: self.id,
+            review_id: self.rid,
+            uid: self.uid,
+            reason: reason_of(&self.reason) as i32,
+            detail: self.detail.clone(),
+            created_at: self.created_at.timestamp(),
+        }
+    }
+
+    pub(in crate::movie::domain) fn report(
+        review_id: i64,
+        user_id: i64,
+        reason: i32,
+        detail: Option<&str>,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_movies_reviews_reports::dsl;
+
+        let reason = pb::ReportReason::from_i32(reason).ok_or_else(|| {
+            invalid_argument!("reason", "SPAM, ABUSE, SPOILER, COPYRIGHT or OTHER")
+        })?;
+        let detail = detail.map(str::trim).filter(|detail| !detail.is_empty());
+        if detail.map_or(false, |detail| detail.chars().count() > MAX_DETAIL_LEN) {
+            return Err(invalid_argument!("detail", "no more than 500 characters").into());
+        }
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            Review::query_id(review_id, conn)?;
+            let existed: Option<i64> = dsl::t_movies_reviews_reports
+                .select(dsl::id)
+                .filter(dsl::rid.eq(review_id))
+                .filter(dsl::uid.eq(user_id))
+                .first(conn)
+                .optional()
+                .map_err(map_internal)?;
+            if existed.is_some() {
+                return Err(already_exists!(format!(
+                    "report of review({}) by user({})",
+                    review_id, user_id
+                ))
+                .into());
+            }
+            diesel::insert_into(dsl::t_movies_reviews_reports)
+                .values((
+                    dsl::rid.eq(review_id),
+                    dsl::uid.eq(user_id),
+                    dsl::reason.eq(reason_name(reason)),
+                    dsl::detail.eq(detail),
+                ))
+                .execute(conn)
+                .map_err(|e| internal!(format!("Cannot report review, err: {}", e)))?;
+            Ok(())
+        })
+    }
+
+    /// Pending reports, the oldest first.
+    pub(in crate::movie::domain) fn list_pending(
+        admin_id: i64,
+        limit: i64,
+        offset: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<Report>, i64)> {
+        use migration::t_movies_reviews_reports::dsl::*;
+
+        // moderated by admins, the same check as the user admin service
+        Admin::authorize(admin_id, conn)?;
+        let total: i64 = t_movies_reviews_reports
+            .filter(status.eq(PENDING))
+            .count()
+            .get_result(conn)
+            .map_err(map_internal)?;
+        let reports: Vec<Report> = t_movies_reviews_reports
+            .filter(status.eq(PENDING))
+            .order((created_at.asc(), id.asc()))
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+            .map_err(map_internal)?;
+        Ok((reports, total))
+    }
+
+    /// Handle all pending reports of the review at once.
+    pub(in crate::movie::domain) fn handle(
+        review_id: i64,
+        admin_id: i64,
+        action: i32,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_movies_reviews_reports::dsl::*;
+
+        let action = pb::ReportAction::from_i32(action)
+            .ok_or_else(|| invalid_argument!("action", "DISMISS or REMOVE"))?;
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            Admin::authorize(admin_id, conn)?;
+            let pending = t_movies_reviews_reports
+                .filter(rid.eq(review_id))
+                .filter(status.eq(PENDING));
+            let pending_cnt: i64 = pending
+                .clone()
+                .count()
+                .get_result(conn)
+                .map_err(map_internal)?;
+            if pending_cnt == 0 {
+                return Err(not_found!(format!("pending reports of review({})", review_id)).into());
+            }
+            let handled = match action {
+                pb::ReportAction::Dismiss => DISMISSED,
+                pb::ReportAction::Remove => REMOVED,
+            };
+            diesel::update(pending)
+                .set((
+                    status.eq(handled),
+                    handled_by.eq(admin_id),
+                    handled_at.eq(now),
+                ))
+                .execute(conn)
+                .map_err(map_internal)?;
+            if action == pb::ReportAction::Remove {
+                diesel::update(t_movies_reviews::table.find(review_id))
+                    .set(t_movies_reviews::removed_at.eq(now))
+                    .execute(conn)
+                    .map_err(map_internal)?;
+            }
+            tracing::info!(
+                "Admin({}) handled {} reports of review({}) with {:?}",
+                admin_id,
+                pending_cnt,
+                review_id,
+                action
+            );
+            Ok(())
+        })
+    }
+}
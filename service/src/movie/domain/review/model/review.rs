@@ -0,0 +1,352 @@
+use crate::movie::domain::movie::model::movie::{Movie, MovieId};
+use crate::movie::domain::score::model::rating::check_user;
+use crate::movie::domain::utils::map_internal;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{already_exists, internal, invalid_argument, not_found};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Double};
+use migration::{t_movies_reviews, t_user_ratings};
+use proto::pb::movie::review::v1 as pb;
+use std::collections::HashMap;
+use tonic::Status;
+
+const MAX_TITLE_LEN: usize = 64;
+const MAX_CONTENT_LEN: usize = 20000;
+
+#[derive(Queryable)]
+pub struct Review {
+    id: i64,
+    uid: i64,
+    mid: i64,
+    title: String,
+    content: String,
+    spoiler: bool,
+    helpful_cnt: i64,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+    unhelpful_cnt: i64,
+    helpful_score: f64,
+    removed_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = t_movies_reviews)]
+struct NewReview<'a> {
+    uid: i64,
+    mid: i64,
+    title: &'a str,
+    content: &'a str,
+    spoiler: bool,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = t_movies_reviews)]
+struct EditReview<'a> {
+    title: &'a str,
+    content: &'a str,
+    spoiler: bool,
+}
+
+// returns the trimmed title and content
+fn check_review<'a>(title: &'a str, content: &'a str) -> GrpcResult<(&'a str, &'a str)> {
+    let (title, content) = (title.trim(), content.trim());
+    if title.is_empty() || title.chars().count() > MAX_TITLE_LEN {
+        return Err(invalid_argument!("title", "1 to 64 characters").into());
+    }
+    if content.is_empty() || content.chars().count() > MAX_CONTENT_LEN {
+        return Err(invalid_argument!("content", "1 to 20000 characters").into());
+    }
+    Ok((title, content))
+}
+
+impl Review {
+    /// `stars` are loaded by [`Review::stars`].
+    pub(in crate::movie::domain) fn to_pb(&self, stars: &HashMap<(i64, i64), i16>) -> pb::Review {
+        pb::Review {
+            id: self.id,
+            movie_id: self.mid,
+            uid: self.uid,
+            title: self.title.clone(),
+            content: self.content.clone(),
+            spoiler: self.spoiler,
+            star: stars.get(&(self.uid, self.mid)).map(|star| *star as i32),
+            helpful_cnt: self.helpful_cnt,
+            created_at: self.created_at.timestamp(),
+            updated_at: self.updated_at.timestamp(),
+            unhelpful_cnt: self.unhelpful_cnt,
+        }
+    }
+
+    /// Stars the authors rated the reviewed movies, keyed by `(uid, mid)`.
+    pub(in crate::movie::domain) fn stars(
+        reviews: &[Review],
+        conn: &mut PgConnection,
+    ) -> GrpcResult<HashMap<(i64, i64), i16>> {
+        if reviews.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let uids: Vec<i64> = reviews.iter().map(|review| review.uid).collect();
+        let mids: Vec<i64> = reviews.iter().map(|review| review.mid).collect();
+        // may load a few more ratings than needed, which are just ignored
+        let stars: Vec<(i64, i64, i16)> = t_user_ratings::table
+            .select((
+                t_user_ratings::uid,
+                t_user_ratings::mid,
+                t_user_ratings::star,
+            ))
+            .filter(t_user_ratings::uid.eq_any(uids))
+            .filter(t_user_ratings::mid.eq_any(mids))
+            .load(conn)
+            .map_err(map_internal)?;
+        Ok(stars
+            .into_iter()
+            .map(|(user_id, movie_id, star)| ((user_id, movie_id), star))
+            .collect())
+    }
+
+    pub(in crate::movie::domain) fn query_id(
+        review_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Review> {
+        use migration::t_movies_reviews::dsl::*;
+
+        let review: Option<Review> = t_movies_reviews
+            .find(review_id)
+            .filter(removed_at.is_null())
+            .first(conn)
+            .optional()
+            .map_err(map_internal)?;
+        review.ok_or_else(|| not_found!(format!("review({})", review_id)).into())
+    }
+
+    pub(in crate::movie::domain) fn post(
+        user_id: i64,
+        movie_id: i64,
+        title: &str,
+        content: &str,
+        spoiler: bool,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<i64> {
+        use migration::t_movies_reviews::dsl;
+
+        let (title, content) = check_review(title, content)?;
+        conn.transaction::<i64, GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            Movie::query_id(MovieId::from(movie_id as u64), conn)?;
+            let existed: Option<(i64, Option<NaiveDateTime>)> = dsl::t_movies_reviews
+                .select((dsl::id, dsl::removed_at))
+                .filter(dsl::uid.eq(user_id))
+                .filter(dsl::mid.eq(movie_id))
+                .first(conn)
+                .optional()
+                .map_err(map_internal)?;
+            match existed {
+                Some((_, Some(_))) => {
+                    return Err(Status::failed_precondition(format!(
+                        "Review of movie({}) by user({}) is removed",
+                        movie_id, user_id
+                    ))
+                    .into());
+                }
+                Some((existed, None)) => {
+                    return Err(already_exists!(format!(
+                        "review({}) of movie({}) by user({})",
+                        existed, movie_id, user_id
+                    ))
+                    .into());
+                }
+                None => {}
+            }
+            let review_id: i64 = diesel::insert_into(dsl::t_movies_reviews)
+                .values(NewReview {
+                    uid: user_id,
+                    mid: movie_id,
+                    title,
+                    content,
+                    spoiler,
+                })
+                .returning(dsl::id)
+                .get_result(conn)
+                .map_err(|e| internal!(format!("Cannot post review, err: {}", e)))?;
+            Ok(review_id)
+        })
+    }
+
+    // only the author may change the review
+    fn check_author(&self, user_id: i64) -> GrpcResult<()> {
+        if self.uid != user_id {
+            return Err(Status::permission_denied(format!(
+                "Review({}) is not written by user({})",
+                self.id, user_id
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    pub(in crate::movie::domain) fn edit(
+        review_id: i64,
+        user_id: i64,
+        title: &str,
+        content: &str,
+        spoiler: bool,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        let edit = {
+            let (title, content) = check_review(title, content)?;
+            EditReview {
+                title,
+                content,
+                spoiler,
+            }
+        };
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            Self::query_id(review_id, conn)?.check_author(user_id)?;
+            diesel::update(t_movies_reviews::table.find(review_id))
+                .set(&edit)
+                .execute(conn)
+                .map_err(map_internal)?;
+            Ok(())
+        })
+    }
+
+    pub(in crate::movie::domain) fn delete(
+        review_id: i64,
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_movies_reviews::dsl::*;
+
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            Self::query_id(review_id, conn)?.check_author(user_id)?;
+            diesel::delete(t_movies_reviews.find(review_id))
+                .execute(conn)
+                .map_err(map_internal)?;
+            Ok(())
+        })
+    }
+
+    // returns the author of the locked review
+    fn lock(review_id: i64, conn: &mut PgConnection) -> GrpcResult<i64> {
+        let author: Option<i64> = t_movies_reviews::table
+            .select(t_movies_reviews::uid)
+            .find(review_id)
+            .filter(t_movies_reviews::removed_at.is_null())
+            .for_update()
+            .first(conn)
+            .optional()
+            .map_err(map_internal)?;
+        author.ok_or_else(|| not_found!(format!("review({})", review_id)).into())
+    }
+
+    // add the deltas to the counts, helpful_score is computed from the counts before
+    // the update plus the deltas
+    fn bump_votes(review_id: i64, up: i64, down: i64, conn: &mut PgConnection) -> GrpcResult<()> {
+        use migration::t_movies_reviews::dsl::*;
+
+        diesel::update(t_movies_reviews.find(review_id))
+            .set((
+                helpful_cnt.eq(helpful_cnt + up),
+                unhelpful_cnt.eq(unhelpful_cnt + down),
+                helpful_score.eq(sql::<Double>("douban_wilson_lower_bound(helpful_cnt + ")
+                    .bind::<BigInt, _>(up)
+                    .sql(", unhelpful_cnt + ")
+                    .bind::<BigInt, _>(down)
+                    .sql(")")),
+            ))
+            .execute(conn)
+            .map_err(map_internal)?;
+        Ok(())
+    }
+
+    /// Vote the review, the previous vote of the user is replaced. Authors cannot
+    /// vote their own reviews.
+    pub(in crate::movie::domain) fn vote(
+        review_id: i64,
+        user_id: i64,
+        vote: i32,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_movies_reviews_votes::dsl::*;
+
+        let vote =
+            pb::Vote::from_i32(vote).ok_or_else(|| invalid_argument!("vote", "UP or DOWN"))?;
+        let up = vote == pb::Vote::Up;
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            if Self::lock(review_id, conn)? == user_id {
+                return Err(Status::failed_precondition(format!(
+                    "User({}) cannot vote own review({})",
+                    user_id, review_id
+                ))
+                .into());
+            }
+            let previous: Option<bool> = t_movies_reviews_votes
+                .select(helpful)
+                .filter(rid.eq(review_id))
+                .filter(uid.eq(user_id))
+                .first(conn)
+                .optional()
+                .map_err(map_internal)?;
+            match previous {
+                Some(previous) if previous == up => Ok(()),
+                Some(_) => {
+                    diesel::update(
+                        t_movies_reviews_votes
+                            .filter(rid.eq(review_id))
+                            .filter(uid.eq(user_id)),
+                    )
+                    .set(helpful.eq(up))
+                    .execute(conn)
+                    .map_err(map_internal)?;
+                    // the vote moves from one count to the other
+                    let delta = if up { 1 } else { -1 };
+                    Self::bump_votes(review_id, delta, -delta, conn)
+                }
+                None => {
+                    diesel::insert_into(t_movies_reviews_votes)
+                        .values((rid.eq(review_id), uid.eq(user_id), helpful.eq(up)))
+                        .execute(conn)
+                        .map_err(|e| internal!(format!("Cannot vote review, err: {}", e)))?;
+                    let (up, down) = if up { (1, 0) } else { (0, 1) };
+                    Self::bump_votes(review_id, up, down, conn)
+                }
+            }
+        })
+    }
+
+    pub(in crate::movie::domain) fn unvote(
+        review_id: i64,
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_movies_reviews_votes::dsl::*;
+
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            Self::lock(review_id, conn)?;
+            let previous: Option<bool> = diesel::delete(
+                t_movies_reviews_votes
+                    .filter(rid.eq(review_id))
+                    .filter(uid.eq(user_id)),
+            )
+            .returning(helpful)
+            .get_result(conn)
+            .optional()
+            .map_err(map_internal)?;
+            match previous {
+                Some(true) => Self::bump_votes(review_id, -1, 0, conn),
+                Some(false) => Self::bump_votes(review_id, 0, -1, conn),
+                None => Err(not_found!(format!(
+                    "vote of review({}) by user({})",
+                    review_id, user_id
+                ))
+                .into()),
+            }
+        })
+    }
+}
@@ -0,0 +1,21 @@
+use crate::movie::domain::review::model::review::Review;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::review::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::GetReviewReq, conn: &mut PgConnection) -> GrpcResult<pb::GetReviewRes> {
+    let review = Review::query_id(req.id, conn)?;
+    let stars = Review::stars(std::slice::from_ref(&review), conn)?;
+    Ok(pb::GetReviewRes {
+        review: Some(review.to_pb(&stars)),
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_get_review(&self) -> impl Query<pb::GetReviewReq> + '_ {
+        move |req: pb::GetReviewReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::movie::domain::review::model::comment::Comment;
+use crate::movie::domain::review::model::filter::ReviewFilter;
+use crate::movie::rpc::MovieResolver;
+use crate::user::domain::follow::model::block::Block;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::review::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListCommentsReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListCommentsRes> {
+    let hidden = match req.viewer {
+        Some(viewer) => Block::hidden_from(viewer, conn)?,
+        None => vec![],
+    };
+    let filter = ReviewFilter::parse(req.movie_id, req.uid, hidden, req.order, req.slice)?;
+    let (comments, total) = filter.list_comments(conn)?;
+    Ok(pb::ListCommentsRes {
+        comments: comments.iter().map(Comment::to_pb).collect(),
+        total,
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_comments(&self) -> impl Query<pb::ListCommentsReq> + '_ {
+        move |req: pb::ListCommentsReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::movie::domain::review::model::report::Report;
+use crate::movie::domain::utils::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::review::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListReportsReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListReportsRes> {
+    let (limit, offset) = req
+        .slice
+        .unwrap_or_default()
+        .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+    let (reports, total) = Report::list_pending(req.admin_id, limit, offset, conn)?;
+    Ok(pb::ListReportsRes {
+        reports: reports.iter().map(Report::to_pb).collect(),
+        total,
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_reports(&self) -> impl Query<pb::ListReportsReq> + '_ {
+        move |req: pb::ListReportsReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::movie::domain::review::model::filter::ReviewFilter;
+use crate::movie::domain::review::model::review::Review;
+use crate::movie::rpc::MovieResolver;
+use crate::user::domain::follow::model::block::Block;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::review::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListReviewsReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListReviewsRes> {
+    let hidden = match req.viewer {
+        Some(viewer) => Block::hidden_from(viewer, conn)?,
+        None => vec![],
+    };
+    let filter = ReviewFilter::parse(req.movie_id, req.uid, hidden, req.order, req.slice)?;
+    let (reviews, total) = filter.list_reviews(conn)?;
+    let stars = Review::stars(&reviews, conn)?;
+    Ok(pb::ListReviewsRes {
+        reviews: reviews.iter().map(|review| review.to_pb(&stars)).collect(),
+        total,
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_reviews(&self) -> impl Query<pb::ListReviewsReq> + '_ {
+        move |req: pb::ListReviewsReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
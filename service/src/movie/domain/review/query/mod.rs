@@ -0,0 +1,4 @@
+pub mod get_review;
+pub mod list_comments;
+pub mod list_reports;
+pub mod list_reviews;
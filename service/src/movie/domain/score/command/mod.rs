@@ -0,0 +1,2 @@
+pub mod score;
+pub mod unscore;
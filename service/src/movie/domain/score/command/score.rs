@@ -0,0 +1,32 @@
+use crate::movie::domain::mark::model::mark::Mark;
+use crate::movie::domain::score::model::rating::Rating;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::{Connection, PgConnection};
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::score::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::ScoreReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    conn.transaction::<(), GrpcStatus, _>(|conn| {
+        Rating::rate(
+            req.uid,
+            req.movie_id,
+            req.star,
+            req.comment.as_deref(),
+            conn,
+        )?;
+        if !req.tags.is_empty() {
+            Mark::retag(req.uid, req.movie_id, &req.tags, conn)?;
+        }
+        Ok(())
+    })?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_score(&self) -> impl Command<pb::ScoreReq> + '_ {
+        move |req: pb::ScoreReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
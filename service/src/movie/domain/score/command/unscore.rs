@@ -0,0 +1,19 @@
+use crate::movie::domain::score::model::rating::Rating;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::score::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::UnscoreReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    Rating::unrate(req.uid, req.movie_id, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_unscore(&self) -> impl Command<pb::UnscoreReq> + '_ {
+        move |req: pb::UnscoreReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
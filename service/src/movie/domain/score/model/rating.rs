@@ -0,0 +1,180 @@
+use crate::movie::domain::movie::model::movie::{Movie, MovieId};
+use crate::movie::domain::score::model::score::MovieScore;
+use crate::movie::domain::utils::map_internal;
+use crate::user::domain::user::model::ban::Ban;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{internal, invalid_argument, not_found};
+use diesel::prelude::*;
+use migration::{t_user_ratings, t_users};
+use proto::pb::movie::score::v1 as pb;
+use tonic::Status;
+
+pub(in crate::movie::domain) const MAX_COMMENT_LEN: usize = 350;
+
+#[derive(Queryable)]
+pub struct Rating {
+    id: i64,
+    uid: i64,
+    mid: i64,
+    star: i16,
+    comment: Option<String>,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+    helpful_cnt: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = t_user_ratings)]
+struct NewRating<'a> {
+    uid: i64,
+    mid: i64,
+    star: i16,
+    comment: Option<&'a str>,
+}
+
+/// Ratings are attributed to the authenticated user, who must be an alive account
+/// and not banned.
+pub(in crate::movie::domain) fn check_user(
+    user_id: i64,
+    conn: &mut PgConnection,
+) -> GrpcResult<()> {
+    let found: Option<i64> = t_users::table
+        .select(t_users::id)
+        .filter(t_users::id.eq(user_id))
+        .filter(t_users::deleted_at.is_null())
+        .first(conn)
+        .optional()
+        .map_err(map_internal)?;
+    if found.is_none() {
+        return Err(
+            Status::unauthenticated(format!("User({}) is not authenticated", user_id)).into(),
+        );
+    }
+    match Ban::active(user_id, conn)? {
+        Some(ban) => Err(ban.status().into()),
+        None => Ok(()),
+    }
+}
+
+impl Rating {
+    pub(in crate::movie::domain) fn query(
+        user_id: i64,
+        movie_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Option<Rating>> {
+        use migration::t_user_ratings::dsl::*;
+
+        let rating: Option<Rating> = t_user_ratings
+            .filter(uid.eq(user_id))
+            .filter(mid.eq(movie_id))
+            .first(conn)
+            .optional()
+            .map_err(map_internal)?;
+        Ok(rating)
+    }
+
+    pub(in crate::movie::domain) fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::MyScore {
+        pb::MyScore {
+            star: self.star as i32,
+            comment: self.comment.clone(),
+            created_at: self.created_at.timestamp(),
+            updated_at: self.updated_at.timestamp(),
+        }
+    }
+
+    /// Rate the movie, the aggregates of the movie are updated in the same transaction,
+    /// the bucket of the previous star is decremented when a rating is changed.
+    pub(in crate::movie::domain) fn rate(
+        user_id: i64,
+        movie_id: i64,
+        new_star: i32,
+        new_comment: Option<&str>,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_ratings::dsl::*;
+
+        if !(1..=5).contains(&new_star) {
+            return Err(invalid_argument!("star", "1 to 5").into());
+        }
+        let new_star = new_star as i16;
+        let new_comment = new_comment
+            .map(str::trim)
+            .filter(|content| !content.is_empty());
+        if new_comment.map_or(false, |content| content.chars().count() > MAX_COMMENT_LEN) {
+            return Err(invalid_argument!("comment", "no more than 350 characters").into());
+        }
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            Movie::query_id(MovieId::from(movie_id as u64), conn)?;
+            // a concurrent first rating of the same user conflicts on the unique index,
+            // then the rating is updated as an existing one
+            let inserted = diesel::insert_into(t_user_ratings)
+                .values(NewRating {
+                    uid: user_id,
+                    mid: movie_id,
+                    star: new_star,
+                    comment: new_comment,
+                })
+                .on_conflict((uid, mid))
+                .do_nothing()
+                .execute(conn)
+                .map_err(|e| internal!(format!("Cannot rate movie, err: {}", e)))?;
+            if inserted > 0 {
+                return MovieScore::bump(movie_id, new_star, 1, conn);
+            }
+            let old_star: i16 = t_user_ratings
+                .select(star)
+                .filter(uid.eq(user_id))
+                .filter(mid.eq(movie_id))
+                .for_update()
+                .first(conn)
+                .map_err(map_internal)?;
+            diesel::update(
+                t_user_ratings
+                    .filter(uid.eq(user_id))
+                    .filter(mid.eq(movie_id)),
+            )
+            .set((star.eq(new_star), comment.eq(new_comment)))
+            .execute(conn)
+            .map_err(map_internal)?;
+            if old_star != new_star {
+                MovieScore::bump(movie_id, old_star, -1, conn)?;
+                MovieScore::bump(movie_id, new_star, 1, conn)?;
+            }
+            Ok(())
+        })
+    }
+
+    pub(in crate::movie::domain) fn unrate(
+        user_id: i64,
+        movie_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_ratings::dsl::*;
+
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            check_user(user_id, conn)?;
+            let old_star: Option<i16> = diesel::delete(
+                t_user_ratings
+                    .filter(uid.eq(user_id))
+                    .filter(mid.eq(movie_id)),
+            )
+            .returning(star)
+            .get_result(conn)
+            .optional()
+            .map_err(map_internal)?;
+            let old_star = old_star.ok_or_else(|| {
+                not_found!(format!(
+                    "rating of movie({}) by user({})",
+                    movie_id, user_id
+                ))
+            })?;
+            MovieScore::bump(movie_id, old_star, -1, conn)
+        })
+    }
+}
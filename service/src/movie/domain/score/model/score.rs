@@ -0,0 +1,76 @@
+use chrono::NaiveDateTime;
+use common::internal;
+use common::status::prelude::*;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer};
+use proto::pb::movie::score::v1 as pb;
+
+/// Aggregated ratings of a movie, `score_avg` is computed by the trigger
+/// `diesel_set_score_avg` in 0-10 whenever the counts change.
+#[derive(Queryable)]
+pub struct MovieScore {
+    id: i64,
+    mid: i64,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+    score_avg: f64,
+    cnt_1: i64,
+    cnt_2: i64,
+    cnt_3: i64,
+    cnt_4: i64,
+    cnt_5: i64,
+}
+
+impl MovieScore {
+    pub(in crate::movie::domain) fn query_mid(
+        movie_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Option<MovieScore>> {
+        use migration::t_movies_scores::dsl::*;
+
+        let score: Option<MovieScore> = t_movies_scores
+            .filter(mid.eq(movie_id))
+            .first(conn)
+            .optional()
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        Ok(score)
+    }
+
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::ScorePayload {
+        pb::ScorePayload {
+            score_avg: self.score_avg as f32,
+            cnt_1: self.cnt_1 as i32,
+            cnt_2: self.cnt_2 as i32,
+            cnt_3: self.cnt_3 as i32,
+            cnt_4: self.cnt_4 as i32,
+            cnt_5: self.cnt_5 as i32,
+        }
+    }
+
+    /// Add `delta` to the count of the star, the aggregate row is created on the first rating.
+    pub(in crate::movie::domain) fn bump(
+        movie_id: i64,
+        star: i16,
+        delta: i32,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        debug_assert!((1..=5).contains(&star), "star out of range");
+        // the column is chosen from a checked star, never from the input directly
+        let column = format!("cnt_{}", star);
+        diesel::sql_query(format!(
+            "INSERT INTO t_movies_scores (mid, {column}) VALUES ($1, greatest($2, 0)) \
+             ON CONFLICT (mid) DO UPDATE SET {column} = greatest(t_movies_scores.{column} + $2, 0)",
+            column = column
+        ))
+        .bind::<BigInt, _>(movie_id)
+        .bind::<Integer, _>(delta)
+        .execute(conn)
+        .map_err(|e| {
+            internal!(format!(
+                "Cannot update scores of movie({}), err: {}",
+                movie_id, e
+            ))
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use crate::movie::domain::score::model::rating::{check_user, Rating};
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::score::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::GetMyScoreReq, conn: &mut PgConnection) -> GrpcResult<pb::GetMyScoreRes> {
+    check_user(req.uid, conn)?;
+    let rating = Rating::query(req.uid, req.movie_id, conn)?;
+    Ok(pb::GetMyScoreRes {
+        score: rating.as_ref().map(Rating::to_pb),
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_get_my_score(&self) -> impl Query<pb::GetMyScoreReq> + '_ {
+        move |req: pb::GetMyScoreReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::movie::domain::movie::model::movie::{Movie, MovieId};
+use crate::movie::domain::score::model::score::MovieScore;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::score::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::GetReq, conn: &mut PgConnection) -> GrpcResult<pb::GetRes> {
+    let score = match MovieScore::query_mid(req.movie_id, conn)? {
+        Some(score) => score.to_pb(),
+        None => {
+            // a movie nobody has rated yet
+            Movie::query_id(MovieId::from(req.movie_id as u64), conn)?;
+            pb::ScorePayload::default()
+        }
+    };
+    Ok(pb::GetRes {
+        payload: Some(score),
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_get_score(&self) -> impl Query<pb::GetReq> + '_ {
+        move |req: pb::GetReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod get_my_score;
+pub mod get_score;
@@ -0,0 +1,300 @@
+//! Full text search of movies and celebrities.
+//!
+//! Documents are segmented by the SQL function `douban_segment` into `search_vector`
+//! columns maintained by triggers, and keywords are turned into queries by
+//! `douban_search_query`, so that a segmenter is plugged in by replacing the former.
+//! The vectors are left out of the schema and only used through SQL fragments.
+//! Keywords in latin letters are matched against the pinyin of Chinese names as well,
+//! the pinyin of rows put without it is filled by [`backfill_pinyin`].
+
+use crate::movie::domain::utils::map_internal;
+use common::invalid_argument;
+use common::status::prelude::*;
+use diesel::dsl::sql;
+use diesel::expression::BoxableExpression;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Float, Text};
+use migration::{t_celebrities, t_movies};
+use pinyin::ToPinyin;
+
+const MAX_KEYWORD_LEN: usize = 128;
+// unit (character)
+const SNIPPET_LEN: usize = 64;
+const MAX_PINYIN_LEN: usize = 1024;
+const MAX_INITIALS_LEN: usize = 256;
+const BACKFILL_BATCH: i64 = 500;
+
+// the same ranges as douban_segment: kana, CJK extension A, unified and compatibility ideographs
+#[inline]
+fn is_cjk(c: char) -> bool {
+    matches!(c, '\u{3040}'..='\u{30ff}' | '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' | '\u{f900}'..='\u{faff}')
+}
+
+#[inline]
+fn escape_html(c: char, buf: &mut String) {
+    match c {
+        '<' => buf.push_str("&lt;"),
+        '>' => buf.push_str("&gt;"),
+        '&' => buf.push_str("&amp;"),
+        '"' => buf.push_str("&quot;"),
+        c => buf.push(c),
+    }
+}
+
+/// Lowercase pinyin without spaces and pinyin initials of the text, latin letters and
+/// digits are kept as they are, i.e. `花样年华2` is `huayangnianhua2` and `hynh2`.
+pub(in crate::movie::domain) fn romanize(text: &str) -> (String, String) {
+    let mut pinyin = String::new();
+    let mut initials = String::new();
+    for (c, romanized) in text.chars().zip(text.to_pinyin()) {
+        match romanized {
+            Some(romanized) => {
+                pinyin.push_str(romanized.plain());
+                initials.push_str(romanized.first_letter());
+            }
+            None if c.is_ascii_alphanumeric() => {
+                pinyin.push(c.to_ascii_lowercase());
+                initials.push(c.to_ascii_lowercase());
+            }
+            None => {}
+        }
+    }
+    // only ascii is pushed, so that truncating never splits a character
+    pinyin.truncate(MAX_PINYIN_LEN);
+    initials.truncate(MAX_INITIALS_LEN);
+    (pinyin, initials)
+}
+
+/// Fill the pinyin of movies and celebrities without it, i.e. the rows put before the
+/// pinyin columns were added or imported by the cli, returns the number of filled rows.
+/// Rows are walked by id, so that a name without any pinyin is visited once.
+pub(in crate::movie) fn backfill_pinyin(conn: &mut PgConnection) -> GrpcResult<usize> {
+    let mut filled = 0;
+    let mut last = 0;
+    loop {
+        let movies: Vec<(i64, String)> = t_movies::table
+            .select((t_movies::id, t_movies::title))
+            .filter(t_movies::pinyin.eq(""))
+            .filter(t_movies::id.gt(last))
+            .order(t_movies::id)
+            .limit(BACKFILL_BATCH)
+            .load(conn)
+            .map_err(map_internal)?;
+        last = match movies.last() {
+            Some((id, _)) => *id,
+            None => break,
+        };
+        for (id, title) in movies {
+            let (pinyin, initials) = romanize(&title);
+            if pinyin.is_empty() {
+                continue;
+            }
+            filled += diesel::update(t_movies::table.find(id))
+                .set((
+                    t_movies::pinyin.eq(pinyin),
+                    t_movies::pinyin_initials.eq(initials),
+                ))
+                .execute(conn)
+                .map_err(map_internal)?;
+        }
+    }
+    let mut last = 0;
+    loop {
+        let celebrities: Vec<(i64, String)> = t_celebrities::table
+            .select((t_celebrities::id, t_celebrities::name))
+            .filter(t_celebrities::pinyin.eq(""))
+            .filter(t_celebrities::id.gt(last))
+            .order(t_celebrities::id)
+            .limit(BACKFILL_BATCH)
+            .load(conn)
+            .map_err(map_internal)?;
+        last = match celebrities.last() {
+            Some((id, _)) => *id,
+            None => break,
+        };
+        for (id, name) in celebrities {
+            let (pinyin, initials) = romanize(name.trim());
+            if pinyin.is_empty() {
+                continue;
+            }
+            filled += diesel::update(t_celebrities::table.find(id))
+                .set((
+                    t_celebrities::pinyin.eq(pinyin),
+                    t_celebrities::pinyin_initials.eq(initials),
+                ))
+                .execute(conn)
+                .map_err(map_internal)?;
+        }
+    }
+    Ok(filled)
+}
+
+/// A keyword of searching movies or celebrities.
+pub struct Keyword {
+    text: String,
+    // prefix pattern of pinyin, only if the keyword is in latin letters
+    pinyin: Option<String>,
+    // lowercase, highlighted in snippets
+    terms: Vec<String>,
+}
+
+impl Keyword {
+    /// Parse a keyword, a blank keyword is no keyword.
+    pub(in crate::movie::domain) fn parse(keyword: Option<String>) -> GrpcResult<Option<Keyword>> {
+        let text = match keyword.map(|keyword| keyword.trim().to_string()) {
+            Some(text) if !text.is_empty() => text,
+            _ => return Ok(None),
+        };
+        if text.chars().count() > MAX_KEYWORD_LEN {
+            return Err(invalid_argument!("keyword", "no more than 128 characters").into());
+        }
+        // a single letter matches too many initials
+        let pinyin = Some(
+            text.chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>()
+                .to_ascii_lowercase(),
+        )
+        .filter(|pinyin| pinyin.len() >= 2 && pinyin.chars().all(|c| c.is_ascii_alphabetic()))
+        .map(|pinyin| format!("{}%", pinyin));
+
+        let mut terms = Vec::new();
+        for word in text.split_whitespace() {
+            let word = word.to_lowercase();
+            let chars: Vec<char> = word.chars().collect();
+            // a CJK word may match partially, i.e. by its bigrams
+            if chars.len() > 2 && chars.iter().all(|c| is_cjk(*c)) {
+                terms.extend(chars.windows(2).map(|bigram| bigram.iter().collect()));
+            }
+            terms.push(word);
+        }
+        Ok(Some(Keyword {
+            text,
+            pinyin,
+            terms,
+        }))
+    }
+
+    pub(in crate::movie::domain) fn pinyin(&self) -> Option<&str> {
+        self.pinyin.as_deref()
+    }
+
+    /// Whether `search_vector` of the table matches the keyword.
+    pub(in crate::movie::domain) fn matches<'a, QS: 'a>(
+        &self,
+        table: &'static str,
+    ) -> Box<dyn BoxableExpression<QS, Pg, SqlType = Bool> + Send + 'a> {
+        Box::new(
+            sql::<Bool>(&format!("{}.search_vector @@ douban_search_query(", table))
+                .bind::<Text, _>(self.text.clone())
+                .sql(")"),
+        )
+    }
+
+    /// Relevance of rows of the table to the keyword, higher is more relevant.
+    pub(in crate::movie::domain) fn rank<'a, QS: 'a>(
+        &self,
+        table: &'static str,
+    ) -> Box<dyn BoxableExpression<QS, Pg, SqlType = Float> + Send + 'a> {
+        Box::new(
+            sql::<Float>(&format!(
+                "ts_rank({}.search_vector, douban_search_query(",
+                table
+            ))
+            .bind::<Text, _>(self.text.clone())
+            .sql("))"),
+        )
+    }
+
+    /// A snippet of the text around the first match, matches are wrapped in `<em></em>`
+    /// and the rest is escaped. Returns none if nothing matches.
+    pub(in crate::movie::domain) fn highlight(&self, text: &str) -> Option<String> {
+        let chars: Vec<char> = text.chars().collect();
+        let lower: Vec<char> = chars
+            .iter()
+            .map(|c| c.to_lowercase().next().unwrap_or(*c))
+            .collect();
+        let mut ranges = Vec::new();
+        for term in &self.terms {
+            let term: Vec<char> = term.chars().collect();
+            if term.is_empty() || term.len() > lower.len() {
+                continue;
+            }
+            ranges.extend(
+                lower
+                    .windows(term.len())
+                    .enumerate()
+                    .filter(|(_, window)| *window == term.as_slice())
+                    .map(|(start, _)| (start, start + term.len())),
+            );
+        }
+        if ranges.is_empty() {
+            return None;
+        }
+        ranges.sort_unstable();
+        let mut merged: Vec<(usize, usize)> = Vec::new();
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        // leave some context before the first match
+        let begin = merged[0].0.saturating_sub(SNIPPET_LEN / 4);
+        let end = (begin + SNIPPET_LEN).min(chars.len());
+        let mut snippet = String::new();
+        if begin > 0 {
+            snippet.push('…');
+        }
+        let mut pos = begin;
+        for (start, stop) in merged {
+            if start >= end {
+                break;
+            }
+            let stop = stop.min(end);
+            chars[pos..start]
+                .iter()
+                .for_each(|c| escape_html(*c, &mut snippet));
+            snippet.push_str("<em>");
+            chars[start..stop]
+                .iter()
+                .for_each(|c| escape_html(*c, &mut snippet));
+            snippet.push_str("</em>");
+            pos = stop;
+        }
+        chars[pos..end]
+            .iter()
+            .for_each(|c| escape_html(*c, &mut snippet));
+        if end < chars.len() {
+            snippet.push('…');
+        }
+        Some(snippet)
+    }
+
+    /// The text wholly wrapped in `<em></em>` if the keyword matches the pinyin of it
+    /// rather than the text itself, a snippet of the beginning if the text is long.
+    pub(in crate::movie::domain) fn highlight_pinyin(
+        &self,
+        text: &str,
+        pinyin: &str,
+        initials: &str,
+    ) -> Option<String> {
+        let prefix = self.pinyin.as_deref()?.trim_end_matches('%');
+        if !pinyin.starts_with(prefix) && !initials.starts_with(prefix) {
+            return None;
+        }
+        let chars: Vec<char> = text.chars().collect();
+        let end = SNIPPET_LEN.min(chars.len());
+        let mut snippet = String::from("<em>");
+        chars[..end]
+            .iter()
+            .for_each(|c| escape_html(*c, &mut snippet));
+        snippet.push_str("</em>");
+        if end < chars.len() {
+            snippet.push('…');
+        }
+        Some(snippet)
+    }
+}
@@ -0,0 +1,203 @@
+use crate::movie::domain::utils::{dedup, map_internal};
+use common::status::prelude::*;
+use common::{internal, invalid_argument};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer, Text};
+use migration::t_user_marks;
+use proto::pb::movie::tag::v1 as pb;
+use zhconv::{zhconv, Variant};
+
+const MAX_TAGS: usize = 10;
+const MAX_TAG_LEN: usize = 32;
+const BACKFILL_BATCH: i64 = 500;
+
+/// Normalize a tag, so that tags differing in case, width or traditional and simplified
+/// Chinese are the same tag, i.e. `Ｓｃｉ-Ｆｉ 電影` is `sci-fi 电影`.
+pub(in crate::movie::domain) fn normalize(tag: &str) -> String {
+    let folded: String = tag
+        .chars()
+        .map(|c| match c {
+            // ideographic space
+            '\u{3000}' => ' ',
+            // fullwidth forms of ascii
+            '\u{ff01}'..='\u{ff5e}' => char::from_u32(c as u32 - 0xfee0).unwrap_or(c),
+            c => c,
+        })
+        .flat_map(char::to_lowercase)
+        .collect();
+    zhconv(&folded, Variant::ZhHans)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Normalized tags without duplicates, in the order given.
+pub(in crate::movie::domain) fn check_tags(tags: &[String]) -> GrpcResult<Vec<String>> {
+    let mut checked: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags.iter().map(|tag| normalize(tag)) {
+        if tag.is_empty() || tag.chars().count() > MAX_TAG_LEN {
+            return Err(invalid_argument!("tags", "1 to 32 characters each").into());
+        }
+        if !checked.contains(&tag) {
+            checked.push(tag);
+        }
+    }
+    if checked.len() > MAX_TAGS {
+        return Err(invalid_argument!("tags", "no more than 10 tags").into());
+    }
+    Ok(checked)
+}
+
+/// Normalize the tags of marks tagged before [`normalize`], whose tags were only
+/// lowercased and trimmed by the migration, the counts follow the change. Returns the
+/// number of marks retagged.
+pub(in crate::movie) fn backfill_tags(conn: &mut PgConnection) -> GrpcResult<usize> {
+    let mut retagged = 0;
+    let mut last = 0;
+    loop {
+        let ids: Vec<i64> = t_user_marks::table
+            .select(t_user_marks::id)
+            .filter(t_user_marks::tags.ne(Vec::<String>::new()))
+            .filter(t_user_marks::id.gt(last))
+            .order(t_user_marks::id)
+            .limit(BACKFILL_BATCH)
+            .load(conn)
+            .map_err(map_internal)?;
+        last = match ids.last() {
+            Some(id) => *id,
+            None => break,
+        };
+        for mark_id in ids {
+            // locked as a mark is, so that a concurrent mark sees the normalized tags
+            let changed = conn.transaction::<bool, GrpcStatus, _>(|conn| {
+                let mark: Option<(i64, i64, Vec<String>)> = t_user_marks::table
+                    .select((t_user_marks::uid, t_user_marks::mid, t_user_marks::tags))
+                    .find(mark_id)
+                    .for_update()
+                    .first(conn)
+                    .optional()
+                    .map_err(map_internal)?;
+                let (user_id, movie_id, tags) = match mark {
+                    Some(mark) => mark,
+                    None => return Ok(false),
+                };
+                let normalized = dedup(
+                    &tags
+                        .iter()
+                        .map(|tag| normalize(tag))
+                        .filter(|tag| !tag.is_empty())
+                        .collect::<Vec<_>>(),
+                );
+                if normalized == tags {
+                    return Ok(false);
+                }
+                diesel::update(t_user_marks::table.find(mark_id))
+                    .set(t_user_marks::tags.eq(&normalized))
+                    .execute(conn)
+                    .map_err(map_internal)?;
+                TagCount::retag(user_id, movie_id, &tags, &normalized, conn)?;
+                Ok(true)
+            })?;
+            if changed {
+                retagged += 1;
+            }
+        }
+    }
+    Ok(retagged)
+}
+
+/// Counts of a tag, of a movie or of a user.
+#[derive(Queryable)]
+pub struct TagCount {
+    tag: String,
+    cnt: i64,
+}
+
+impl TagCount {
+    pub(in crate::movie::domain) fn to_pb(&self) -> pb::TagCount {
+        pb::TagCount {
+            tag: self.tag.clone(),
+            cnt: self.cnt,
+        }
+    }
+
+    /// Top tags of the movie.
+    pub(in crate::movie::domain) fn list_movie(
+        movie_id: i64,
+        limit: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Vec<TagCount>> {
+        use migration::t_movies_tags::dsl::*;
+
+        t_movies_tags
+            .select((tag, cnt))
+            .filter(mid.eq(movie_id))
+            .filter(cnt.gt(0))
+            .order((cnt.desc(), tag.asc()))
+            .limit(limit)
+            .load(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)).into())
+    }
+
+    /// Tag cloud of the user.
+    pub(in crate::movie::domain) fn list_user(
+        user_id: i64,
+        limit: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Vec<TagCount>> {
+        use migration::t_user_tags::dsl::*;
+
+        t_user_tags
+            .select((tag, cnt))
+            .filter(uid.eq(user_id))
+            .filter(cnt.gt(0))
+            .order((cnt.desc(), tag.asc()))
+            .limit(limit)
+            .load(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)).into())
+    }
+
+    /// Apply the change of tags of a mark to the counts of the movie and the user,
+    /// only tags added or removed are counted.
+    pub(in crate::movie::domain) fn retag(
+        user_id: i64,
+        movie_id: i64,
+        old_tags: &[String],
+        new_tags: &[String],
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        let removed = old_tags.iter().filter(|tag| !new_tags.contains(tag));
+        let added = new_tags.iter().filter(|tag| !old_tags.contains(tag));
+        for (tag, delta) in removed
+            .map(|tag| (tag, -1))
+            .chain(added.map(|tag| (tag, 1)))
+        {
+            Self::bump("t_movies_tags", "mid", movie_id, tag, delta, conn)?;
+            Self::bump("t_user_tags", "uid", user_id, tag, delta, conn)?;
+        }
+        Ok(())
+    }
+
+    // the table and the column are constants of callers, never from the input
+    fn bump(
+        table: &'static str,
+        column: &'static str,
+        owner: i64,
+        tag: &str,
+        delta: i32,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        diesel::sql_query(format!(
+            "INSERT INTO {table} ({column}, tag, cnt) VALUES ($1, $2, greatest($3, 0)) \
+             ON CONFLICT ({column}, tag) DO UPDATE SET cnt = greatest({table}.cnt + $3, 0)",
+            table = table,
+            column = column
+        ))
+        .bind::<BigInt, _>(owner)
+        .bind::<Text, _>(tag)
+        .bind::<Integer, _>(delta)
+        .execute(conn)
+        .map_err(|e| internal!(format!("Cannot update tags of {}, err: {}", table, e)))?;
+        Ok(())
+    }
+}
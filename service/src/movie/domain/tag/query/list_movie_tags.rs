@@ -0,0 +1,29 @@
+use crate::movie::domain::tag::model::tag::TagCount;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::tag::v1 as pb;
+use std::ops::DerefMut;
+
+const DEFAULT_LIMIT: i64 = 10;
+const MAX_LIMIT: i64 = 50;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListMovieTagsReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListMovieTagsRes> {
+    let limit = req
+        .limit
+        .map_or(DEFAULT_LIMIT, |limit| (limit as i64).clamp(1, MAX_LIMIT));
+    let tags = TagCount::list_movie(req.movie_id, limit, conn)?;
+    Ok(pb::ListMovieTagsRes {
+        tags: tags.iter().map(TagCount::to_pb).collect(),
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_movie_tags(&self) -> impl Query<pb::ListMovieTagsReq> + '_ {
+        move |req: pb::ListMovieTagsReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::movie::domain::score::model::rating::check_user;
+use crate::movie::domain::tag::model::tag::TagCount;
+use crate::movie::rpc::MovieResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::movie::tag::v1 as pb;
+use std::ops::DerefMut;
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 500;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::ListMyTagsReq, conn: &mut PgConnection) -> GrpcResult<pb::ListMyTagsRes> {
+    check_user(req.uid, conn)?;
+    let limit = req
+        .limit
+        .map_or(DEFAULT_LIMIT, |limit| (limit as i64).clamp(1, MAX_LIMIT));
+    let tags = TagCount::list_user(req.uid, limit, conn)?;
+    Ok(pb::ListMyTagsRes {
+        tags: tags.iter().map(TagCount::to_pb).collect(),
+    })
+}
+
+impl MovieResolver {
+    pub(in crate::movie) fn create_list_my_tags(&self) -> impl Query<pb::ListMyTagsReq> + '_ {
+        move |req: pb::ListMyTagsReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod list_movie_tags;
+pub mod list_my_tags;
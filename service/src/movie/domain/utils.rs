@@ -0,0 +1,37 @@
+use common::status::prelude::*;
+use common::{internal, not_found};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::hash::Hash;
+
+// page size of a listing if the slice gives no limit
+pub(in crate::movie::domain) const DEFAULT_LIMIT: i64 = 20;
+pub(in crate::movie::domain) const MAX_LIMIT: i64 = 100;
+
+pub(in crate::movie::domain) fn map_not_found<'a>(
+    scope: &'a str,
+    identifier: impl Display + 'a,
+) -> impl Fn(diesel::result::Error) -> tonic::Status + 'a {
+    move |e| {
+        if e == diesel::NotFound {
+            return not_found!(format!("{}({})", scope, identifier));
+        }
+        internal!(format!("Database connection error: {}", e))
+    }
+}
+
+#[inline]
+pub(in crate::movie::domain) fn map_internal(e: diesel::result::Error) -> tonic::Status {
+    internal!(format!("Database connection error: {}", e))
+}
+
+// remove duplicates but keep the order
+#[inline]
+pub(in crate::movie::domain) fn dedup<T: Clone + Eq + Hash>(values: &[T]) -> Vec<T> {
+    let mut seen = HashSet::new();
+    values
+        .iter()
+        .filter(|value| seen.insert(*value))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,20 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        resolver
+            .review_client()
+            .del_comment(pb::DelCommentReq { movie_id: id, uid })
+            .await
+            .map(|_| ())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
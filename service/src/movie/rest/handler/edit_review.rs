@@ -0,0 +1,31 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Path(id): Path<i64>,
+    Form(ReviewReq {
+        title,
+        content,
+        spoiler,
+    }): Form<ReviewReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        resolver
+            .review_client()
+            .edit_review(pb::EditReviewReq {
+                id,
+                uid,
+                title,
+                content,
+                spoiler: spoiler.unwrap_or_default(),
+            })
+            .await
+            .map(|_| ())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
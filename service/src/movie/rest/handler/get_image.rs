@@ -0,0 +1,35 @@
+use super::*;
+use axum::extract::Path;
+use axum::response::{IntoResponse, Response};
+use http::header::{CACHE_CONTROL, CONTENT_TYPE};
+
+// the content of a key never changes, so that it is cached as long as possible
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    Path(key): Path<String>,
+) -> Response {
+    let resp = resolver
+        .media_client()
+        .get_image(media_pb::GetImageReq { key })
+        .await
+        .map(|res| res.into_inner())
+        .map_err(HttpStatus::from);
+    match resp {
+        Ok(res) => (
+            StatusCode::OK,
+            [
+                (CONTENT_TYPE, res.content_type),
+                (
+                    CACHE_CONTROL,
+                    "public, max-age=31536000, immutable".to_string(),
+                ),
+            ],
+            res.content,
+        )
+            .into_response(),
+        Err(e) => {
+            let resp: Result<(), HttpStatus> = Err(e);
+            (resp.http_code(), Json(resp.into())).into_response()
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<Resp<mark_pb::GetMarkCountRes>>) {
+    let resp = resolver
+        .mark_client()
+        .get_mark_count(mark_pb::GetMarkCountReq { movie_id: id })
+        .await
+        .map(|res| res.into_inner())
+        .map_err(HttpStatus::from);
+    (resp.http_code(), Json(resp.into()))
+}
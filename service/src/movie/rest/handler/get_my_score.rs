@@ -0,0 +1,20 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<Resp<score_pb::GetMyScoreRes>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        resolver
+            .score_client()
+            .get_my_score(score_pb::GetMyScoreReq { movie_id: id, uid })
+            .await
+            .map(|res| res.into_inner())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,19 @@
+use super::*;
+use axum::extract::{Path, Query};
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    Path(id): Path<i64>,
+    Query(ListTagsReq { limit }): Query<ListTagsReq>,
+) -> (StatusCode, Json<Resp<tag_pb::ListMovieTagsRes>>) {
+    let resp = resolver
+        .tag_client()
+        .list_movie_tags(tag_pb::ListMovieTagsReq {
+            movie_id: id,
+            limit,
+        })
+        .await
+        .map(|res| res.into_inner())
+        .map_err(HttpStatus::from);
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,38 @@
+use super::*;
+use axum::extract::Query;
+use proto::pb::common::v1::{ByPage, Slice};
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Query(ListMyMarksReq {
+        status,
+        tag,
+        page,
+        per_page,
+    }): Query<ListMyMarksReq>,
+) -> (StatusCode, Json<Resp<mark_pb::ListMyMarksRes>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        let status = status.as_deref().map(mark_status).transpose()?;
+        resolver
+            .mark_client()
+            .list_my_marks(mark_pb::ListMyMarksReq {
+                uid,
+                status: status.map(|status| status as i32),
+                tag,
+                slice: Some(Slice {
+                    limit: None,
+                    page: Some(ByPage {
+                        page: page.unwrap_or(1),
+                        per_page: per_page.unwrap_or_default(),
+                    }),
+                }),
+            })
+            .await
+            .map(|res| res.into_inner())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,20 @@
+use super::*;
+use axum::extract::Query;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Query(ListTagsReq { limit }): Query<ListTagsReq>,
+) -> (StatusCode, Json<Resp<tag_pb::ListMyTagsRes>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        resolver
+            .tag_client()
+            .list_my_tags(tag_pb::ListMyTagsReq { uid, limit })
+            .await
+            .map(|res| res.into_inner())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,34 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Path(id): Path<i64>,
+    Form(MarkReq {
+        status,
+        tags,
+        star,
+        comment,
+    }): Form<MarkReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        let status = mark_status(&status)?;
+        resolver
+            .mark_client()
+            .mark(mark_pb::MarkReq {
+                movie_id: id,
+                uid,
+                status: status as i32,
+                tags: split_tags(tags.as_deref()),
+                star,
+                comment,
+            })
+            .await
+            .map(|_| ())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,60 @@
+pub(crate) mod del_comment;
+pub(crate) mod del_review;
+pub(crate) mod edit_review;
+pub(crate) mod get_image;
+pub(crate) mod get_mark_count;
+pub(crate) mod get_my_score;
+pub(crate) mod list_movie_tags;
+pub(crate) mod list_my_marks;
+pub(crate) mod list_my_tags;
+pub(crate) mod mark;
+pub(crate) mod post_review;
+pub(crate) mod put_comment;
+pub(crate) mod report_review;
+pub(crate) mod score;
+pub(crate) mod unmark;
+pub(crate) mod unscore;
+pub(crate) mod unvote_comment;
+pub(crate) mod unvote_review;
+pub(crate) mod upload_image;
+pub(crate) mod vote_comment;
+pub(crate) mod vote_review;
+
+use crate::movie::rest::types::*;
+use crate::movie::rest::RestResolver;
+use axum::extract::State;
+use axum::*;
+use common::status::prelude::*;
+use http::StatusCode;
+use proto::pb::movie::mark::v1 as mark_pb;
+use proto::pb::movie::media::v1 as media_pb;
+use proto::pb::movie::review::v1 as pb;
+use proto::pb::movie::score::v1 as score_pb;
+use proto::pb::movie::tag::v1 as tag_pb;
+use std::sync::Arc;
+use tonic::Status;
+
+// the id of the authenticated user, set by the auth layer
+pub(crate) fn user_id(uid: &UserId) -> Result<i64, HttpStatus> {
+    uid.as_string()
+        .parse()
+        .map_err(|_| HttpStatus::from(Status::unauthenticated("Invalid user id")))
+}
+
+// tags separated by comma
+pub(crate) fn split_tags(tags: Option<&str>) -> Vec<String> {
+    tags.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+pub(crate) fn mark_status(status: &str) -> Result<mark_pb::MarkStatus, HttpStatus> {
+    mark_pb::MarkStatus::from_str_name(&status.to_uppercase()).ok_or_else(|| {
+        HttpStatus::from(Status::invalid_argument(
+            "Request field status is invalid, expect wish, watching or watched",
+        ))
+    })
+}
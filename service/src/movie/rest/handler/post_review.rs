@@ -0,0 +1,31 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Path(id): Path<i64>,
+    Form(ReviewReq {
+        title,
+        content,
+        spoiler,
+    }): Form<ReviewReq>,
+) -> (StatusCode, Json<Resp<pb::PostReviewRes>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        resolver
+            .review_client()
+            .post_review(pb::PostReviewReq {
+                movie_id: id,
+                uid,
+                title,
+                content,
+                spoiler: spoiler.unwrap_or_default(),
+            })
+            .await
+            .map(|res| res.into_inner())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
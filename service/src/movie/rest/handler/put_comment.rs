@@ -0,0 +1,25 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Path(id): Path<i64>,
+    Form(CommentReq { content }): Form<CommentReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        resolver
+            .review_client()
+            .put_comment(pb::PutCommentReq {
+                movie_id: id,
+                uid,
+                content,
+            })
+            .await
+            .map(|_| ())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,31 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Path(id): Path<i64>,
+    Form(ReportReviewReq { reason, detail }): Form<ReportReviewReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        let reason = pb::ReportReason::from_str_name(&reason.to_uppercase()).ok_or_else(|| {
+            HttpStatus::from(Status::invalid_argument(
+                "Request field reason is invalid, expect spam, abuse, spoiler, copyright or other",
+            ))
+        })?;
+        resolver
+            .review_client()
+            .report_review(pb::ReportReviewReq {
+                id,
+                uid,
+                reason: reason as i32,
+                detail,
+            })
+            .await
+            .map(|_| ())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
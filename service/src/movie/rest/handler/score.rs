@@ -0,0 +1,31 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Path(id): Path<i64>,
+    Form(ScoreReq {
+        star,
+        comment,
+        tags,
+    }): Form<ScoreReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        resolver
+            .score_client()
+            .score(score_pb::ScoreReq {
+                star,
+                movie_id: id,
+                uid,
+                comment,
+                tags: split_tags(tags.as_deref()),
+            })
+            .await
+            .map(|_| ())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
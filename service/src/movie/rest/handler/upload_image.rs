@@ -0,0 +1,50 @@
+use super::*;
+use axum::extract::{Multipart, Query};
+
+// unit (byte), size of a message streamed to the movie service
+const CHUNK_SIZE: usize = 64 * 1024;
+
+// the image is the field named file of a multipart form
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Query(UploadImageReq { kind }): Query<UploadImageReq>,
+    mut multipart: Multipart,
+) -> (StatusCode, Json<Resp<media_pb::UploadImageRes>>) {
+    let resp = async {
+        user_id(&uid)?;
+        let kind = media_pb::ImageKind::from_str_name(&kind.to_uppercase()).ok_or_else(|| {
+            HttpStatus::from(Status::invalid_argument(
+                "Request field kind is invalid, expect poster or photo",
+            ))
+        })?;
+        let invalid = || {
+            HttpStatus::from(Status::invalid_argument(
+                "Request body is invalid, expect a multipart form with a file",
+            ))
+        };
+        let mut content = None;
+        while let Some(field) = multipart.next_field().await.map_err(|_| invalid())? {
+            if field.name() == Some("file") {
+                content = Some(field.bytes().await.map_err(|_| invalid())?);
+                break;
+            }
+        }
+        let content = content.ok_or_else(invalid)?;
+        let chunks: Vec<_> = content
+            .chunks(CHUNK_SIZE)
+            .map(|chunk| media_pb::UploadImageReq {
+                kind: kind as i32,
+                chunk: chunk.to_vec(),
+            })
+            .collect();
+        resolver
+            .media_client()
+            .upload_image(futures::stream::iter(chunks))
+            .await
+            .map(|res| res.into_inner())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
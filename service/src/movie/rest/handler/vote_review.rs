@@ -0,0 +1,30 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Path(id): Path<i64>,
+    Form(VoteReviewReq { vote }): Form<VoteReviewReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = async {
+        let uid = user_id(&uid)?;
+        let vote = pb::Vote::from_str_name(&vote.to_uppercase()).ok_or_else(|| {
+            HttpStatus::from(Status::invalid_argument(
+                "Request field vote is invalid, expect up or down",
+            ))
+        })?;
+        resolver
+            .review_client()
+            .vote_review(pb::VoteReviewReq {
+                id,
+                uid,
+                vote: vote as i32,
+            })
+            .await
+            .map(|_| ())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
@@ -1 +1,149 @@
+use crate::movie::rest::error::handle_error;
+use crate::movie::rpc::MovieResolver;
+use axum::error_handling::HandleErrorLayer;
+use common::config::layer::LayerConfig;
+use common::config::middleware::MiddlewareConfig;
+use common::config::service::ServiceConfig;
+use common::config::Config;
+use common::infra::*;
+use common::registry::{EtcdRegistry, ServiceDiscover};
+use proto::pb::movie::mark::v1::mark_service_client::MarkServiceClient;
+use proto::pb::movie::media::v1::media_service_client::MediaServiceClient;
+use proto::pb::movie::review::v1::review_service_client::ReviewServiceClient;
+use proto::pb::movie::score::v1::score_service_client::ScoreServiceClient;
+use proto::pb::movie::tag::v1::tag_service_client::TagServiceClient;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tonic::transport::Channel;
+use tower::load_shed::LoadShedLayer;
+use tower::ServiceBuilder;
+use tower_http::ServiceBuilderExt;
 
+mod error;
+mod handler;
+mod router;
+mod types;
+
+fn max_upload_size() -> usize {
+    10 * 1024 * 1024
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RestConfig {
+    #[serde(default)]
+    pub service_conf: <Config as ServiceConfig>::RestService,
+    #[serde(default)]
+    pub etcd: <Config as MiddlewareConfig>::Etcd,
+    // the same cookie as the user rest service, so that logged in users are recognized
+    #[serde(default)]
+    pub cookie_conf: <Config as LayerConfig>::CookieAuth,
+    // unit (byte), body limit of uploading images, keep it no less than the max_size of
+    // media of the movie service
+    #[serde(default = "max_upload_size")]
+    pub max_upload_size: usize,
+}
+
+impl Default for RestConfig {
+    fn default() -> Self {
+        Self {
+            service_conf: Default::default(),
+            etcd: Default::default(),
+            cookie_conf: Default::default(),
+            max_upload_size: max_upload_size(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RestResolver {
+    conf: RestConfig,
+    review_client: ReviewServiceClient<Channel>,
+    score_client: ScoreServiceClient<Channel>,
+    mark_client: MarkServiceClient<Channel>,
+    tag_client: TagServiceClient<Channel>,
+    media_client: MediaServiceClient<Channel>,
+}
+
+impl Resolver for RestResolver {
+    const TARGET: Target = Target::REST;
+    const DOMAIN: &'static str = "movie";
+    type Config = RestConfig;
+
+    fn conf(&self) -> &Self::Config {
+        &self.conf
+    }
+}
+
+impl RestResolver {
+    pub async fn new(conf: RestConfig) -> Self {
+        let registry = EtcdRegistry::discover(conf.etcd.clone());
+        let (channel, tx) = Channel::balance_channel(1024);
+        let service_key = MovieResolver::service_key();
+        registry
+            .discover_to_channel(&service_key, tx)
+            .await
+            .expect("Cannot discover movie service to channel");
+        let review_client = ReviewServiceClient::new(channel.clone());
+        let score_client = ScoreServiceClient::new(channel.clone());
+        let mark_client = MarkServiceClient::new(channel.clone());
+        let tag_client = TagServiceClient::new(channel.clone());
+        let media_client = MediaServiceClient::new(channel);
+        Self {
+            conf,
+            review_client,
+            score_client,
+            mark_client,
+            tag_client,
+            media_client,
+        }
+    }
+
+    pub fn review_client(&self) -> ReviewServiceClient<Channel> {
+        self.review_client.clone()
+    }
+
+    pub fn score_client(&self) -> ScoreServiceClient<Channel> {
+        self.score_client.clone()
+    }
+
+    pub fn mark_client(&self) -> MarkServiceClient<Channel> {
+        self.mark_client.clone()
+    }
+
+    pub fn tag_client(&self) -> TagServiceClient<Channel> {
+        self.tag_client.clone()
+    }
+
+    pub fn media_client(&self) -> MediaServiceClient<Channel> {
+        self.media_client.clone()
+    }
+
+    pub async fn serve(&self) {
+        let addr = self.conf.service_conf.service.listen_addr.parse().unwrap();
+        axum::Server::bind(&addr)
+            .serve(
+                self.make_router()
+                    .await
+                    .layer(
+                        ServiceBuilder::new()
+                            .catch_panic()
+                            .trace_for_http()
+                            .layer(HandleErrorLayer::new(handle_error))
+                            .timeout(Duration::from_secs(self.conf.service_conf.service.timeout))
+                            .option_layer(if self.conf.service_conf.service.load_shed {
+                                Some(LoadShedLayer::new())
+                            } else {
+                                None
+                            })
+                            .concurrency_limit(self.conf.service_conf.service.concurrency_limit),
+                    )
+                    .into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,73 @@
+use crate::auth::layer::{AuthBuilder, WWWAuth};
+use crate::movie::rest::handler::del_comment;
+use crate::movie::rest::handler::del_review;
+use crate::movie::rest::handler::edit_review;
+use crate::movie::rest::handler::get_image;
+use crate::movie::rest::handler::get_mark_count;
+use crate::movie::rest::handler::get_my_score;
+use crate::movie::rest::handler::list_movie_tags;
+use crate::movie::rest::handler::list_my_marks;
+use crate::movie::rest::handler::list_my_tags;
+use crate::movie::rest::handler::mark;
+use crate::movie::rest::handler::post_review;
+use crate::movie::rest::handler::put_comment;
+use crate::movie::rest::handler::report_review;
+use crate::movie::rest::handler::score;
+use crate::movie::rest::handler::unmark;
+use crate::movie::rest::handler::unscore;
+use crate::movie::rest::handler::unvote_comment;
+use crate::movie::rest::handler::unvote_review;
+use crate::movie::rest::handler::upload_image;
+use crate::movie::rest::handler::vote_comment;
+use crate::movie::rest::handler::vote_review;
+use crate::movie::rest::types::IdProvider;
+use crate::movie::rest::RestResolver;
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{get, post};
+use axum::Router;
+use common::infra::Resolver;
+use common::layer::AsyncHttpAuthLayer;
+use std::sync::Arc;
+use tower::ServiceBuilder;
+
+impl RestResolver {
+    pub async fn make_router(&self) -> Router {
+        let auth = AuthBuilder::cookie(self.conf.etcd.clone())
+            .cookie_conf(self.conf.cookie_conf.clone())
+            .www(WWWAuth::cookie(
+                Self::DOMAIN,
+                self.conf.cookie_conf.cookie_name.as_str(),
+            ))
+            .finish::<IdProvider, _>()
+            .await;
+        let auth_router = Router::new()
+            .route("/movies/:id/reviews", post(post_review::handle))
+            .route("/reviews/:id", post(edit_review::handle))
+            .route("/reviews/:id/delete", post(del_review::handle))
+            .route("/movies/:id/comment", post(put_comment::handle))
+            .route("/movies/:id/uncomment", post(del_comment::handle))
+            .route("/comments/:id/vote", post(vote_comment::handle))
+            .route("/comments/:id/unvote", post(unvote_comment::handle))
+            .route("/reviews/:id/vote", post(vote_review::handle))
+            .route("/reviews/:id/unvote", post(unvote_review::handle))
+            .route("/reviews/:id/report", post(report_review::handle))
+            .route("/movies/:id/mark", post(mark::handle))
+            .route("/movies/:id/unmark", post(unmark::handle))
+            .route("/movies/:id/score", post(score::handle))
+            .route("/movies/:id/unscore", post(unscore::handle))
+            .route("/me/scores/:id", get(get_my_score::handle))
+            .route("/me/marks", get(list_my_marks::handle))
+            .route("/me/tags", get(list_my_tags::handle))
+            .route(
+                "/media",
+                post(upload_image::handle).layer(DefaultBodyLimit::max(self.conf.max_upload_size)),
+            )
+            .layer(ServiceBuilder::new().layer(AsyncHttpAuthLayer::new(auth)));
+        Router::new()
+            .route("/movies/:id/marks", get(get_mark_count::handle))
+            .route("/movies/:id/tags", get(list_movie_tags::handle))
+            .route("/media/*key", get(get_image::handle))
+            .merge(auth_router)
+            .with_state(Arc::new(self.clone()))
+    }
+}
@@ -0,0 +1,85 @@
+use crate::auth::layer::IdentityProvider;
+use common::infra::Id;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct VoteReviewReq {
+    // up or down
+    pub(crate) vote: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct ReportReviewReq {
+    // spam, abuse, spoiler, copyright or other
+    pub(crate) reason: String,
+    pub(crate) detail: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct ReviewReq {
+    pub(crate) title: String,
+    pub(crate) content: String,
+    pub(crate) spoiler: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct CommentReq {
+    pub(crate) content: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct ScoreReq {
+    // 1-5
+    pub(crate) star: i32,
+    pub(crate) comment: Option<String>,
+    // tags separated by comma, replace the tags of the mark if any
+    pub(crate) tags: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct MarkReq {
+    // wish, watching or watched
+    pub(crate) status: String,
+    // tags separated by comma
+    pub(crate) tags: Option<String>,
+    // only for watched
+    pub(crate) star: Option<i32>,
+    pub(crate) comment: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct ListMyMarksReq {
+    // wish, watching or watched, all if absent
+    pub(crate) status: Option<String>,
+    pub(crate) tag: Option<String>,
+    pub(crate) page: Option<i32>,
+    pub(crate) per_page: Option<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct ListTagsReq {
+    pub(crate) limit: Option<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct UploadImageReq {
+    // poster or photo
+    pub(crate) kind: String,
+}
+
+#[derive(Clone)]
+pub(crate) struct IdProvider;
+
+pub(crate) struct User;
+pub(crate) struct Group;
+pub(crate) struct Extra;
+
+pub(crate) type UserId = Id<User>;
+pub(crate) type GroupId = Id<Group>;
+pub(crate) type ExtraId = Id<Extra>;
+
+impl IdentityProvider for IdProvider {
+    type Id = UserId;
+    type Group = GroupId;
+    type Extra = ExtraId;
+}
@@ -0,0 +1,62 @@
+use crate::movie::rpc::MovieResolver;
+use common::infra::*;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::celebrity::v1::celebrity_service_server;
+use proto::pb::movie::celebrity::v1::*;
+use tonic::{Request, Response, Status};
+
+pub struct CelebrityService(pub MovieResolver);
+
+#[tonic::async_trait]
+impl celebrity_service_server::CelebrityService for CelebrityService {
+    async fn get_celebrity(&self, req: Request<GetReq>) -> Result<Response<GetRes>, Status> {
+        let query = self.0.create_get_celebrity();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_celebrities(&self, req: Request<ListReq>) -> Result<Response<ListRes>, Status> {
+        let query = self.0.create_list_celebrities();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_actors(&self, req: Request<ListReq>) -> Result<Response<ListRes>, Status> {
+        let query = self.0.create_list_actors();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_directors(&self, req: Request<ListReq>) -> Result<Response<ListRes>, Status> {
+        let query = self.0.create_list_directors();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_writers(&self, req: Request<ListReq>) -> Result<Response<ListRes>, Status> {
+        let query = self.0.create_list_writers();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn put_celebrity(&self, req: Request<PutReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_put_celebrity();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn del_celebrity(&self, req: Request<DelReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_del_celebrity();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn get_filmography(
+        &self,
+        req: Request<GetFilmographyReq>,
+    ) -> Result<Response<GetFilmographyRes>, Status> {
+        let query = self.0.create_get_filmography();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+}
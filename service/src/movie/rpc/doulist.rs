@@ -0,0 +1,113 @@
+use crate::movie::rpc::MovieResolver;
+use common::infra::*;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::doulist::v1::doulist_service_server;
+use proto::pb::movie::doulist::v1::*;
+use tonic::{Request, Response, Status};
+
+pub struct DoulistService(pub MovieResolver);
+
+#[tonic::async_trait]
+impl doulist_service_server::DoulistService for DoulistService {
+    async fn create_doulist(
+        &self,
+        req: Request<CreateDoulistReq>,
+    ) -> Result<Response<CreateDoulistRes>, Status> {
+        let cmd = self.0.create_create_doulist();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn edit_doulist(
+        &self,
+        req: Request<EditDoulistReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_edit_doulist();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn del_doulist(&self, req: Request<DelDoulistReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_del_doulist();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn get_doulist(
+        &self,
+        req: Request<GetDoulistReq>,
+    ) -> Result<Response<GetDoulistRes>, Status> {
+        let query = self.0.create_get_doulist();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_doulists(
+        &self,
+        req: Request<ListDoulistsReq>,
+    ) -> Result<Response<ListDoulistsRes>, Status> {
+        let query = self.0.create_list_doulists();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_followed_doulists(
+        &self,
+        req: Request<ListFollowedDoulistsReq>,
+    ) -> Result<Response<ListFollowedDoulistsRes>, Status> {
+        let query = self.0.create_list_followed_doulists();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_items(
+        &self,
+        req: Request<ListItemsReq>,
+    ) -> Result<Response<ListItemsRes>, Status> {
+        let query = self.0.create_list_items();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn add_item(&self, req: Request<AddItemReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_add_item();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn edit_item(&self, req: Request<EditItemReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_edit_item();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn move_item(&self, req: Request<MoveItemReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_move_item();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn remove_item(&self, req: Request<RemoveItemReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_remove_item();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn follow_doulist(
+        &self,
+        req: Request<FollowDoulistReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_follow_doulist();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn unfollow_doulist(
+        &self,
+        req: Request<UnfollowDoulistReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_unfollow_doulist();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+}
@@ -0,0 +1,41 @@
+use crate::movie::rpc::MovieResolver;
+use common::infra::*;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::mark::v1::mark_service_server;
+use proto::pb::movie::mark::v1::*;
+use tonic::{Request, Response, Status};
+
+pub struct MarkService(pub MovieResolver);
+
+#[tonic::async_trait]
+impl mark_service_server::MarkService for MarkService {
+    async fn mark(&self, req: Request<MarkReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_mark();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn unmark(&self, req: Request<UnmarkReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_unmark();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_my_marks(
+        &self,
+        req: Request<ListMyMarksReq>,
+    ) -> Result<Response<ListMyMarksRes>, Status> {
+        let query = self.0.create_list_my_marks();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn get_mark_count(
+        &self,
+        req: Request<GetMarkCountReq>,
+    ) -> Result<Response<GetMarkCountRes>, Status> {
+        let query = self.0.create_get_mark_count();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+}
@@ -0,0 +1,39 @@
+use crate::movie::rpc::MovieResolver;
+use common::infra::*;
+use common::invalid_argument;
+use proto::pb::movie::media::v1::media_service_server;
+use proto::pb::movie::media::v1::*;
+use tonic::{Request, Response, Status, Streaming};
+
+pub struct MediaService(pub MovieResolver);
+
+#[tonic::async_trait]
+impl media_service_server::MediaService for MediaService {
+    async fn upload_image(
+        &self,
+        req: Request<Streaming<UploadImageReq>>,
+    ) -> Result<Response<UploadImageRes>, Status> {
+        let max_size = self.0.media_conf().max_size;
+        let mut stream = req.into_inner();
+        // chunks are gathered into the first message, stopped once the image is too large
+        let mut image = match stream.message().await? {
+            Some(first) => first,
+            None => return Err(invalid_argument!("chunk", "an image").into()),
+        };
+        while let Some(next) = stream.message().await? {
+            if image.chunk.len() + next.chunk.len() > max_size {
+                return Err(invalid_argument!("chunk", "an image within the size limit").into());
+            }
+            image.chunk.extend_from_slice(&next.chunk);
+        }
+        let cmd = self.0.create_upload_image();
+        let resp = cmd.execute(image).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn get_image(&self, req: Request<GetImageReq>) -> Result<Response<GetImageRes>, Status> {
+        let query = self.0.create_get_image();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+}
@@ -1,8 +1,178 @@
+pub mod celebrity;
+pub mod doulist;
+pub mod mark;
+pub mod media;
+pub mod movie;
+pub mod recommend;
+pub mod review;
+pub mod score;
+pub mod tag;
+
+use crate::movie::domain::media::model::blob::{
+    BlobStore, LocalBlobStore, MemoryBlobStore, S3BlobStore,
+};
+use crate::movie::domain::movie::model::ranking::Ranking;
+use crate::movie::domain::recommend::model::similarity::Similarity;
+use crate::movie::domain::search::backfill_pinyin;
+use crate::movie::domain::tag::model::tag::backfill_tags;
+use crate::movie::rpc::celebrity::CelebrityService;
+use crate::movie::rpc::doulist::DoulistService;
+use crate::movie::rpc::mark::MarkService;
+use crate::movie::rpc::media::MediaService;
+use crate::movie::rpc::movie::MovieService;
+use crate::movie::rpc::recommend::RecommendService;
+use crate::movie::rpc::review::ReviewService;
+use crate::movie::rpc::score::ScoreService;
+use crate::movie::rpc::tag::TagService;
+use common::config::env::optional;
 use common::config::middleware::MiddlewareConfig;
 use common::config::service::ServiceConfig;
 use common::config::Config;
 use common::infra::{Resolver, Target};
+use common::registry::{EtcdRegistry, ServiceRegister};
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+use proto::pb::movie::celebrity::v1::celebrity_service_server::CelebrityServiceServer;
+use proto::pb::movie::doulist::v1::doulist_service_server::DoulistServiceServer;
+use proto::pb::movie::mark::v1::mark_service_server::MarkServiceServer;
+use proto::pb::movie::media::v1::media_service_server::MediaServiceServer;
+use proto::pb::movie::movie::v1::movie_service_server::MovieServiceServer;
+use proto::pb::movie::recommend::v1::recommend_service_server::RecommendServiceServer;
+use proto::pb::movie::review::v1::review_service_server::ReviewServiceServer;
+use proto::pb::movie::score::v1::score_service_server::ScoreServiceServer;
+use proto::pb::movie::tag::v1::tag_service_server::TagServiceServer;
+use r2d2::PooledConnection;
+use s3::error::S3Error;
 use serde::{Deserialize, Serialize};
+use std::ops::DerefMut;
+use std::time::Duration;
+use tonic::transport::Server;
+use tower::load_shed::LoadShedLayer;
+use tower::ServiceBuilder;
+use tower_http::ServiceBuilderExt;
+
+fn pg_dsn() -> String {
+    optional("PG_DB", "postgres://root:@localhost/s_douban_rs")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RankingConfig {
+    // minimum ratings of a movie to enter the charts, also the weight of the global mean
+    pub min_votes: i64,
+    // mean score in 0-10 that scores are pulled towards, the mean of all ratings if absent
+    pub global_mean: Option<f64>,
+    // max movies of every chart
+    pub chart_size: i64,
+    // unit (second), how often the charts are computed, must be positive
+    pub refresh_interval: u64,
+}
+
+impl Default for RankingConfig {
+    fn default() -> Self {
+        Self {
+            min_votes: 1000,
+            global_mean: None,
+            chart_size: 250,
+            refresh_interval: 3600,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecommendConfig {
+    // neighbours kept for every movie
+    pub neighbours: usize,
+    // minimum users rating both movies for a similarity to count
+    pub min_common: u32,
+    // only the latest ratings of a user are used, bounding the pairs of a user
+    pub max_user_ratings: usize,
+    // unit (second), how often the similarities are computed, must be positive
+    pub refresh_interval: u64,
+}
+
+impl Default for RecommendConfig {
+    fn default() -> Self {
+        Self {
+            neighbours: 20,
+            min_common: 3,
+            max_user_ratings: 300,
+            refresh_interval: 86400,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BlobStoreKind {
+    Local,
+    S3,
+    // lost on restart, only for trying out the service
+    Memory,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct S3Config {
+    // i.e. http://127.0.0.1:9000 for a local MinIO
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+    // address the bucket by path instead of subdomain, required by MinIO
+    pub path_style: bool,
+}
+
+impl Default for S3Config {
+    fn default() -> Self {
+        Self {
+            endpoint: "http://127.0.0.1:9000".to_string(),
+            region: "us-east-1".to_string(),
+            bucket: "douban".to_string(),
+            access_key: optional("S3_ACCESS_KEY", "minioadmin"),
+            secret_key: optional("S3_SECRET_KEY", "minioadmin"),
+            path_style: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MediaConfig {
+    // where the images are stored
+    pub store: BlobStoreKind,
+    // root directory of the local store
+    pub local_root: String,
+    pub s3: S3Config,
+    // urls are the key appended to it, i.e. the media route of the rest service or the
+    // public url of the bucket
+    pub public_url: String,
+    // unit (byte)
+    pub max_size: usize,
+    // unit (pixel), max width and height, bounding the memory of decoding
+    pub max_dimension: u32,
+    // unit (pixel), widths of the thumbnails, the ones not narrower than the image are skipped
+    pub thumbnail_widths: Vec<u32>,
+    // 1-100, quality of the lossy thumbnails and WebP variants
+    pub quality: u8,
+}
+
+impl Default for MediaConfig {
+    fn default() -> Self {
+        Self {
+            store: BlobStoreKind::Local,
+            local_root: "./media".to_string(),
+            s3: Default::default(),
+            public_url: "http://127.0.0.1:5002/media".to_string(),
+            max_size: 10 * 1024 * 1024,
+            max_dimension: 8192,
+            thumbnail_widths: vec![160, 320, 640],
+            quality: 80,
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MovieConfig {
@@ -12,11 +182,38 @@ pub struct MovieConfig {
     redis: <Config as MiddlewareConfig>::Redis,
     #[serde(default)]
     etcd: <Config as MiddlewareConfig>::Etcd,
+    #[serde(default = "pg_dsn")]
+    pg_dsn: String,
+    #[serde(default)]
+    ranking: RankingConfig,
+    #[serde(default)]
+    recommend: RecommendConfig,
+    #[serde(default)]
+    media: MediaConfig,
 }
 
+impl Default for MovieConfig {
+    fn default() -> Self {
+        Self {
+            service_conf: Default::default(),
+            redis: Default::default(),
+            etcd: Default::default(),
+            pg_dsn: pg_dsn(),
+            ranking: Default::default(),
+            recommend: Default::default(),
+            media: Default::default(),
+        }
+    }
+}
+
+type Register<T> = common::config::register::Register<MovieConfig, T>;
+
 #[derive(Clone)]
 pub struct MovieResolver {
     conf: MovieConfig,
+    pg_pool: Register<&'static Pool<ConnectionManager<PgConnection>>>,
+    // built up front, so that an invalid config fails the start instead of a request
+    blob_store: &'static dyn BlobStore,
 }
 
 impl Resolver for MovieResolver {
@@ -28,3 +225,210 @@ impl Resolver for MovieResolver {
         &self.conf
     }
 }
+
+impl MovieResolver {
+    /// Fails on an invalid config of the S3 blob store.
+    pub fn new(conf: MovieConfig) -> Result<Self, S3Error> {
+        let blob_store: Box<dyn BlobStore> = match conf.media.store {
+            BlobStoreKind::Local => Box::new(LocalBlobStore::new(&conf.media.local_root)),
+            BlobStoreKind::S3 => Box::new(S3BlobStore::new(&conf.media.s3)?),
+            BlobStoreKind::Memory => Box::<MemoryBlobStore>::default(),
+        };
+        Ok(Self {
+            conf,
+            pg_pool: Register::once_ref(|conf| {
+                Pool::new(ConnectionManager::new(&conf.pg_dsn)).unwrap()
+            }),
+            blob_store: Box::leak(blob_store),
+        })
+    }
+
+    pub fn blob_store(&self) -> &'static dyn BlobStore {
+        self.blob_store
+    }
+
+    pub fn media_conf(&self) -> &MediaConfig {
+        &self.conf.media
+    }
+
+    pub fn pg_conn(&self) -> PooledConnection<ConnectionManager<PgConnection>> {
+        self.resolve(&self.pg_pool)
+            .get()
+            .expect("Cannot get pg connection")
+    }
+
+    pub async fn register_service(&self) {
+        let registry = EtcdRegistry::register(
+            self.conf.etcd.clone(),
+            self.conf.service_conf.service.clone(),
+        );
+        let service_key = Self::service_key();
+        registry
+            .register_service(&service_key)
+            .await
+            .expect("Cannot register service into etcd");
+    }
+
+    /// Spawn the job filling the pinyin of movies and celebrities without it once, every
+    /// instance may run it as filling is idempotent.
+    pub fn spawn_pinyin_job(&self) -> tokio::task::JoinHandle<()> {
+        let resolver = self.clone();
+        tokio::task::spawn_blocking(
+            move || match backfill_pinyin(resolver.pg_conn().deref_mut()) {
+                Ok(filled) => tracing::info!("Filled pinyin of {} movies and celebrities", filled),
+                Err(e) => tracing::error!("Failed to fill pinyin, err: {:?}", e),
+            },
+        )
+    }
+
+    /// Spawn the job normalizing the tags of marks tagged before normalization once,
+    /// every instance may run it as normalizing is idempotent.
+    pub fn spawn_tags_job(&self) -> tokio::task::JoinHandle<()> {
+        let resolver = self.clone();
+        tokio::task::spawn_blocking(
+            move || match backfill_tags(resolver.pg_conn().deref_mut()) {
+                Ok(retagged) => tracing::info!("Normalized tags of {} marks", retagged),
+                Err(e) => tracing::error!("Failed to normalize tags, err: {:?}", e),
+            },
+        )
+    }
+
+    /// Spawn the job recomputing the top charts, every instance may run it, the
+    /// charts are computed by one of them at a time on the blocking threads.
+    pub fn spawn_ranking_job(&self) -> tokio::task::JoinHandle<()> {
+        let resolver = self.clone();
+        let conf = resolver.conf.ranking.clone();
+        assert!(
+            conf.refresh_interval > 0,
+            "ranking.refresh_interval must be positive"
+        );
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(conf.refresh_interval));
+            loop {
+                interval.tick().await;
+                let (resolver, conf) = (resolver.clone(), conf.clone());
+                let refreshed = tokio::task::spawn_blocking(move || {
+                    Ranking::refresh(&conf, resolver.pg_conn().deref_mut())
+                })
+                .await;
+                match refreshed {
+                    Ok(Ok(Some(ranked))) => tracing::info!("Ranked {} entries into charts", ranked),
+                    Ok(Ok(None)) => {
+                        tracing::debug!("Charts are being computed by another instance")
+                    }
+                    Ok(Err(e)) => tracing::error!("Failed to compute charts, err: {:?}", e),
+                    Err(e) => tracing::error!("Ranking job panicked, err: {:?}", e),
+                }
+            }
+        })
+    }
+
+    /// Spawn the job recomputing the similarities of movies, the computation is CPU
+    /// bound and runs on the blocking threads.
+    pub fn spawn_similarity_job(&self) -> tokio::task::JoinHandle<()> {
+        let resolver = self.clone();
+        let conf = resolver.conf.recommend.clone();
+        assert!(
+            conf.refresh_interval > 0,
+            "recommend.refresh_interval must be positive"
+        );
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(conf.refresh_interval));
+            loop {
+                interval.tick().await;
+                let (resolver, conf) = (resolver.clone(), conf.clone());
+                let refreshed = tokio::task::spawn_blocking(move || {
+                    Similarity::refresh(&conf, resolver.pg_conn().deref_mut())
+                })
+                .await;
+                match refreshed {
+                    Ok(Ok(Some(pairs))) => tracing::info!("Computed {} similar movies", pairs),
+                    Ok(Ok(None)) => {
+                        tracing::debug!("Similarities are being computed by another instance")
+                    }
+                    Ok(Err(e)) => tracing::error!("Failed to compute similarities, err: {:?}", e),
+                    Err(e) => tracing::error!("Similarity job panicked, err: {:?}", e),
+                }
+            }
+        })
+    }
+
+    pub async fn serve(&self) -> Result<(), tonic::transport::Error> {
+        let movie_srv = MovieService(self.clone());
+        let celebrity_srv = CelebrityService(self.clone());
+        let score_srv = ScoreService(self.clone());
+        let review_srv = ReviewService(self.clone());
+        let mark_srv = MarkService(self.clone());
+        let tag_srv = TagService(self.clone());
+        let doulist_srv = DoulistService(self.clone());
+        let recommend_srv = RecommendService(self.clone());
+        let media_srv = MediaService(self.clone());
+        let addr = self
+            .conf
+            .service_conf
+            .service
+            .listen_addr
+            .parse()
+            .expect("cannot parse a valid listen_addr");
+
+        let layer = ServiceBuilder::new()
+            .catch_panic()
+            .trace_for_grpc()
+            .option_layer(if self.conf.service_conf.service.load_shed {
+                Some(LoadShedLayer::new())
+            } else {
+                None
+            })
+            .concurrency_limit(self.conf.service_conf.service.concurrency_limit);
+
+        let serve = Server::builder()
+            .timeout(Duration::from_secs(self.conf.service_conf.service.timeout))
+            .layer(layer)
+            .add_optional_service(if self.conf.service_conf.health_check {
+                let (mut reporter, svc) = tonic_health::server::health_reporter();
+                reporter
+                    .set_serving::<MovieServiceServer<MovieService>>()
+                    .await;
+                reporter
+                    .set_serving::<CelebrityServiceServer<CelebrityService>>()
+                    .await;
+                reporter
+                    .set_serving::<ScoreServiceServer<ScoreService>>()
+                    .await;
+                reporter
+                    .set_serving::<ReviewServiceServer<ReviewService>>()
+                    .await;
+                reporter
+                    .set_serving::<MarkServiceServer<MarkService>>()
+                    .await;
+                reporter.set_serving::<TagServiceServer<TagService>>().await;
+                reporter
+                    .set_serving::<DoulistServiceServer<DoulistService>>()
+                    .await;
+                reporter
+                    .set_serving::<RecommendServiceServer<RecommendService>>()
+                    .await;
+                reporter
+                    .set_serving::<MediaServiceServer<MediaService>>()
+                    .await;
+                Some(svc)
+            } else {
+                None
+            })
+            .add_service(MovieServiceServer::new(movie_srv))
+            .add_service(CelebrityServiceServer::new(celebrity_srv))
+            .add_service(ScoreServiceServer::new(score_srv))
+            .add_service(ReviewServiceServer::new(review_srv))
+            .add_service(MarkServiceServer::new(mark_srv))
+            .add_service(TagServiceServer::new(tag_srv))
+            .add_service(DoulistServiceServer::new(doulist_srv))
+            .add_service(RecommendServiceServer::new(recommend_srv))
+            .add_service(MediaServiceServer::new(media_srv));
+
+        serve
+            .serve_with_shutdown(addr, async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await
+    }
+}
@@ -0,0 +1,44 @@
+use crate::movie::rpc::MovieResolver;
+use common::infra::*;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::movie::v1::movie_service_server;
+use proto::pb::movie::movie::v1::*;
+use tonic::{Request, Response, Status};
+
+pub struct MovieService(pub MovieResolver);
+
+#[tonic::async_trait]
+impl movie_service_server::MovieService for MovieService {
+    async fn get_movie(&self, req: Request<GetReq>) -> Result<Response<GetRes>, Status> {
+        let query = self.0.create_get_movie();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_movies(&self, req: Request<ListReq>) -> Result<Response<ListRes>, Status> {
+        let query = self.0.create_list_movies();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn put_movie(&self, req: Request<PutReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_put_movie();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn del_movie(&self, req: Request<DelReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_del_movie();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_top_movies(
+        &self,
+        req: Request<ListTopMoviesReq>,
+    ) -> Result<Response<ListTopMoviesRes>, Status> {
+        let query = self.0.create_list_top_movies();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+}
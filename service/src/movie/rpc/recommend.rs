@@ -0,0 +1,28 @@
+use crate::movie::rpc::MovieResolver;
+use common::infra::*;
+use proto::pb::movie::recommend::v1::recommend_service_server;
+use proto::pb::movie::recommend::v1::*;
+use tonic::{Request, Response, Status};
+
+pub struct RecommendService(pub MovieResolver);
+
+#[tonic::async_trait]
+impl recommend_service_server::RecommendService for RecommendService {
+    async fn recommend_for_user(
+        &self,
+        req: Request<RecommendForUserReq>,
+    ) -> Result<Response<RecommendForUserRes>, Status> {
+        let query = self.0.create_recommend_for_user();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn similar_movies(
+        &self,
+        req: Request<SimilarMoviesReq>,
+    ) -> Result<Response<SimilarMoviesRes>, Status> {
+        let query = self.0.create_similar_movies();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+}
@@ -0,0 +1,131 @@
+use crate::movie::rpc::MovieResolver;
+use common::infra::*;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::review::v1::review_service_server;
+use proto::pb::movie::review::v1::*;
+use tonic::{Request, Response, Status};
+
+pub struct ReviewService(pub MovieResolver);
+
+#[tonic::async_trait]
+impl review_service_server::ReviewService for ReviewService {
+    async fn get_review(
+        &self,
+        req: Request<GetReviewReq>,
+    ) -> Result<Response<GetReviewRes>, Status> {
+        let query = self.0.create_get_review();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_reviews(
+        &self,
+        req: Request<ListReviewsReq>,
+    ) -> Result<Response<ListReviewsRes>, Status> {
+        let query = self.0.create_list_reviews();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn post_review(
+        &self,
+        req: Request<PostReviewReq>,
+    ) -> Result<Response<PostReviewRes>, Status> {
+        let cmd = self.0.create_post_review();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn edit_review(&self, req: Request<EditReviewReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_edit_review();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn del_review(&self, req: Request<DelReviewReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_del_review();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_comments(
+        &self,
+        req: Request<ListCommentsReq>,
+    ) -> Result<Response<ListCommentsRes>, Status> {
+        let query = self.0.create_list_comments();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn put_comment(&self, req: Request<PutCommentReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_put_comment();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn del_comment(&self, req: Request<DelCommentReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_del_comment();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn vote_comment(
+        &self,
+        req: Request<VoteCommentReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_vote_comment();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn unvote_comment(
+        &self,
+        req: Request<UnvoteCommentReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_unvote_comment();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn vote_review(&self, req: Request<VoteReviewReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_vote_review();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn unvote_review(
+        &self,
+        req: Request<UnvoteReviewReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_unvote_review();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn report_review(
+        &self,
+        req: Request<ReportReviewReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_report_review();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_reports(
+        &self,
+        req: Request<ListReportsReq>,
+    ) -> Result<Response<ListReportsRes>, Status> {
+        let query = self.0.create_list_reports();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn handle_reports(
+        &self,
+        req: Request<HandleReportsReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_handle_reports();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+}
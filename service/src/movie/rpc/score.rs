@@ -0,0 +1,38 @@
+use crate::movie::rpc::MovieResolver;
+use common::infra::*;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::movie::score::v1::score_service_server;
+use proto::pb::movie::score::v1::*;
+use tonic::{Request, Response, Status};
+
+pub struct ScoreService(pub MovieResolver);
+
+#[tonic::async_trait]
+impl score_service_server::ScoreService for ScoreService {
+    async fn get_score(&self, req: Request<GetReq>) -> Result<Response<GetRes>, Status> {
+        let query = self.0.create_get_score();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn score(&self, req: Request<ScoreReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_score();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn unscore(&self, req: Request<UnscoreReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_unscore();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn get_my_score(
+        &self,
+        req: Request<GetMyScoreReq>,
+    ) -> Result<Response<GetMyScoreRes>, Status> {
+        let query = self.0.create_get_my_score();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+}
@@ -0,0 +1,28 @@
+use crate::movie::rpc::MovieResolver;
+use common::infra::*;
+use proto::pb::movie::tag::v1::tag_service_server;
+use proto::pb::movie::tag::v1::*;
+use tonic::{Request, Response, Status};
+
+pub struct TagService(pub MovieResolver);
+
+#[tonic::async_trait]
+impl tag_service_server::TagService for TagService {
+    async fn list_movie_tags(
+        &self,
+        req: Request<ListMovieTagsReq>,
+    ) -> Result<Response<ListMovieTagsRes>, Status> {
+        let query = self.0.create_list_movie_tags();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_my_tags(
+        &self,
+        req: Request<ListMyTagsReq>,
+    ) -> Result<Response<ListMyTagsRes>, Status> {
+        let query = self.0.create_list_my_tags();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+}
@@ -0,0 +1,35 @@
+use crate::user::domain::follow::model::follow::Follow;
+use crate::user::domain::user::model::ban::Ban;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::FollowReq,
+    conn: &mut PgConnection,
+    redis: &mut redis::Connection,
+) -> GrpcResult<EmptyRes> {
+    let user = User::query_identifier(&req.identifier, conn)?;
+    Ban::check(user.id(), conn)?;
+    let target = User::query_alive(req.uid, conn)?;
+    Follow::follow(user.id(), target.id(), conn, redis)?;
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_follow(&self) -> impl Command<pb::FollowReq> + '_ {
+        move |req: pb::FollowReq| async move {
+            execute(
+                req,
+                self.pg_conn().deref_mut(),
+                self.redis_conn().deref_mut(),
+            )
+            .await
+        }
+    }
+}
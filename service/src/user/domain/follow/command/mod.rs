@@ -0,0 +1,4 @@
+pub mod block;
+pub mod follow;
+pub mod unblock;
+pub mod unfollow;
@@ -0,0 +1,22 @@
+use crate::user::domain::follow::model::block::Block;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::UnblockReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    let user = User::query_identifier(&req.identifier, conn)?;
+    // a deleted user can still be unblocked
+    Block::unblock(user.id(), req.uid, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_unblock(&self) -> impl Command<pb::UnblockReq> + '_ {
+        move |req: pb::UnblockReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::user::domain::follow::model::follow::Follow;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::UnfollowReq,
+    conn: &mut PgConnection,
+    redis: &mut redis::Connection,
+) -> GrpcResult<EmptyRes> {
+    let user = User::query_identifier(&req.identifier, conn)?;
+    // a deleted user can still be unfollowed
+    Follow::unfollow(user.id(), req.uid, conn, redis)?;
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_unfollow(&self) -> impl Command<pb::UnfollowReq> + '_ {
+        move |req: pb::UnfollowReq| async move {
+            execute(
+                req,
+                self.pg_conn().deref_mut(),
+                self.redis_conn().deref_mut(),
+            )
+            .await
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::user::domain::follow::model::follow::Follow;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{internal, invalid_argument};
+use diesel::prelude::*;
+use migration::{t_user_blocks, t_users};
+
+#[derive(Queryable)]
+pub struct Block {
+    id: i64,
+    blocker: i64,
+    blocked: i64,
+    created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = t_user_blocks)]
+struct NewBlock {
+    blocker: i64,
+    blocked: i64,
+}
+
+impl Block {
+    /// Block the user, follows in both directions are removed.
+    pub(in crate::user::domain) fn block(
+        from: i64,
+        to: i64,
+        conn: &mut PgConnection,
+        redis: &mut redis::Connection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_blocks::dsl::*;
+
+        if from == to {
+            return Err(invalid_argument!("uid", "another user").into());
+        }
+        let removed = conn.transaction::<_, GrpcStatus, _>(|conn| {
+            Self::lock_pair(from, to, conn)?;
+            diesel::insert_into(t_user_blocks)
+                .values(NewBlock {
+                    blocker: from,
+                    blocked: to,
+                })
+                .on_conflict((blocker, blocked))
+                .do_nothing()
+                .execute(conn)
+                .map_err(|e| internal!(format!("Cannot block user({}), err: {}", to, e)))?;
+            Follow::remove_between(from, to, conn)
+        })?;
+        Follow::invalidate_counts(&removed, redis)
+    }
+
+    pub(in crate::user::domain) fn unblock(
+        from: i64,
+        to: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_blocks::dsl::*;
+
+        diesel::delete(
+            t_user_blocks
+                .filter(blocker.eq(from))
+                .filter(blocked.eq(to)),
+        )
+        .execute(conn)
+        .map_err(|e| internal!(format!("Cannot unblock user({}), err: {}", to, e)))?;
+        Ok(())
+    }
+
+    /// Lock both users in the order of ids until the transaction ends, so that blocking
+    /// and following between the same users are serialized.
+    pub(in crate::user::domain) fn lock_pair(
+        a: i64,
+        b: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        t_users::table
+            .select(t_users::id)
+            .filter(t_users::id.eq_any([a, b]))
+            .order(t_users::id)
+            .for_no_key_update()
+            .load::<i64>(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        Ok(())
+    }
+
+    /// Whether either of the users blocks the other.
+    pub(crate) fn between(a: i64, b: i64, conn: &mut PgConnection) -> GrpcResult<bool> {
+        use migration::t_user_blocks::dsl::*;
+
+        let blocking: bool = diesel::select(diesel::dsl::exists(
+            t_user_blocks.filter(
+                blocker
+                    .eq(a)
+                    .and(blocked.eq(b))
+                    .or(blocker.eq(b).and(blocked.eq(a))),
+            ),
+        ))
+        .get_result(conn)
+        .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        Ok(blocking)
+    }
+
+    /// Users blocked by or blocking the user, their content is hidden from the user.
+    pub(crate) fn hidden_from(uid: i64, conn: &mut PgConnection) -> GrpcResult<Vec<i64>> {
+        use migration::t_user_blocks::dsl::*;
+
+        let pairs: Vec<(i64, i64)> = t_user_blocks
+            .select((blocker, blocked))
+            .filter(blocker.eq(uid).or(blocked.eq(uid)))
+            .load(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        Ok(pairs
+            .into_iter()
+            .map(|(a, b)| if a == uid { b } else { a })
+            .collect())
+    }
+}
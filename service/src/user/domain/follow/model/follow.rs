@@ -0,0 +1,265 @@
+use crate::user::domain::follow::model::block::Block;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{internal, invalid_argument};
+use diesel::dsl::not;
+use diesel::prelude::*;
+use migration::{t_user_follows, t_users};
+use redis::Commands;
+use std::collections::HashSet;
+use tonic::Status;
+
+// unit (second)
+const COUNT_EXPIRES: usize = 3600;
+
+#[derive(Queryable)]
+pub struct Follow {
+    id: i64,
+    follower: i64,
+    followee: i64,
+    created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = t_user_follows)]
+struct NewFollow {
+    follower: i64,
+    followee: i64,
+}
+
+#[inline]
+fn followers_key(uid: i64) -> String {
+    format!("user:follow:followers:{}", uid)
+}
+
+#[inline]
+fn following_key(uid: i64) -> String {
+    format!("user:follow:following:{}", uid)
+}
+
+#[inline]
+fn map_err(e: diesel::result::Error) -> Status {
+    internal!(format!("Database connection error: {}", e))
+}
+
+impl Follow {
+    pub(in crate::user::domain) fn follow(
+        from: i64,
+        to: i64,
+        conn: &mut PgConnection,
+        redis: &mut redis::Connection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_follows::dsl::*;
+
+        if from == to {
+            return Err(invalid_argument!("uid", "another user").into());
+        }
+        let inserted = conn.transaction::<_, GrpcStatus, _>(|conn| {
+            // a concurrent block of the users waits for the follow, then removes it
+            Block::lock_pair(from, to, conn)?;
+            if Block::between(from, to, conn)? {
+                return Err(
+                    Status::permission_denied(format!("Cannot follow user({})", to)).into(),
+                );
+            }
+            let inserted = diesel::insert_into(t_user_follows)
+                .values(NewFollow {
+                    follower: from,
+                    followee: to,
+                })
+                .on_conflict((follower, followee))
+                .do_nothing()
+                .execute(conn)
+                .map_err(|e| internal!(format!("Cannot follow user({}), err: {}", to, e)))?;
+            Ok(inserted)
+        })?;
+        if inserted > 0 {
+            Self::invalidate_counts(&[(from, to)], redis)?;
+        }
+        Ok(())
+    }
+
+    pub(in crate::user::domain) fn unfollow(
+        from: i64,
+        to: i64,
+        conn: &mut PgConnection,
+        redis: &mut redis::Connection,
+    ) -> GrpcResult<()> {
+        use migration::t_user_follows::dsl::*;
+
+        let deleted = diesel::delete(
+            t_user_follows
+                .filter(follower.eq(from))
+                .filter(followee.eq(to)),
+        )
+        .execute(conn)
+        .map_err(|e| internal!(format!("Cannot unfollow user({}), err: {}", to, e)))?;
+        if deleted > 0 {
+            Self::invalidate_counts(&[(from, to)], redis)?;
+        }
+        Ok(())
+    }
+
+    /// Remove follows in both directions, returns the removed `(follower, followee)` pairs.
+    pub(in crate::user::domain) fn remove_between(
+        a: i64,
+        b: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Vec<(i64, i64)>> {
+        use migration::t_user_follows::dsl::*;
+
+        let removed: Vec<(i64, i64)> = diesel::delete(
+            t_user_follows.filter(
+                follower
+                    .eq(a)
+                    .and(followee.eq(b))
+                    .or(follower.eq(b).and(followee.eq(a))),
+            ),
+        )
+        .returning((follower, followee))
+        .get_results(conn)
+        .map_err(map_err)?;
+        Ok(removed)
+    }
+
+    /// Returns the page of follower ids (latest first) and the total count,
+    /// deleted users and users in `hidden` are excluded.
+    pub(in crate::user::domain) fn list_followers(
+        uid: i64,
+        hidden: &[i64],
+        limit: i64,
+        offset: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<i64>, i64)> {
+        use migration::t_user_follows::dsl::*;
+
+        let query = || {
+            let alive = t_users::table
+                .select(t_users::id)
+                .filter(t_users::deleted_at.is_null());
+            t_user_follows
+                .filter(followee.eq(uid))
+                .filter(follower.eq_any(alive))
+                .filter(not(follower.eq_any(hidden)))
+        };
+        let total: i64 = query().count().get_result(conn).map_err(map_err)?;
+        let ids: Vec<i64> = query()
+            .select(follower)
+            .order((created_at.desc(), id.desc()))
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+            .map_err(map_err)?;
+        Ok((ids, total))
+    }
+
+    /// Returns the page of followee ids (latest first) and the total count,
+    /// deleted users and users in `hidden` are excluded.
+    pub(in crate::user::domain) fn list_following(
+        uid: i64,
+        hidden: &[i64],
+        limit: i64,
+        offset: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<i64>, i64)> {
+        use migration::t_user_follows::dsl::*;
+
+        let query = || {
+            let alive = t_users::table
+                .select(t_users::id)
+                .filter(t_users::deleted_at.is_null());
+            t_user_follows
+                .filter(follower.eq(uid))
+                .filter(followee.eq_any(alive))
+                .filter(not(followee.eq_any(hidden)))
+        };
+        let total: i64 = query().count().get_result(conn).map_err(map_err)?;
+        let ids: Vec<i64> = query()
+            .select(followee)
+            .order((created_at.desc(), id.desc()))
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+            .map_err(map_err)?;
+        Ok((ids, total))
+    }
+
+    /// Returns the ids among `uids` which are followed by `from`.
+    pub(in crate::user::domain) fn following_among(
+        from: i64,
+        uids: &[i64],
+        conn: &mut PgConnection,
+    ) -> GrpcResult<HashSet<i64>> {
+        use migration::t_user_follows::dsl::*;
+
+        let ids: Vec<i64> = t_user_follows
+            .select(followee)
+            .filter(follower.eq(from))
+            .filter(followee.eq_any(uids))
+            .load(conn)
+            .map_err(map_err)?;
+        Ok(ids.into_iter().collect())
+    }
+
+    /// Returns `(followers, following)` of the user, counts are cached in redis
+    /// and invalidated whenever a follow is changed.
+    pub(in crate::user::domain) fn count(
+        uid: i64,
+        conn: &mut PgConnection,
+        redis: &mut redis::Connection,
+    ) -> GrpcResult<(i64, i64)> {
+        use migration::t_user_follows::dsl::*;
+
+        let followers = Self::cached_count(&followers_key(uid), redis, || {
+            t_user_follows
+                .filter(followee.eq(uid))
+                .count()
+                .get_result(conn)
+                .map_err(map_err)
+        })?;
+        let following = Self::cached_count(&following_key(uid), redis, || {
+            t_user_follows
+                .filter(follower.eq(uid))
+                .count()
+                .get_result(conn)
+                .map_err(map_err)
+        })?;
+        Ok((followers, following))
+    }
+
+    #[inline]
+    fn cached_count(
+        key: &str,
+        redis: &mut redis::Connection,
+        count: impl FnOnce() -> Result<i64, Status>,
+    ) -> GrpcResult<i64> {
+        let cached: Option<i64> = redis
+            .get(key)
+            .map_err(|e| internal!(format!("Redis error, cannot get key {}, err: {}", key, e)))?;
+        if let Some(cached) = cached {
+            return Ok(cached);
+        }
+        let value = count()?;
+        let _: () = redis
+            .set_ex(key, value, COUNT_EXPIRES)
+            .map_err(|e| internal!(format!("Redis failed to set key {}, err: {}", key, e)))?;
+        Ok(value)
+    }
+
+    pub(in crate::user::domain) fn invalidate_counts(
+        pairs: &[(i64, i64)],
+        redis: &mut redis::Connection,
+    ) -> GrpcResult<()> {
+        if pairs.is_empty() {
+            return Ok(());
+        }
+        let keys: Vec<String> = pairs
+            .iter()
+            .flat_map(|(from, to)| [following_key(*from), followers_key(*to)])
+            .collect();
+        let _: () = redis
+            .del(&keys)
+            .map_err(|_| internal!(format!("Failed to del keys {:?}", keys)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::user::domain::follow::model::follow::Follow;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::CountFollowsReq,
+    conn: &mut PgConnection,
+    redis: &mut redis::Connection,
+) -> GrpcResult<pb::CountFollowsRes> {
+    let user = User::query_alive(req.uid, conn)?;
+    let (followers, following) = Follow::count(user.id(), conn, redis)?;
+    Ok(pb::CountFollowsRes {
+        followers,
+        following,
+    })
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_count_follows(&self) -> impl Query<pb::CountFollowsReq> + '_ {
+        move |req: pb::CountFollowsReq| async move {
+            execute(
+                req,
+                self.pg_conn().deref_mut(),
+                self.redis_conn().deref_mut(),
+            )
+            .await
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::user::domain::follow::model::follow::Follow;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::invalid_argument;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+const MAX_UIDS: usize = 100;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::IsFollowingReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::IsFollowingRes> {
+    if req.uids.len() > MAX_UIDS {
+        return Err(invalid_argument!("uids", "no more than 100 ids").into());
+    }
+    let user = User::query_identifier(&req.identifier, conn)?;
+    let following = Follow::following_among(user.id(), &req.uids, conn)?;
+    Ok(pb::IsFollowingRes {
+        following: req
+            .uids
+            .iter()
+            .map(|uid| (*uid, following.contains(uid)))
+            .collect(),
+    })
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_is_following(&self) -> impl Query<pb::IsFollowingReq> + '_ {
+        move |req: pb::IsFollowingReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::user::domain::follow::model::block::Block;
+use crate::user::domain::follow::model::follow::Follow;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListFollowsReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListFollowsRes> {
+    let user = User::query_alive(req.uid, conn)?;
+    let hidden = match &req.viewer {
+        Some(viewer) => Block::hidden_from(User::query_identifier(viewer, conn)?.id(), conn)?,
+        None => vec![],
+    };
+    let (limit, offset) = req
+        .slice
+        .unwrap_or_default()
+        .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+    let (ids, total) = Follow::list_followers(user.id(), &hidden, limit, offset, conn)?;
+    let users = User::query_ids(&ids, conn)?;
+    Ok(pb::ListFollowsRes {
+        users: users.iter().map(User::public_profile).collect(),
+        total,
+    })
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_list_followers(&self) -> impl Query<pb::ListFollowsReq> + '_ {
+        move |req: pb::ListFollowsReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
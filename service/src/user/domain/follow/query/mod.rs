@@ -0,0 +1,4 @@
+pub mod count_follows;
+pub mod is_following;
+pub mod list_followers;
+pub mod list_following;
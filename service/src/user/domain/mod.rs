@@ -1 +1,2 @@
+pub mod follow;
 pub mod user;
@@ -0,0 +1,44 @@
+use crate::user::domain::user::model::admin::Admin;
+use crate::user::rpc::UserResolver;
+use chrono::NaiveDateTime;
+use common::invalid_argument;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::auth::token::v1::token_service_client::TokenServiceClient;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+use tonic::transport::Channel;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::BanUserReq,
+    conn: &mut PgConnection,
+    client: TokenServiceClient<Channel>,
+) -> GrpcResult<EmptyRes> {
+    let admin = Admin::authorize(req.admin_id, conn)?;
+    let reason = req.reason.trim();
+    if reason.is_empty() || reason.chars().count() > 512 {
+        return Err(invalid_argument!("reason", "1 to 512 characters").into());
+    }
+    let expires_at = match req.expires_at {
+        Some(secs) => Some(
+            NaiveDateTime::from_timestamp_opt(secs, 0)
+                .filter(|t| *t > chrono::Local::now().naive_local())
+                .ok_or_else(|| invalid_argument!("expires_at", "a future unix timestamp"))?,
+        ),
+        None => None,
+    };
+    admin.ban(req.uid, reason, expires_at, conn)?;
+    // tokens which have been signed are revoked, so that the ban takes effect at once
+    admin.force_logout(req.uid, client).await?;
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_ban_user(&self) -> impl Command<pb::BanUserReq> + '_ {
+        move |req: pb::BanUserReq| async move {
+            execute(req, self.pg_conn().deref_mut(), self.token_client()).await
+        }
+    }
+}
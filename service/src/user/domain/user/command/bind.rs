@@ -1,4 +1,4 @@
-use crate::user::domain::user::model::user::User;
+use crate::user::domain::user::model::user::{IdentifierKind, User};
 use crate::user::rpc::UserResolver;
 use common::{infra::Command, status::prelude::*};
 use diesel::PgConnection;
@@ -9,7 +9,8 @@ use std::ops::DerefMut;
 #[tracing::instrument(skip_all, err)]
 async fn execute(req: pb::BindReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
     // todo check req
-    let mut user = User::query_identifier(&req.identifier, conn)?;
+    let kind = IdentifierKind::parse(&req.identifier, req.identifier_type)?;
+    let mut user = User::query_kind(&req.identifier, &kind, conn)?;
     if req.email.is_some() || req.phone.is_some() {
         user.bind(req.email, req.phone, conn)?;
     }
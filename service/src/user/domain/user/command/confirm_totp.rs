@@ -0,0 +1,31 @@
+use crate::user::domain::user::model::totp::Totp;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use aes_gcm::Aes256Gcm;
+use common::not_found;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ConfirmTotpReq,
+    cipher: &Aes256Gcm,
+    conn: &mut PgConnection,
+) -> GrpcResult<EmptyRes> {
+    let user = User::query_identifier(&req.identifier, conn)?;
+    let mut totp = Totp::query_uid(user.id(), conn)?
+        .ok_or_else(|| not_found!(format!("totp of user({})", user.id())))?;
+    totp.confirm(&req.code, cipher, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_confirm_totp(&self) -> impl Command<pb::ConfirmTotpReq> + '_ {
+        move |req: pb::ConfirmTotpReq| async move {
+            execute(req, self.totp_cipher(), self.pg_conn().deref_mut()).await
+        }
+    }
+}
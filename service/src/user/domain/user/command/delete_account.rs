@@ -0,0 +1,47 @@
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::invalid_argument;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::auth::token::v1::token_service_client::TokenServiceClient;
+use proto::pb::auth::token::v1::RevokeTokenReq;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+use tonic::transport::Channel;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::DeleteAccountReq,
+    secret: &str,
+    conn: &mut PgConnection,
+    mut client: TokenServiceClient<Channel>,
+) -> GrpcResult<EmptyRes> {
+    let mut user = User::query_identifier(&req.identifier, conn)?;
+    if !user.check_password(&req.password, secret)? {
+        return Err(invalid_argument!("password", "correct password").into());
+    }
+    user.delete_account(conn)?;
+    // sign out all sessions, tokens which have been signed are revoked
+    client
+        .revoke_token(RevokeTokenReq {
+            sub: user.id().to_string(),
+        })
+        .await?;
+    tracing::info!("User({}) deleted the account", user.id());
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_delete_account(&self) -> impl Command<pb::DeleteAccountReq> + '_ {
+        move |req: pb::DeleteAccountReq| async move {
+            execute(
+                req,
+                self.hash_secret(),
+                self.pg_conn().deref_mut(),
+                self.token_client(),
+            )
+            .await
+        }
+    }
+}
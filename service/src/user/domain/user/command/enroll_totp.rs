@@ -0,0 +1,38 @@
+use crate::user::domain::user::model::totp::Totp;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use aes_gcm::Aes256Gcm;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::EnrollTotpReq,
+    cipher: &Aes256Gcm,
+    secret: &str,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::EnrollTotpRes> {
+    let user = User::query_identifier(&req.identifier, conn)?;
+    let enrollment = Totp::enroll(user.id(), cipher, secret, conn)?;
+    Ok(pb::EnrollTotpRes {
+        otpauth_uri: enrollment.otpauth_uri(user.username()),
+        secret: enrollment.secret,
+        recovery_codes: enrollment.recovery_codes,
+    })
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_enroll_totp(&self) -> impl Command<pb::EnrollTotpReq> + '_ {
+        move |req: pb::EnrollTotpReq| async move {
+            execute(
+                req,
+                self.totp_cipher(),
+                self.hash_secret(),
+                self.pg_conn().deref_mut(),
+            )
+            .await
+        }
+    }
+}
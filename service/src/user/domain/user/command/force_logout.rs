@@ -0,0 +1,28 @@
+use crate::user::domain::user::model::admin::Admin;
+use crate::user::rpc::UserResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::auth::token::v1::token_service_client::TokenServiceClient;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+use tonic::transport::Channel;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ForceLogoutReq,
+    conn: &mut PgConnection,
+    client: TokenServiceClient<Channel>,
+) -> GrpcResult<EmptyRes> {
+    let admin = Admin::authorize(req.admin_id, conn)?;
+    admin.force_logout(req.uid, client).await?;
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_force_logout(&self) -> impl Command<pb::ForceLogoutReq> + '_ {
+        move |req: pb::ForceLogoutReq| async move {
+            execute(req, self.pg_conn().deref_mut(), self.token_client()).await
+        }
+    }
+}
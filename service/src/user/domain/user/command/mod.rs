@@ -1,2 +1,12 @@
+pub mod ban_user;
 pub mod bind;
+pub mod confirm_totp;
+pub mod delete_account;
+pub mod enroll_totp;
+pub mod force_logout;
 pub mod register;
+pub mod set_role_group;
+pub mod unban_user;
+pub mod unlock;
+pub mod update_profile;
+pub mod verify_totp;
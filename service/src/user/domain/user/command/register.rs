@@ -1,4 +1,5 @@
 use crate::user::domain::user::model::user::User;
+use crate::user::domain::user::model::validation::{violations_status, RegisterValidator};
 use crate::user::rpc::{RoleGroup, UserResolver};
 use common::{infra::Command, status::prelude::*};
 use diesel::PgConnection;
@@ -10,9 +11,13 @@ use std::ops::DerefMut;
 async fn execute(
     req: pb::RegisterReq,
     secret: &str,
+    validator: &RegisterValidator,
     conn: &mut PgConnection,
 ) -> GrpcResult<EmptyRes> {
-    // TODO check req parameters.
+    let violations = validator.validate(&req.username, &req.password);
+    if !violations.is_empty() {
+        return Err(violations_status(&violations).into());
+    }
 
     User::register(&req.username, &req.password, secret, RoleGroup::USER, conn)?;
     Ok(EmptyRes {})
@@ -21,7 +26,13 @@ async fn execute(
 impl UserResolver {
     pub(in crate::user) fn create_register(&self) -> impl Command<pb::RegisterReq> + '_ {
         move |req: pb::RegisterReq| async move {
-            execute(req, self.hash_secret(), self.pg_conn().deref_mut()).await
+            execute(
+                req,
+                self.hash_secret(),
+                self.register_validator(),
+                self.pg_conn().deref_mut(),
+            )
+            .await
         }
     }
 }
@@ -0,0 +1,23 @@
+use crate::user::domain::user::model::admin::Admin;
+use crate::user::rpc::{RoleGroup, UserResolver};
+use common::invalid_argument;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::SetRoleGroupReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    let admin = Admin::authorize(req.admin_id, conn)?;
+    let rg = RoleGroup::from_name(&req.role_group)
+        .ok_or_else(|| invalid_argument!("role_group", "user or admin"))?;
+    admin.set_role_group(req.uid, rg, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_set_role_group(&self) -> impl Command<pb::SetRoleGroupReq> + '_ {
+        move |req: pb::SetRoleGroupReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
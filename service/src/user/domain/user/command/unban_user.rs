@@ -0,0 +1,20 @@
+use crate::user::domain::user::model::admin::Admin;
+use crate::user::rpc::UserResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::UnbanUserReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    let admin = Admin::authorize(req.admin_id, conn)?;
+    admin.unban(req.uid, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_unban_user(&self) -> impl Command<pb::UnbanUserReq> + '_ {
+        move |req: pb::UnbanUserReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
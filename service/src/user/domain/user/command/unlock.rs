@@ -0,0 +1,35 @@
+use crate::user::domain::user::model::admin::Admin;
+use crate::user::domain::user::model::attempt::LoginGuard;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::UnlockReq,
+    guard: LoginGuard<'_>,
+    conn: &mut PgConnection,
+) -> GrpcResult<EmptyRes> {
+    let admin = Admin::authorize(req.admin_id, conn)?;
+    let user = User::query_identifier(&req.identifier, conn)?;
+    guard.unlock(&user.account())?;
+    tracing::info!(admin = admin.id(), uid = user.id(), "Admin unlocked user");
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_unlock(&self) -> impl Command<pb::UnlockReq> + '_ {
+        move |req: pb::UnlockReq| async move {
+            execute(
+                req,
+                LoginGuard::new(self.attempt_store(), self.login_guard_conf()),
+                self.pg_conn().deref_mut(),
+            )
+            .await
+        }
+    }
+}
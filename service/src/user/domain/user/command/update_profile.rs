@@ -0,0 +1,24 @@
+use crate::user::domain::user::model::ban::Ban;
+use crate::user::domain::user::model::profile::PutProfile;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Command, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::UpdateProfileReq, conn: &mut PgConnection) -> GrpcResult<EmptyRes> {
+    let user = User::query_identifier(&req.identifier, conn)?;
+    Ban::check(user.id(), conn)?;
+    let put = PutProfile::parse(req)?;
+    user.update_profile(put, conn)?;
+    Ok(EmptyRes {})
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_update_profile(&self) -> impl Command<pb::UpdateProfileReq> + '_ {
+        move |req: pb::UpdateProfileReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::user::domain::user::model::attempt::LoginGuard;
+use crate::user::domain::user::model::ban::Ban;
+use crate::user::domain::user::model::totp::{PendingLogin, Totp};
+use crate::user::domain::user::model::user::{User, UserId};
+use crate::user::rpc::UserResolver;
+use aes_gcm::Aes256Gcm;
+use common::{infra::Command, status::prelude::*};
+use common::{invalid_argument, not_found};
+use diesel::PgConnection;
+use proto::pb::auth::token::v1::token_service_client::TokenServiceClient;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+use tonic::transport::Channel;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::VerifyTotpReq,
+    cipher: &Aes256Gcm,
+    secret: &str,
+    guard: LoginGuard<'_>,
+    conn: &mut PgConnection,
+    redis: &mut redis::Connection,
+    client: TokenServiceClient<Channel>,
+) -> GrpcResult<pb::LoginRes> {
+    let PendingLogin { uid, attempt } = Totp::peek_challenge(&req.challenge, redis)?;
+    let user = User::query_id(UserId::from(uid as u64), conn)?;
+    // failures of the second factor are counted with the ones of the password, so that
+    // issuing new challenges does not allow guessing the code without limit
+    let account = user.account();
+    guard.check(&account, attempt.ip())?;
+    let mut totp =
+        Totp::query_uid(uid, conn)?.ok_or_else(|| not_found!(format!("totp of user({})", uid)))?;
+    let passed = totp.verify(&req.code, cipher, conn)?
+        || Totp::use_recovery_code(uid, &req.code, secret, conn)?;
+    if !passed {
+        Totp::fail_challenge(&req.challenge, redis)?;
+        guard.failed(&account, attempt.ip())?;
+        attempt.record(uid, false, conn)?;
+        return Err(invalid_argument!("code", "valid TOTP code or recovery code").into());
+    }
+    Totp::clear_challenge(&req.challenge, redis)?;
+    // the user might be banned after the challenge is issued
+    if let Some(ban) = Ban::active(uid, conn)? {
+        return Err(ban.status().into());
+    }
+    // the login succeeds only now, so that the password alone neither resets the failures
+    // nor makes the device known
+    guard.succeeded(&account)?;
+    if attempt.is_new_device(uid, conn)? {
+        attempt.notify_new_device(uid, redis);
+    }
+    attempt.record(uid, true, conn)?;
+    user.sign_token_pair(client).await
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_verify_totp(&self) -> impl Command<pb::VerifyTotpReq> + '_ {
+        move |req: pb::VerifyTotpReq| async move {
+            execute(
+                req,
+                self.totp_cipher(),
+                self.hash_secret(),
+                LoginGuard::new(self.attempt_store(), self.login_guard_conf()),
+                self.pg_conn().deref_mut(),
+                self.redis_conn().deref_mut(),
+                self.token_client(),
+            )
+            .await
+        }
+    }
+}
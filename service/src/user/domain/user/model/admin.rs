@@ -0,0 +1,240 @@
+use crate::user::domain::user::model::ban::Ban;
+use crate::user::domain::user::model::user::{map_not_found, User, UserId};
+use crate::user::rpc::RoleGroup;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{internal, invalid_argument, not_found};
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use migration::{t_users, t_users_bans};
+use proto::pb::auth::token::v1::token_service_client::TokenServiceClient;
+use proto::pb::auth::token::v1::RevokeTokenReq;
+use proto::pb::user::sys::v1 as pb;
+use std::collections::HashMap;
+use tonic::transport::Channel;
+use tonic::Status;
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+#[inline]
+fn parse_timestamp(field: &'static str, secs: Option<i64>) -> GrpcResult<Option<NaiveDateTime>> {
+    secs.map(|secs| {
+        NaiveDateTime::from_timestamp_opt(secs, 0)
+            .ok_or_else(|| invalid_argument!(field, "unix timestamp (second)").into())
+    })
+    .transpose()
+}
+
+pub struct UserFilter {
+    role_group: Option<String>,
+    created_start: Option<NaiveDateTime>,
+    created_end: Option<NaiveDateTime>,
+    last_login_start: Option<NaiveDateTime>,
+    last_login_end: Option<NaiveDateTime>,
+    banned: Option<bool>,
+    limit: i64,
+    offset: i64,
+}
+
+impl UserFilter {
+    pub(in crate::user::domain) fn parse(req: pb::ListUsersReq) -> GrpcResult<UserFilter> {
+        if let Some(rg) = &req.role_group {
+            if RoleGroup::from_name(rg).is_none() {
+                return Err(invalid_argument!("role_group", "user or admin").into());
+            }
+        }
+        let (limit, offset) = req
+            .slice
+            .unwrap_or_default()
+            .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+        Ok(UserFilter {
+            role_group: req.role_group,
+            created_start: parse_timestamp("created_start", req.created_start)?,
+            created_end: parse_timestamp("created_end", req.created_end)?,
+            last_login_start: parse_timestamp("last_login_start", req.last_login_start)?,
+            last_login_end: parse_timestamp("last_login_end", req.last_login_end)?,
+            banned: req.banned,
+            limit,
+            offset,
+        })
+    }
+
+    fn query(&self) -> t_users::BoxedQuery<'_, Pg> {
+        use migration::t_users::dsl::*;
+
+        let mut query = t_users.into_boxed();
+        if let Some(rg) = &self.role_group {
+            query = query.filter(role_group.eq(rg));
+        }
+        if let Some(start) = self.created_start {
+            query = query.filter(created_at.ge(start));
+        }
+        if let Some(end) = self.created_end {
+            query = query.filter(created_at.le(end));
+        }
+        if let Some(start) = self.last_login_start {
+            query = query.filter(last_login.ge(start));
+        }
+        if let Some(end) = self.last_login_end {
+            query = query.filter(last_login.le(end));
+        }
+        if let Some(banned) = self.banned {
+            let now = chrono::Local::now().naive_local();
+            let banned_ids = t_users_bans::table.select(t_users_bans::uid).filter(
+                t_users_bans::expires_at
+                    .is_null()
+                    .or(t_users_bans::expires_at.gt(now)),
+            );
+            query = if banned {
+                query.filter(id.eq_any(banned_ids))
+            } else {
+                query.filter(diesel::dsl::not(id.eq_any(banned_ids)))
+            };
+        }
+        query
+    }
+}
+
+/// An user whose role group is admin, every action is logged with the id of the admin.
+pub struct Admin {
+    id: i64,
+}
+
+impl Admin {
+    /// The role group is checked against the database rather than the token,
+    /// so that a demoted, banned or deleted admin loses the permission immediately.
+    /// Shared by the admin actions of other domains, i.e. moderating reviews.
+    pub(crate) fn authorize(admin_id: i64, conn: &mut PgConnection) -> GrpcResult<Admin> {
+        let denied = || Status::permission_denied(format!("User({}) is not an admin", admin_id));
+        let user = User::query_alive(admin_id, conn).map_err(|_| denied())?;
+        if user.role_group() != RoleGroup::ADMIN.name() || Ban::active(admin_id, conn)?.is_some() {
+            return Err(denied().into());
+        }
+        Ok(Admin { id: admin_id })
+    }
+
+    pub(in crate::user::domain) fn id(&self) -> i64 {
+        self.id
+    }
+
+    // an admin cannot ban or demote itself, there would be no admin to undo it
+    #[inline]
+    fn check_other(&self, uid: i64) -> GrpcResult<()> {
+        if uid == self.id {
+            return Err(Status::failed_precondition("Admin cannot operate on itself").into());
+        }
+        Ok(())
+    }
+
+    pub(in crate::user::domain) fn list_users(
+        &self,
+        filter: &UserFilter,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<pb::ListUsersRes> {
+        use migration::t_users::dsl::*;
+
+        let total: i64 = filter
+            .query()
+            .count()
+            .get_result(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        let users: Vec<User> = filter
+            .query()
+            .order(id.asc())
+            .limit(filter.limit)
+            .offset(filter.offset)
+            .load(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        let mut bans: HashMap<i64, Ban> = t_users_bans::table
+            .filter(t_users_bans::uid.eq_any(users.iter().map(User::id).collect::<Vec<_>>()))
+            .load::<Ban>(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?
+            .into_iter()
+            .filter(Ban::is_active)
+            .map(|ban| (ban.uid(), ban))
+            .collect();
+        tracing::info!(admin = self.id, total, "Admin listed users");
+        Ok(pb::ListUsersRes {
+            users: users
+                .iter()
+                .map(|user| pb::UserDetail {
+                    me: Some(user.private_profile()),
+                    ban: bans.remove(&user.id()).as_ref().map(Ban::to_pb),
+                })
+                .collect(),
+            total,
+        })
+    }
+
+    pub(in crate::user::domain) fn set_role_group(
+        &self,
+        uid: i64,
+        rg: RoleGroup,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_users::dsl::*;
+
+        self.check_other(uid)?;
+        let updated = diesel::update(t_users.find(uid))
+            .set(role_group.eq(rg.name()))
+            .execute(conn)
+            .map_err(map_not_found("user", uid))?;
+        if updated == 0 {
+            return Err(not_found!(format!("user({})", uid)).into());
+        }
+        tracing::info!(
+            admin = self.id,
+            uid,
+            "Admin set role group to {}",
+            rg.name()
+        );
+        Ok(())
+    }
+
+    pub(in crate::user::domain) fn ban(
+        &self,
+        uid: i64,
+        reason: &str,
+        expires_at: Option<NaiveDateTime>,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        self.check_other(uid)?;
+        // ensure the user exists
+        User::query_id(UserId::from(uid as u64), conn)?;
+        Ban::ban(uid, self.id, reason, expires_at, conn)?;
+        tracing::info!(
+            admin = self.id,
+            uid,
+            "Admin banned user until {:?}, reason: {}",
+            expires_at,
+            reason
+        );
+        Ok(())
+    }
+
+    pub(in crate::user::domain) fn unban(
+        &self,
+        uid: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        Ban::unban(uid, conn)?;
+        tracing::info!(admin = self.id, uid, "Admin unbanned user");
+        Ok(())
+    }
+
+    /// Revoke all tokens of the user, the user has to login again.
+    pub(in crate::user::domain) async fn force_logout(
+        &self,
+        uid: i64,
+        mut client: TokenServiceClient<Channel>,
+    ) -> GrpcResult<()> {
+        client
+            .revoke_token(RevokeTokenReq {
+                sub: uid.to_string(),
+            })
+            .await?;
+        tracing::info!(admin = self.id, uid, "Admin forced user to logout");
+        Ok(())
+    }
+}
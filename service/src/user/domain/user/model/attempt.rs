@@ -0,0 +1,349 @@
+use crate::user::rpc::LoginGuardConfig;
+use common::internal;
+use common::status::prelude::*;
+use parking_lot::Mutex;
+use redis::Commands;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tonic::Status;
+
+#[derive(Default, Clone, Copy, Debug)]
+pub struct Attempts {
+    pub failures: u64,
+    // unix timestamp (second)
+    pub last_failure: u64,
+}
+
+/// Storage of failed login attempts, keys are expired after the window
+/// so that a counter resets itself if nothing happens.
+pub trait AttemptStore: Send + Sync {
+    fn attempts(&self, key: &str) -> GrpcResult<Attempts>;
+
+    fn record_failure(&self, key: &str, window: u64) -> GrpcResult<Attempts>;
+
+    fn lock(&self, key: &str, secs: u64) -> GrpcResult<()>;
+
+    // remaining seconds of a lock
+    fn locked(&self, key: &str) -> GrpcResult<Option<u64>>;
+
+    fn clear(&self, key: &str) -> GrpcResult<()>;
+}
+
+pub struct RedisAttemptStore {
+    pool: r2d2::Pool<redis::Client>,
+}
+
+impl RedisAttemptStore {
+    pub fn new(pool: r2d2::Pool<redis::Client>) -> Self {
+        Self { pool }
+    }
+
+    fn conn(&self) -> r2d2::PooledConnection<redis::Client> {
+        self.pool
+            .get()
+            .expect("Cannot get redis connection from r2d2 pool")
+    }
+}
+
+impl AttemptStore for RedisAttemptStore {
+    fn attempts(&self, key: &str) -> GrpcResult<Attempts> {
+        let (failures, last_failure): (Option<u64>, Option<u64>) = self
+            .conn()
+            .hget(key, &["failures", "last_failure"])
+            .map_err(|e| internal!(format!("Redis error, cannot get key {}, err: {}", key, e)))?;
+        Ok(Attempts {
+            failures: failures.unwrap_or_default(),
+            last_failure: last_failure.unwrap_or_default(),
+        })
+    }
+
+    fn record_failure(&self, key: &str, window: u64) -> GrpcResult<Attempts> {
+        let now = jsonwebtoken::get_current_timestamp();
+        let (failures, _, _): (u64, (), bool) = redis::pipe()
+            .atomic()
+            .hincr(key, "failures", 1)
+            .hset(key, "last_failure", now)
+            .expire(key, window as usize)
+            .query(&mut *self.conn())
+            .map_err(|e| internal!(format!("Redis failed to incr key {}, err: {}", key, e)))?;
+        Ok(Attempts {
+            failures,
+            last_failure: now,
+        })
+    }
+
+    fn lock(&self, key: &str, secs: u64) -> GrpcResult<()> {
+        let lock_key = format!("{}:lock", key);
+        let _: () = self
+            .conn()
+            .set_ex(&lock_key, 1, secs as usize)
+            .map_err(|e| internal!(format!("Redis failed to set key {}, err: {}", lock_key, e)))?;
+        Ok(())
+    }
+
+    fn locked(&self, key: &str) -> GrpcResult<Option<u64>> {
+        let lock_key = format!("{}:lock", key);
+        // -2 if the key does not exist
+        let ttl: i64 = self.conn().ttl(&lock_key).map_err(|e| {
+            internal!(format!(
+                "Redis error, cannot get key {}, err: {}",
+                lock_key, e
+            ))
+        })?;
+        Ok((ttl > 0).then_some(ttl as u64))
+    }
+
+    fn clear(&self, key: &str) -> GrpcResult<()> {
+        let lock_key = format!("{}:lock", key);
+        let _: () = self
+            .conn()
+            .del(&[key, lock_key.as_str()])
+            .map_err(|_| internal!(format!("Failed to del key {}", key)))?;
+        Ok(())
+    }
+}
+
+/// In-process store, used for a single instance deployment or local development.
+#[derive(Default)]
+pub struct MemoryAttemptStore {
+    attempts: Mutex<HashMap<String, (Attempts, Instant)>>,
+    locks: Mutex<HashMap<String, Instant>>,
+}
+
+impl AttemptStore for MemoryAttemptStore {
+    fn attempts(&self, key: &str) -> GrpcResult<Attempts> {
+        let mut attempts = self.attempts.lock();
+        match attempts.get(key) {
+            Some((_, expire_at)) if *expire_at <= Instant::now() => {
+                attempts.remove(key);
+                Ok(Attempts::default())
+            }
+            Some((attempt, _)) => Ok(*attempt),
+            None => Ok(Attempts::default()),
+        }
+    }
+
+    fn record_failure(&self, key: &str, window: u64) -> GrpcResult<Attempts> {
+        let now = Instant::now();
+        let mut attempts = self.attempts.lock();
+        // evict expired counters lazily, keeps memory bounded by the window
+        attempts.retain(|_, (_, expire_at)| *expire_at > now);
+        let (attempt, expire_at) = attempts
+            .entry(key.to_string())
+            .or_insert_with(|| (Attempts::default(), now));
+        attempt.failures += 1;
+        attempt.last_failure = jsonwebtoken::get_current_timestamp();
+        *expire_at = now + Duration::from_secs(window);
+        Ok(*attempt)
+    }
+
+    fn lock(&self, key: &str, secs: u64) -> GrpcResult<()> {
+        self.locks
+            .lock()
+            .insert(key.to_string(), Instant::now() + Duration::from_secs(secs));
+        Ok(())
+    }
+
+    fn locked(&self, key: &str) -> GrpcResult<Option<u64>> {
+        let mut locks = self.locks.lock();
+        let now = Instant::now();
+        match locks.get(key) {
+            Some(expire_at) if *expire_at > now => Ok(Some((*expire_at - now).as_secs().max(1))),
+            Some(_) => {
+                locks.remove(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn clear(&self, key: &str) -> GrpcResult<()> {
+        self.attempts.lock().remove(key);
+        self.locks.lock().remove(key);
+        Ok(())
+    }
+}
+
+#[inline]
+fn account_key(account: &str) -> String {
+    format!("user:login:failures:account:{}", account)
+}
+
+#[inline]
+fn ip_key(ip: &str) -> String {
+    format!("user:login:failures:ip:{}", ip)
+}
+
+#[inline]
+fn too_many_attempts(secs: u64) -> Status {
+    Status::resource_exhausted(format!(
+        "Too many failed login attempts, retry after {}s",
+        secs
+    ))
+}
+
+/// Throttle login attempts per account and per client ip.
+///
+/// An account is the user an identifier resolves to, or the canonical identifier if it
+/// resolves to nobody, so that spellings of an identifier or other identifiers of the user
+/// share one counter.
+///
+/// Each failure of an account doubles the delay before the next attempt is accepted, the
+/// account is locked once the failures reach `max_failures`, a client ip is locked once its
+/// failures reach `ip_max_failures`.
+pub struct LoginGuard<'a> {
+    store: &'a dyn AttemptStore,
+    conf: &'a LoginGuardConfig,
+}
+
+impl<'a> LoginGuard<'a> {
+    pub(in crate::user::domain) fn new(
+        store: &'a dyn AttemptStore,
+        conf: &'a LoginGuardConfig,
+    ) -> Self {
+        Self { store, conf }
+    }
+
+    fn delay(&self, failures: u64) -> u64 {
+        if failures == 0 {
+            return 0;
+        }
+        let exp = (failures - 1).min(63) as u32;
+        self.conf
+            .base_delay
+            .saturating_mul(2u64.saturating_pow(exp))
+            .min(self.conf.max_delay)
+    }
+
+    pub(in crate::user::domain) fn check(&self, account: &str, ip: Option<&str>) -> GrpcResult<()> {
+        if let Some(ip) = ip {
+            if let Some(secs) = self.store.locked(&ip_key(ip))? {
+                return Err(too_many_attempts(secs).into());
+            }
+        }
+        let key = account_key(account);
+        if let Some(secs) = self.store.locked(&key)? {
+            return Err(too_many_attempts(secs).into());
+        }
+        let attempts = self.store.attempts(&key)?;
+        let retry_at = attempts.last_failure + self.delay(attempts.failures);
+        let now = jsonwebtoken::get_current_timestamp();
+        if retry_at > now {
+            return Err(too_many_attempts(retry_at - now).into());
+        }
+        Ok(())
+    }
+
+    pub(in crate::user::domain) fn failed(
+        &self,
+        account: &str,
+        ip: Option<&str>,
+    ) -> GrpcResult<()> {
+        if let Some(ip) = ip {
+            let key = ip_key(ip);
+            let attempts = self.store.record_failure(&key, self.conf.window)?;
+            if attempts.failures >= self.conf.ip_max_failures {
+                self.store.lock(&key, self.conf.lock)?;
+            }
+        }
+        let key = account_key(account);
+        let attempts = self.store.record_failure(&key, self.conf.window)?;
+        if attempts.failures >= self.conf.max_failures {
+            tracing::warn!(
+                "Account({}) is locked after {} failures",
+                account,
+                attempts.failures
+            );
+            self.store.lock(&key, self.conf.lock)?;
+        }
+        Ok(())
+    }
+
+    pub(in crate::user::domain) fn succeeded(&self, account: &str) -> GrpcResult<()> {
+        self.store.clear(&account_key(account))
+    }
+
+    pub(in crate::user::domain) fn unlock(&self, account: &str) -> GrpcResult<()> {
+        self.store.clear(&account_key(account))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conf() -> LoginGuardConfig {
+        LoginGuardConfig {
+            max_failures: 3,
+            ip_max_failures: 5,
+            base_delay: 0,
+            ..LoginGuardConfig::default()
+        }
+    }
+
+    #[test]
+    fn lock_account_after_failures() {
+        let store = MemoryAttemptStore::default();
+        let conf = conf();
+        let guard = LoginGuard::new(&store, &conf);
+        for _ in 0..2 {
+            guard.failed("id:42", None).unwrap();
+            guard.check("id:42", None).unwrap();
+        }
+        guard.failed("id:42", None).unwrap();
+        let err = guard.check("id:42", None).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+        // other accounts are not affected
+        guard.check("id:43", None).unwrap();
+
+        guard.unlock("id:42").unwrap();
+        guard.check("id:42", None).unwrap();
+    }
+
+    #[test]
+    fn succeeded_resets_failures() {
+        let store = MemoryAttemptStore::default();
+        let conf = conf();
+        let guard = LoginGuard::new(&store, &conf);
+        for _ in 0..2 {
+            guard.failed("id:42", None).unwrap();
+        }
+        guard.succeeded("id:42").unwrap();
+        for _ in 0..2 {
+            guard.failed("id:42", None).unwrap();
+        }
+        guard.check("id:42", None).unwrap();
+    }
+
+    #[test]
+    fn lock_ip_after_failures() {
+        let store = MemoryAttemptStore::default();
+        let conf = conf();
+        let guard = LoginGuard::new(&store, &conf);
+        for i in 0..5 {
+            guard
+                .failed(&format!("id:{}", i), Some("10.0.0.1"))
+                .unwrap();
+        }
+        let err = guard.check("id:42", Some("10.0.0.1")).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+        guard.check("id:42", Some("10.0.0.2")).unwrap();
+    }
+
+    #[test]
+    fn delay_doubles() {
+        let store = MemoryAttemptStore::default();
+        let conf = LoginGuardConfig {
+            base_delay: 2,
+            max_delay: 10,
+            ..LoginGuardConfig::default()
+        };
+        let guard = LoginGuard::new(&store, &conf);
+        assert_eq!(
+            (0..6)
+                .map(|failures| guard.delay(failures))
+                .collect::<Vec<_>>(),
+            [0, 2, 4, 8, 10, 10]
+        );
+    }
+}
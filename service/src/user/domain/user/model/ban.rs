@@ -0,0 +1,117 @@
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{internal, not_found};
+use diesel::prelude::*;
+use migration::t_users_bans;
+use proto::pb::user::sys::v1 as pb;
+use tonic::Status;
+
+#[derive(Queryable, Clone)]
+pub struct Ban {
+    id: i64,
+    uid: i64,
+    reason: String,
+    banned_by: i64,
+    expires_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = t_users_bans)]
+#[diesel(treat_none_as_null = true)]
+struct NewBan<'a> {
+    uid: i64,
+    reason: &'a str,
+    banned_by: i64,
+    expires_at: Option<NaiveDateTime>,
+}
+
+impl Ban {
+    pub(in crate::user::domain) fn uid(&self) -> i64 {
+        self.uid
+    }
+
+    pub(in crate::user::domain) fn is_active(&self) -> bool {
+        self.expires_at
+            .map_or(true, |t| t > chrono::Local::now().naive_local())
+    }
+
+    /// The error returned to a banned user.
+    pub(crate) fn status(&self) -> Status {
+        let until = self
+            .expires_at
+            .map_or("forever".to_string(), |t| format!("until {}", t));
+        Status::permission_denied(format!(
+            "User({}) is banned {}, reason: {}",
+            self.uid, until, self.reason
+        ))
+    }
+
+    pub(in crate::user::domain) fn to_pb(&self) -> pb::Ban {
+        pb::Ban {
+            reason: self.reason.clone(),
+            banned_by: self.banned_by,
+            expires_at: self.expires_at.map(|t| t.timestamp()),
+            banned_at: self.updated_at.timestamp(),
+        }
+    }
+
+    /// Returns the ban of the user if it has not expired, shared with the movie domain.
+    pub(crate) fn active(user_id: i64, conn: &mut PgConnection) -> GrpcResult<Option<Ban>> {
+        use migration::t_users_bans::dsl::*;
+
+        let ban: Option<Ban> = t_users_bans
+            .filter(uid.eq(user_id))
+            .first(conn)
+            .optional()
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        Ok(ban.filter(Ban::is_active))
+    }
+
+    /// Reject the writes of a banned user.
+    pub(in crate::user::domain) fn check(user_id: i64, conn: &mut PgConnection) -> GrpcResult<()> {
+        match Ban::active(user_id, conn)? {
+            Some(ban) => Err(ban.status().into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Ban the user, an existing ban is replaced.
+    pub(in crate::user::domain) fn ban(
+        user_id: i64,
+        admin_id: i64,
+        why: &str,
+        until: Option<NaiveDateTime>,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_users_bans::dsl::*;
+
+        let new = NewBan {
+            uid: user_id,
+            reason: why,
+            banned_by: admin_id,
+            expires_at: until,
+        };
+        diesel::insert_into(t_users_bans)
+            .values(&new)
+            .on_conflict(uid)
+            .do_update()
+            .set(&new)
+            .execute(conn)
+            .map_err(|e| internal!(format!("Cannot ban user({}), err: {}", user_id, e)))?;
+        Ok(())
+    }
+
+    pub(in crate::user::domain) fn unban(user_id: i64, conn: &mut PgConnection) -> GrpcResult<()> {
+        use migration::t_users_bans::dsl::*;
+
+        let deleted = diesel::delete(t_users_bans.filter(uid.eq(user_id)))
+            .execute(conn)
+            .map_err(|e| internal!(format!("Cannot unban user({}), err: {}", user_id, e)))?;
+        if deleted == 0 {
+            return Err(not_found!(format!("ban of user({})", user_id)).into());
+        }
+        Ok(())
+    }
+}
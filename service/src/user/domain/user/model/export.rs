@@ -0,0 +1,255 @@
+use crate::user::domain::user::model::oauth::OAuth;
+use crate::user::domain::user::model::user::User;
+use chrono::NaiveDateTime;
+use common::internal;
+use common::status::prelude::*;
+use diesel::prelude::*;
+use migration::{t_doulists, t_movies_reviews, t_user_marks, t_user_ratings};
+use proto::pb::user::sys::v1 as pb;
+use serde::Serialize;
+use std::io::Write;
+
+#[derive(Serialize)]
+struct Identities<'a> {
+    id: i64,
+    username: &'a str,
+    email: Option<&'a str>,
+    phone: Option<&'a str>,
+    github: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct Rating {
+    movie_id: i64,
+    score: i32,
+    comment: Option<String>,
+    // unix timestamp (second)
+    rated_at: i64,
+}
+
+#[derive(Serialize)]
+struct Review {
+    movie_id: i64,
+    title: String,
+    content: String,
+    spoiler: bool,
+    // unix timestamp (second)
+    written_at: i64,
+}
+
+#[derive(Serialize)]
+struct Mark {
+    movie_id: i64,
+    status: String,
+    tags: Vec<String>,
+    // unix timestamp (second)
+    marked_at: i64,
+}
+
+#[derive(Serialize)]
+struct Doulist {
+    id: i64,
+    title: String,
+    description: String,
+    public: bool,
+    // unix timestamp (second)
+    created_at: i64,
+}
+
+/// Personal data of an user, exported on request of the user.
+pub struct Export {
+    profile: pb::GetMeRes,
+    github: Option<i64>,
+    ratings: Vec<Rating>,
+    reviews: Vec<Review>,
+    marks: Vec<Mark>,
+    doulists: Vec<Doulist>,
+}
+
+impl Export {
+    pub(in crate::user::domain) fn collect(
+        user: &User,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Export> {
+        use migration::t_oauth::dsl::*;
+
+        let oauth: Option<OAuth> = match user.oauth_id() {
+            Some(oid) => t_oauth
+                .select((id, github))
+                .find(oid)
+                .first(conn)
+                .optional()
+                .map_err(|e| internal!(format!("Database connection error: {}", e)))?,
+            None => None,
+        };
+        let ratings: Vec<(i64, i16, Option<String>, NaiveDateTime)> = t_user_ratings::table
+            .select((
+                t_user_ratings::mid,
+                t_user_ratings::star,
+                t_user_ratings::comment,
+                t_user_ratings::updated_at,
+            ))
+            .filter(t_user_ratings::uid.eq(user.id()))
+            .order(t_user_ratings::updated_at.desc())
+            .load(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        let reviews: Vec<(i64, String, String, bool, NaiveDateTime)> = t_movies_reviews::table
+            .select((
+                t_movies_reviews::mid,
+                t_movies_reviews::title,
+                t_movies_reviews::content,
+                t_movies_reviews::spoiler,
+                t_movies_reviews::created_at,
+            ))
+            .filter(t_movies_reviews::uid.eq(user.id()))
+            .order(t_movies_reviews::created_at.desc())
+            .load(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        let marks: Vec<(i64, String, Vec<String>, NaiveDateTime)> = t_user_marks::table
+            .select((
+                t_user_marks::mid,
+                t_user_marks::status,
+                t_user_marks::tags,
+                t_user_marks::marked_at,
+            ))
+            .filter(t_user_marks::uid.eq(user.id()))
+            .order(t_user_marks::marked_at.desc())
+            .load(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        let doulists: Vec<(i64, String, String, bool, NaiveDateTime)> = t_doulists::table
+            .select((
+                t_doulists::id,
+                t_doulists::title,
+                t_doulists::description,
+                t_doulists::public,
+                t_doulists::created_at,
+            ))
+            .filter(t_doulists::uid.eq(user.id()))
+            .order(t_doulists::created_at.desc())
+            .load(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        Ok(Export {
+            profile: user.private_profile(),
+            github: oauth.and_then(|oauth| oauth.github),
+            ratings: ratings
+                .into_iter()
+                .map(|(movie_id, star, comment, rated_at)| Rating {
+                    movie_id,
+                    score: star as i32,
+                    comment,
+                    rated_at: rated_at.timestamp(),
+                })
+                .collect(),
+            reviews: reviews
+                .into_iter()
+                .map(|(movie_id, title, content, spoiler, written_at)| Review {
+                    movie_id,
+                    title,
+                    content,
+                    spoiler,
+                    written_at: written_at.timestamp(),
+                })
+                .collect(),
+            marks: marks
+                .into_iter()
+                .map(|(movie_id, status, tags, marked_at)| Mark {
+                    movie_id,
+                    status,
+                    tags,
+                    marked_at: marked_at.timestamp(),
+                })
+                .collect(),
+            doulists: doulists
+                .into_iter()
+                .map(|(id, title, description, public, created_at)| Doulist {
+                    id,
+                    title,
+                    description,
+                    public,
+                    created_at: created_at.timestamp(),
+                })
+                .collect(),
+        })
+    }
+
+    fn identities(&self) -> Identities<'_> {
+        Identities {
+            id: self.profile.profile.as_ref().map_or(0, |p| p.id),
+            username: &self.profile.username,
+            email: self.profile.email.as_deref(),
+            phone: self.profile.phone.as_deref(),
+            github: self.github,
+        }
+    }
+
+    pub(in crate::user::domain) fn archive(
+        &self,
+        format: pb::ExportFormat,
+    ) -> GrpcResult<pb::ExportMyDataRes> {
+        let map_err =
+            |e: serde_json::Error| internal!(format!("Cannot serialize export, err: {}", e));
+        let res = match format {
+            pb::ExportFormat::Json => {
+                let data = serde_json::to_vec_pretty(&serde_json::json!({
+                    "profile": self.profile,
+                    "identities": self.identities(),
+                    "ratings": self.ratings,
+                    "reviews": self.reviews,
+                    "marks": self.marks,
+                    "doulists": self.doulists,
+                }))
+                .map_err(map_err)?;
+                pb::ExportMyDataRes {
+                    filename: "export.json".to_string(),
+                    content_type: "application/json".to_string(),
+                    data,
+                }
+            }
+            pb::ExportFormat::Zip => {
+                let files = [
+                    (
+                        "profile.json",
+                        serde_json::to_vec_pretty(&self.profile).map_err(map_err)?,
+                    ),
+                    (
+                        "identities.json",
+                        serde_json::to_vec_pretty(&self.identities()).map_err(map_err)?,
+                    ),
+                    (
+                        "ratings.json",
+                        serde_json::to_vec_pretty(&self.ratings).map_err(map_err)?,
+                    ),
+                    (
+                        "reviews.json",
+                        serde_json::to_vec_pretty(&self.reviews).map_err(map_err)?,
+                    ),
+                    (
+                        "marks.json",
+                        serde_json::to_vec_pretty(&self.marks).map_err(map_err)?,
+                    ),
+                    (
+                        "doulists.json",
+                        serde_json::to_vec_pretty(&self.doulists).map_err(map_err)?,
+                    ),
+                ];
+                let map_err =
+                    |e: std::io::Error| internal!(format!("Cannot write zip archive, err: {}", e));
+                let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+                let options = zip::write::FileOptions::default()
+                    .compression_method(zip::CompressionMethod::Deflated);
+                for (name, content) in files {
+                    zip.start_file(name, options)
+                        .map_err(|e| map_err(e.into()))?;
+                    zip.write_all(&content).map_err(map_err)?;
+                }
+                let data = zip.finish().map_err(|e| map_err(e.into()))?.into_inner();
+                pb::ExportMyDataRes {
+                    filename: "export.zip".to_string(),
+                    content_type: "application/zip".to_string(),
+                    data,
+                }
+            }
+        };
+        Ok(res)
+    }
+}
@@ -0,0 +1,208 @@
+use crate::user::domain::user::model::user::IdentifierKind;
+use chrono::NaiveDateTime;
+use common::internal;
+use common::status::prelude::*;
+use diesel::prelude::*;
+use migration::t_login_events;
+use proto::pb::user::sys::v1 as pb;
+use redis::Commands;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// redis channel of new device events, subscribed by the notification service
+const NEW_DEVICE_CHANNEL: &str = "user:event:new_device";
+
+#[derive(Queryable)]
+pub struct LoginEvent {
+    id: i64,
+    uid: i64,
+    identifier_kind: String,
+    ip: Option<String>,
+    user_agent: Option<String>,
+    ua_fingerprint: Option<String>,
+    succeeded: bool,
+    created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = t_login_events)]
+struct NewLoginEvent<'a> {
+    uid: i64,
+    identifier_kind: &'a str,
+    ip: Option<&'a str>,
+    user_agent: Option<&'a str>,
+    ua_fingerprint: Option<&'a str>,
+    succeeded: bool,
+}
+
+#[derive(Serialize)]
+pub struct NewDeviceEvent<'a> {
+    uid: i64,
+    ip: Option<&'a str>,
+    user_agent: Option<&'a str>,
+    // unix timestamp (second)
+    at: u64,
+}
+
+#[inline]
+fn fingerprint(user_agent: &str) -> String {
+    let hash = Sha256::digest(user_agent.trim().to_lowercase().as_bytes());
+    hash.iter().take(16).map(|b| format!("{:02x}", b)).collect()
+}
+
+/// A login attempt of a known user, recorded into the login history. It is kept with a
+/// TOTP challenge until the second factor is verified.
+#[derive(Serialize, Deserialize)]
+pub struct LoginAttempt {
+    identifier_kind: String,
+    ip: Option<String>,
+    user_agent: Option<String>,
+    ua_fingerprint: Option<String>,
+}
+
+impl LoginAttempt {
+    pub(in crate::user::domain) fn new(
+        kind: &IdentifierKind,
+        ip: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> Self {
+        // columns are varchar(64) and varchar(512)
+        let ip = ip.filter(|ip| ip.len() <= 64);
+        let user_agent = user_agent.map(|ua| match ua.char_indices().nth(512) {
+            Some((end, _)) => &ua[..end],
+            None => ua,
+        });
+        Self {
+            identifier_kind: kind.name().to_string(),
+            ip: ip.map(ToOwned::to_owned),
+            user_agent: user_agent.map(ToOwned::to_owned),
+            ua_fingerprint: user_agent.map(fingerprint),
+        }
+    }
+
+    pub(in crate::user::domain) fn ip(&self) -> Option<&str> {
+        self.ip.as_deref()
+    }
+
+    pub(in crate::user::domain) fn record(
+        &self,
+        user_id: i64,
+        success: bool,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_login_events::dsl::*;
+
+        diesel::insert_into(t_login_events)
+            .values(NewLoginEvent {
+                uid: user_id,
+                identifier_kind: &self.identifier_kind,
+                ip: self.ip.as_deref(),
+                user_agent: self.user_agent.as_deref(),
+                ua_fingerprint: self.ua_fingerprint.as_deref(),
+                succeeded: success,
+            })
+            .execute(conn)
+            .map_err(|e| internal!(format!("Cannot record login event, err: {}", e)))?;
+        Ok(())
+    }
+
+    /// Whether the ip or the user agent has never been used in a successful login of the user,
+    /// the first login of an user is not regarded as a new device.
+    pub(in crate::user::domain) fn is_new_device(
+        &self,
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<bool> {
+        use migration::t_login_events::dsl::*;
+
+        let map_err = |e| internal!(format!("Database connection error: {}", e));
+        let history = t_login_events
+            .filter(uid.eq(user_id))
+            .filter(succeeded.eq(true));
+        let logged_in: bool = diesel::select(diesel::dsl::exists(history))
+            .get_result(conn)
+            .map_err(map_err)?;
+        if !logged_in {
+            return Ok(false);
+        }
+        if let Some(addr) = &self.ip {
+            let seen: bool = diesel::select(diesel::dsl::exists(history.filter(ip.eq(addr))))
+                .get_result(conn)
+                .map_err(map_err)?;
+            if !seen {
+                return Ok(true);
+            }
+        }
+        if let Some(fp) = &self.ua_fingerprint {
+            let seen: bool =
+                diesel::select(diesel::dsl::exists(history.filter(ua_fingerprint.eq(fp))))
+                    .get_result(conn)
+                    .map_err(map_err)?;
+            if !seen {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Publish a new device event, a failure is logged rather than failing the login.
+    pub(in crate::user::domain) fn notify_new_device(
+        &self,
+        user_id: i64,
+        conn: &mut redis::Connection,
+    ) {
+        let event = NewDeviceEvent {
+            uid: user_id,
+            ip: self.ip.as_deref(),
+            user_agent: self.user_agent.as_deref(),
+            at: jsonwebtoken::get_current_timestamp(),
+        };
+        tracing::info!(
+            "User({}) logged in from a new device, ip: {:?}, user agent: {:?}",
+            user_id,
+            self.ip,
+            self.user_agent
+        );
+        let payload = serde_json::to_string(&event).expect("NewDeviceEvent is serializable");
+        let published: redis::RedisResult<i64> = conn.publish(NEW_DEVICE_CHANNEL, payload);
+        if let Err(e) = published {
+            tracing::warn!("Failed to publish new device event, err: {}", e);
+        }
+    }
+}
+
+impl LoginEvent {
+    pub(in crate::user::domain) fn list(
+        user_id: i64,
+        limit: i64,
+        offset: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<LoginEvent>, i64)> {
+        use migration::t_login_events::dsl::*;
+
+        let map_err = |e| internal!(format!("Database connection error: {}", e));
+        let total: i64 = t_login_events
+            .filter(uid.eq(user_id))
+            .count()
+            .get_result(conn)
+            .map_err(map_err)?;
+        let events: Vec<LoginEvent> = t_login_events
+            .filter(uid.eq(user_id))
+            .order(created_at.desc())
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+            .map_err(map_err)?;
+        Ok((events, total))
+    }
+
+    pub(in crate::user::domain) fn to_pb(&self) -> pb::LoginEvent {
+        pb::LoginEvent {
+            identifier_kind: self.identifier_kind.clone(),
+            ip: self.ip.clone(),
+            user_agent: self.user_agent.clone(),
+            succeeded: self.succeeded,
+            created_at: self.created_at.timestamp(),
+        }
+    }
+}
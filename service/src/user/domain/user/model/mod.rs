@@ -1,2 +1,12 @@
+pub mod admin;
+pub mod attempt;
+pub mod ban;
+pub mod export;
+pub mod login_event;
 pub mod oauth;
+pub mod phone;
+pub mod profile;
+pub mod search;
+pub mod totp;
 pub mod user;
+pub mod validation;
@@ -0,0 +1,38 @@
+use once_cell::sync::OnceCell;
+use phonenumber::country;
+use phonenumber::Mode;
+
+// region of numbers written without a country code, set once by the resolver
+static DEFAULT_REGION: OnceCell<country::Id> = OnceCell::new();
+
+/// Set the region used to parse national numbers, the first call wins.
+pub fn init_default_region(region: &str) {
+    let id = region
+        .to_uppercase()
+        .parse::<country::Id>()
+        .unwrap_or_else(|_| panic!("phone_region({}) is not an ISO 3166-1 alpha-2 code", region));
+    let _ = DEFAULT_REGION.set(id);
+}
+
+#[inline]
+fn default_region() -> country::Id {
+    *DEFAULT_REGION.get_or_init(|| country::Id::CN)
+}
+
+/// Normalise a phone number into E.164, `None` if it is not a valid number.
+///
+/// `+86 138 0013 8000`, `008613800138000` and `13800138000` (in region CN)
+/// are all normalised into `+8613800138000`.
+pub fn normalize(phone: &str) -> Option<String> {
+    let phone = phone.trim();
+    // the international call prefix used by most regions
+    let phone = match phone.strip_prefix("00") {
+        Some(rest) => format!("+{}", rest),
+        None => phone.to_string(),
+    };
+    let number = phonenumber::parse(Some(default_region()), &phone).ok()?;
+    if !phonenumber::is_valid(&number) {
+        return None;
+    }
+    Some(number.format().mode(Mode::E164).to_string())
+}
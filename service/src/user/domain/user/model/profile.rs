@@ -0,0 +1,110 @@
+use common::invalid_argument;
+use common::status::prelude::*;
+use diesel::prelude::*;
+use migration::t_users;
+use proto::pb::user::sys::v1 as pb;
+
+const NICKNAME_MAX: usize = 32;
+const AVATAR_URL_MAX: usize = 512;
+const BIO_MAX: usize = 256;
+const LOCATION_MAX: usize = 64;
+const GENDERS: [&str; 3] = ["male", "female", "other"];
+
+/// Validated changes of a profile, `Some(None)` clears an optional field.
+///
+/// There is no username here, username cannot be changed once registered.
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = t_users)]
+pub struct PutProfile {
+    nickname: Option<String>,
+    avatar_url: Option<Option<String>>,
+    bio: Option<Option<String>>,
+    gender: Option<Option<String>>,
+    location: Option<Option<String>>,
+}
+
+#[inline]
+fn check_text(value: &str, max: usize) -> bool {
+    value.chars().count() <= max && !value.chars().any(char::is_control)
+}
+
+// empty string clears the field
+#[inline]
+fn clearable(value: Option<String>) -> Option<Option<String>> {
+    value.map(|value| {
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+impl PutProfile {
+    pub(in crate::user::domain) fn parse(req: pb::UpdateProfileReq) -> GrpcResult<Self> {
+        let nickname = req.nickname.map(|nickname| nickname.trim().to_string());
+        if let Some(ref nickname) = nickname {
+            if nickname.is_empty() || !check_text(nickname, NICKNAME_MAX) {
+                return Err(invalid_argument!(
+                    "nickname",
+                    "1 to 32 characters without control characters"
+                )
+                .into());
+            }
+        }
+
+        let avatar_url = clearable(req.avatar_url);
+        if let Some(Some(ref url)) = avatar_url {
+            let scheme = url.starts_with("https://") || url.starts_with("http://");
+            if !scheme || url.len() > AVATAR_URL_MAX || url.contains(char::is_whitespace) {
+                return Err(
+                    invalid_argument!("avatar_url", "a http(s) url of at most 512 bytes").into(),
+                );
+            }
+        }
+
+        let bio = clearable(req.bio);
+        if let Some(Some(ref bio)) = bio {
+            // line breaks are allowed in bio
+            let stripped = bio.replace(['\n', '\r'], "");
+            if !check_text(&stripped, BIO_MAX) || bio.chars().count() > BIO_MAX {
+                return Err(invalid_argument!(
+                    "bio",
+                    "at most 256 characters without control characters"
+                )
+                .into());
+            }
+        }
+
+        let gender = clearable(req.gender).map(|gender| gender.map(|g| g.to_lowercase()));
+        if let Some(Some(ref gender)) = gender {
+            if !GENDERS.contains(&gender.as_str()) {
+                return Err(invalid_argument!("gender", "one of male, female, other").into());
+            }
+        }
+
+        let location = clearable(req.location);
+        if let Some(Some(ref location)) = location {
+            if !check_text(location, LOCATION_MAX) {
+                return Err(invalid_argument!(
+                    "location",
+                    "at most 64 characters without control characters"
+                )
+                .into());
+            }
+        }
+
+        Ok(Self {
+            nickname,
+            avatar_url,
+            bio,
+            gender,
+            location,
+        })
+    }
+
+    pub(in crate::user::domain) fn is_empty(&self) -> bool {
+        self.nickname.is_none()
+            && self.avatar_url.is_none()
+            && self.bio.is_none()
+            && self.gender.is_none()
+            && self.location.is_none()
+    }
+}
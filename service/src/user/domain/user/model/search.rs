@@ -0,0 +1,97 @@
+use crate::user::domain::user::model::user::User;
+use common::status::prelude::*;
+use common::{internal, invalid_argument};
+use diesel::dsl::{not, sql};
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer, Text};
+use migration::t_users;
+use proto::pb::user::sys::v1 as pb;
+
+const MAX_KEYWORD_LEN: usize = 64;
+
+#[inline]
+fn escape_like(keyword: &str) -> String {
+    keyword
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Search users by username and nickname, backed by the trigram indexes.
+pub struct UserSearch {
+    keyword: String,
+    order: pb::SearchOrder,
+    hidden: Vec<i64>,
+}
+
+impl UserSearch {
+    pub(in crate::user::domain) fn new(
+        keyword: &str,
+        order: i32,
+        hidden: Vec<i64>,
+    ) -> GrpcResult<UserSearch> {
+        let keyword = keyword.trim().to_lowercase();
+        if keyword.is_empty() || keyword.chars().count() > MAX_KEYWORD_LEN {
+            return Err(invalid_argument!("keyword", "1 to 64 characters").into());
+        }
+        let order = pb::SearchOrder::from_i32(order)
+            .ok_or_else(|| invalid_argument!("order", "POPULAR or RECENT"))?;
+        Ok(UserSearch {
+            keyword,
+            order,
+            hidden,
+        })
+    }
+
+    fn query(&self) -> t_users::BoxedQuery<'_, Pg> {
+        use migration::t_users::dsl::*;
+
+        let pattern = format!("%{}%", escape_like(&self.keyword));
+        t_users
+            .filter(username.ilike(pattern.clone()).or(nickname.ilike(pattern)))
+            .filter(deleted_at.is_null())
+            .filter(not(id.eq_any(&self.hidden)))
+            .into_boxed()
+    }
+
+    pub(in crate::user::domain) fn search(
+        &self,
+        limit: i64,
+        offset: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<(Vec<User>, i64)> {
+        use migration::t_users::dsl::*;
+
+        let map_err = |e| internal!(format!("Database connection error: {}", e));
+        let total: i64 = self.query().count().get_result(conn).map_err(map_err)?;
+        // 0: exact match, 1: prefix match, 2: substring match
+        let relevance = sql::<Integer>("CASE WHEN lower(username) = ")
+            .bind::<Text, _>(self.keyword.clone())
+            .sql(" OR lower(nickname) = ")
+            .bind::<Text, _>(self.keyword.clone())
+            .sql(" THEN 0 WHEN lower(username) LIKE ")
+            .bind::<Text, _>(format!("{}%", escape_like(&self.keyword)))
+            .sql(" OR lower(nickname) LIKE ")
+            .bind::<Text, _>(format!("{}%", escape_like(&self.keyword)))
+            .sql(" THEN 1 ELSE 2 END");
+        let query = self.query().order(relevance.asc());
+        let query = match self.order {
+            pb::SearchOrder::Popular => query
+                .then_order_by(
+                    sql::<BigInt>(
+                        "(SELECT count(*) FROM t_user_follows WHERE t_user_follows.followee = t_users.id)",
+                    )
+                    .desc(),
+                )
+                .then_order_by(id.desc()),
+            pb::SearchOrder::Recent => query.then_order_by(created_at.desc()).then_order_by(id.desc()),
+        };
+        let users: Vec<User> = query
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+            .map_err(map_err)?;
+        Ok((users, total))
+    }
+}
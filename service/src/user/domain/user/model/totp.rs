@@ -0,0 +1,401 @@
+use crate::user::domain::user::model::login_event::LoginAttempt;
+use crate::user::domain::user::model::user::{hash_password, map_not_found};
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine;
+use chrono::NaiveDateTime;
+use common::status::prelude::*;
+use common::{already_exists, internal, invalid_argument, not_found};
+use diesel::prelude::*;
+use hmac::{Hmac, Mac};
+use migration::{t_users_recovery_codes, t_users_totp};
+use proto::pb::user::sys::v1::TotpChallenge;
+use rand::distributions::Alphanumeric;
+use rand::{random, Rng};
+use redis::Commands;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const DIGITS: u32 = 6;
+// unit (second)
+const PERIOD: u64 = 30;
+// accept codes of adjacent time steps to tolerate clock drift
+const SKEW: i64 = 1;
+const SECRET_LEN: usize = 20;
+const NONCE_LEN: usize = 12;
+const RECOVERY_CODES: usize = 10;
+// unit (second)
+const CHALLENGE_EXPIRES: usize = 300;
+const CHALLENGE_ATTEMPTS: i64 = 5;
+const ISSUER: &str = "douban-rs";
+
+#[derive(Queryable)]
+pub struct Totp {
+    id: i64,
+    uid: i64,
+    secret: String,
+    enabled: bool,
+    last_step: i64,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = t_users_totp)]
+struct NewTotp<'a> {
+    uid: i64,
+    secret: &'a str,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = t_users_recovery_codes)]
+struct NewRecoveryCode {
+    uid: i64,
+    hashed_code: String,
+}
+
+/// A login of which the password is checked, completed by `VerifyTotp` with the second factor.
+#[derive(Serialize, Deserialize)]
+pub struct PendingLogin {
+    pub uid: i64,
+    pub attempt: LoginAttempt,
+}
+
+pub struct Enrollment {
+    // base32 encoded secret
+    pub secret: String,
+    pub recovery_codes: Vec<String>,
+}
+
+impl Enrollment {
+    pub fn otpauth_uri(&self, account: &str) -> String {
+        format!(
+            "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={digits}&period={period}",
+            issuer = ISSUER,
+            account = account,
+            secret = self.secret,
+            digits = DIGITS,
+            period = PERIOD,
+        )
+    }
+}
+
+#[inline]
+fn encrypt(cipher: &Aes256Gcm, plain: &[u8]) -> GrpcResult<String> {
+    let nonce: [u8; NONCE_LEN] = random();
+    let mut sealed = cipher
+        .encrypt(Nonce::from_slice(&nonce), plain)
+        .map_err(|_| internal!("Cannot encrypt TOTP secret"))?;
+    let mut bytes = nonce.to_vec();
+    bytes.append(&mut sealed);
+    Ok(base64::prelude::BASE64_STANDARD.encode(bytes))
+}
+
+#[inline]
+fn decrypt(cipher: &Aes256Gcm, sealed: &str) -> GrpcResult<Vec<u8>> {
+    let bytes = base64::prelude::BASE64_STANDARD
+        .decode(sealed)
+        .map_err(|_| internal!("Failed base64 decode TOTP secret"))?;
+    if bytes.len() < NONCE_LEN {
+        return Err(internal!("TOTP secret is corrupted").into());
+    }
+    let (nonce, sealed) = bytes.split_at(NONCE_LEN);
+    let plain = cipher
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| internal!("Cannot decrypt TOTP secret"))?;
+    Ok(plain)
+}
+
+// RFC 4226 HOTP, TOTP is HOTP with a time step counter
+#[inline]
+fn hotp(key: &[u8], counter: u64) -> String {
+    let mut mac = Hmac::<sha1::Sha1>::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(&counter.to_be_bytes());
+    let hash = Mac::finalize(mac).into_bytes();
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(DIGITS),
+        width = DIGITS as usize
+    )
+}
+
+#[inline]
+fn current_step() -> i64 {
+    (jsonwebtoken::get_current_timestamp() / PERIOD) as i64
+}
+
+#[inline]
+fn recovery_code() -> String {
+    let raw: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(10)
+        .map(char::from)
+        .collect::<String>()
+        .to_lowercase();
+    format!("{}-{}", &raw[..5], &raw[5..])
+}
+
+#[inline]
+fn challenge_key(value: &str) -> String {
+    format!("user:totp:challenge:{}", value)
+}
+
+impl Totp {
+    pub(in crate::user::domain) fn query_uid(
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Option<Totp>> {
+        use migration::t_users_totp::dsl::*;
+
+        let totp: Option<Totp> = t_users_totp
+            .filter(uid.eq(user_id))
+            .first(conn)
+            .optional()
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        Ok(totp)
+    }
+
+    pub(in crate::user::domain) fn is_enabled(
+        user_id: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<bool> {
+        Ok(Self::query_uid(user_id, conn)?.map_or(false, |totp| totp.enabled))
+    }
+
+    /// Generate a new secret and a set of recovery codes for the user,
+    /// a pending enrollment will be replaced.
+    pub(in crate::user::domain) fn enroll(
+        user_id: i64,
+        cipher: &Aes256Gcm,
+        hash_secret: &str,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Enrollment> {
+        use migration::t_users_totp::dsl::*;
+
+        conn.transaction::<Enrollment, GrpcStatus, _>(|conn| {
+            if Self::is_enabled(user_id, conn)? {
+                return Err(already_exists!(format!("totp of user({})", user_id)).into());
+            }
+            let raw: [u8; SECRET_LEN] = random();
+            let sealed = encrypt(cipher, &raw)?;
+            diesel::insert_into(t_users_totp)
+                .values(NewTotp {
+                    uid: user_id,
+                    secret: &sealed,
+                })
+                .on_conflict(uid)
+                .do_update()
+                .set((secret.eq(&sealed), enabled.eq(false), last_step.eq(0)))
+                .execute(conn)
+                .map_err(|e| internal!(format!("Cannot enroll totp, err: {}", e)))?;
+
+            let codes: Vec<String> = (0..RECOVERY_CODES).map(|_| recovery_code()).collect();
+            diesel::delete(
+                t_users_recovery_codes::table.filter(t_users_recovery_codes::uid.eq(user_id)),
+            )
+            .execute(conn)
+            .map_err(|e| internal!(format!("Cannot clear recovery codes, err: {}", e)))?;
+            diesel::insert_into(t_users_recovery_codes::table)
+                .values(
+                    codes
+                        .iter()
+                        .map(|code| NewRecoveryCode {
+                            uid: user_id,
+                            hashed_code: hash_password(hash_secret, code),
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .map_err(|e| internal!(format!("Cannot create recovery codes, err: {}", e)))?;
+
+            Ok(Enrollment {
+                secret: base32::encode(base32::Alphabet::RFC4648 { padding: false }, &raw),
+                recovery_codes: codes,
+            })
+        })
+    }
+
+    /// Activate a pending enrollment with the first code generated by the authenticator.
+    pub(in crate::user::domain) fn confirm(
+        &mut self,
+        code: &str,
+        cipher: &Aes256Gcm,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_users_totp::dsl::*;
+
+        if self.enabled {
+            return Err(already_exists!(format!("totp of user({})", self.uid)).into());
+        }
+        if !self.verify(code, cipher, conn)? {
+            return Err(invalid_argument!("code", "valid TOTP code").into());
+        }
+        diesel::update(t_users_totp.find(self.id))
+            .set(enabled.eq(true))
+            .execute(conn)
+            .map_err(map_not_found("totp", self.uid))?;
+        self.enabled = true;
+        Ok(())
+    }
+
+    /// Check the code against the adjacent time steps, a code of a step
+    /// which has been accepted before is rejected.
+    pub(in crate::user::domain) fn verify(
+        &mut self,
+        code: &str,
+        cipher: &Aes256Gcm,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<bool> {
+        use migration::t_users_totp::dsl::*;
+
+        if code.len() != DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return Ok(false);
+        }
+        let key = decrypt(cipher, &self.secret)?;
+        let now = current_step();
+        let step = (now - SKEW..=now + SKEW)
+            .filter(|step| *step > self.last_step)
+            .find(|step| hotp(&key, *step as u64) == code);
+        let step = match step {
+            Some(step) => step,
+            None => return Ok(false),
+        };
+        // compare-and-set, so that a concurrent request cannot reuse the code
+        let updated = diesel::update(t_users_totp.find(self.id).filter(last_step.lt(step)))
+            .set(last_step.eq(step))
+            .execute(conn)
+            .map_err(map_not_found("totp", self.uid))?;
+        self.last_step = step;
+        Ok(updated == 1)
+    }
+
+    pub(in crate::user::domain) fn use_recovery_code(
+        user_id: i64,
+        code: &str,
+        hash_secret: &str,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<bool> {
+        use migration::t_users_recovery_codes::dsl::*;
+
+        let hashed = hash_password(hash_secret, code.trim().to_lowercase().as_str());
+        let updated = diesel::update(
+            t_users_recovery_codes
+                .filter(uid.eq(user_id))
+                .filter(hashed_code.eq(hashed))
+                .filter(used_at.is_null()),
+        )
+        .set(used_at.eq(chrono::Local::now().naive_local()))
+        .execute(conn)
+        .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        Ok(updated == 1)
+    }
+
+    /// Issue a short-lived challenge which is exchanged for a token pair by `VerifyTotp`
+    pub(in crate::user::domain) fn challenge(
+        pending: &PendingLogin,
+        conn: &mut redis::Connection,
+    ) -> GrpcResult<TotpChallenge> {
+        let value = Uuid::new_v4().to_string();
+        let key = challenge_key(&value);
+        let pending = serde_json::to_string(pending).expect("PendingLogin is serializable");
+        let _: () = conn
+            .set_ex(&key, pending, CHALLENGE_EXPIRES)
+            .map_err(|e| internal!(format!("Redis failed to set key {}, err: {}", key, e)))?;
+        Ok(TotpChallenge {
+            value,
+            expires_in: CHALLENGE_EXPIRES as i64,
+        })
+    }
+
+    pub(in crate::user::domain) fn peek_challenge(
+        value: &str,
+        conn: &mut redis::Connection,
+    ) -> GrpcResult<PendingLogin> {
+        let key = challenge_key(value);
+        let pending: Option<String> = conn
+            .get(&key)
+            .map_err(|e| internal!(format!("Redis error, cannot get key {}, err: {}", key, e)))?;
+        let pending = pending.ok_or_else(|| not_found!(format!("challenge({})", value)))?;
+        let pending = serde_json::from_str(&pending)
+            .map_err(|e| internal!(format!("Challenge({}) is corrupted, err: {}", value, e)))?;
+        Ok(pending)
+    }
+
+    /// Record a failed attempt, the challenge is revoked after too many failures.
+    pub(in crate::user::domain) fn fail_challenge(
+        value: &str,
+        conn: &mut redis::Connection,
+    ) -> GrpcResult<()> {
+        let key = challenge_key(value);
+        let attempts_key = format!("{}:attempts", key);
+        let (attempts, _): (i64, bool) = redis::pipe()
+            .atomic()
+            .incr(&attempts_key, 1)
+            .expire(&attempts_key, CHALLENGE_EXPIRES)
+            .query(conn)
+            .map_err(|e| {
+                internal!(format!(
+                    "Redis failed to incr key {}, err: {}",
+                    attempts_key, e
+                ))
+            })?;
+        if attempts >= CHALLENGE_ATTEMPTS {
+            Self::clear_challenge(value, conn)?;
+        }
+        Ok(())
+    }
+
+    pub(in crate::user::domain) fn clear_challenge(
+        value: &str,
+        conn: &mut redis::Connection,
+    ) -> GrpcResult<()> {
+        let key = challenge_key(value);
+        let attempts_key = format!("{}:attempts", key);
+        let _: () = conn
+            .del(&[&key, &attempts_key])
+            .map_err(|_| internal!(format!("Failed to del key {}", key)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RFC_KEY: &[u8] = b"12345678901234567890";
+
+    // RFC 4226 Appendix D
+    #[test]
+    fn hotp_vectors() {
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583",
+            "399871", "520489",
+        ];
+        for (counter, code) in expected.iter().enumerate() {
+            assert_eq!(hotp(RFC_KEY, counter as u64), *code, "counter {}", counter);
+        }
+    }
+
+    // RFC 6238 Appendix B of SHA1, the codes are the last 6 of the 8 digits
+    #[test]
+    fn totp_vectors() {
+        let expected = [
+            (59, "287082"),
+            (1111111109, "081804"),
+            (1111111111, "050471"),
+            (1234567890, "005924"),
+            (2000000000, "279037"),
+            (20000000000, "353130"),
+        ];
+        for (time, code) in expected {
+            assert_eq!(hotp(RFC_KEY, time / PERIOD), code, "time {}", time);
+        }
+    }
+}
@@ -1,24 +1,29 @@
 use crate::user::domain::user::model::oauth::{GithubId, NewOAuth, PutOAuth};
+use crate::user::domain::user::model::phone;
+use crate::user::domain::user::model::profile::PutProfile;
 use crate::user::rpc::{RoleGroup, UserResolver};
 use base64::Engine;
 use chrono::NaiveDateTime;
 use common::infra::Resolver;
 use common::status::prelude::*;
-use common::utils::regex::{check_cn_phone, check_email};
+use common::utils::regex::check_email;
 use common::{already_exists, infra, internal, invalid_argument, not_found};
 use diesel::prelude::*;
 use diesel::result::Error;
 use hmac::{Hmac, Mac};
-use migration::t_users;
+use migration::{t_oauth, t_users, t_users_recovery_codes, t_users_totp};
 use proto::pb::auth::token::v1::token_service_client::TokenServiceClient;
 use proto::pb::auth::token::v1::{GenerateTokenReq, Payload};
-use proto::pb::user::sys::v1::LoginRes;
+use proto::pb::user::sys::v1::{GetMeRes, IdentifierType, LoginRes, UserProfile};
+use std::collections::HashMap;
 use std::fmt::Display;
 use tonic::transport::Channel;
 use tonic::Status;
 
 pub type UserId = infra::Id<User>;
 
+const DELETED_NICKNAME: &str = "deleted user";
+
 #[derive(Queryable)]
 pub struct User {
     id: i64,
@@ -32,6 +37,12 @@ pub struct User {
     created_at: NaiveDateTime,
     updated_at: NaiveDateTime,
     last_login: Option<NaiveDateTime>,
+    avatar_url: Option<String>,
+    bio: Option<String>,
+    gender: Option<String>,
+    location: Option<String>,
+    deleted_at: Option<NaiveDateTime>,
+    purged_at: Option<NaiveDateTime>,
 }
 
 #[derive(AsChangeset, Default)]
@@ -54,7 +65,7 @@ struct NewUser<'a> {
     hashed_password: String,
 }
 
-fn map_not_found<'a>(
+pub(super) fn map_not_found<'a>(
     scope: &'a str,
     identifier: impl Display + 'a,
 ) -> impl Fn(Error) -> Status + 'a {
@@ -67,7 +78,7 @@ fn map_not_found<'a>(
 }
 
 #[inline]
-fn hash_password(secret: &str, password: &str) -> String {
+pub(super) fn hash_password(secret: &str, password: &str) -> String {
     let mut mac = Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes())
         .expect("HMAC can take key of any size");
     mac.update(password.as_bytes());
@@ -75,7 +86,114 @@ fn hash_password(secret: &str, password: &str) -> String {
     base64::prelude::BASE64_STANDARD.encode(output)
 }
 
+/// How an identifier is resolved to an user.
+///
+/// Without an explicit type, the first matched kind wins in the order of email, phone, id
+/// and username. Digits which are a valid phone number, such as `13800138000` in region CN,
+/// resolve to a phone, an id of that form needs the explicit `ID` type. Usernames in the
+/// format of the other kinds are rejected at registration, so that a username always
+/// resolves to itself, users registered before that rule need an explicit type.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum IdentifierKind {
+    Id(i64),
+    Email,
+    // normalised into E.164
+    Phone(String),
+    Username,
+}
+
+impl IdentifierKind {
+    pub fn of(identifier: &str) -> IdentifierKind {
+        if check_email(identifier) {
+            return IdentifierKind::Email;
+        }
+        if let Some(phone) = phone::normalize(identifier) {
+            return IdentifierKind::Phone(phone);
+        }
+        if let Ok(uid) = identifier.parse::<i64>() {
+            return IdentifierKind::Id(uid);
+        }
+        IdentifierKind::Username
+    }
+
+    /// Resolve the identifier as the given type, detected by [`IdentifierKind::of`] if absent.
+    pub fn parse(identifier: &str, typ: Option<i32>) -> GrpcResult<IdentifierKind> {
+        let typ = match typ {
+            Some(typ) => IdentifierType::from_i32(typ).ok_or_else(|| {
+                invalid_argument!("identifier_type", "AUTO, ID, EMAIL, PHONE or USERNAME")
+            })?,
+            None => IdentifierType::Auto,
+        };
+        match typ {
+            IdentifierType::Auto => Ok(Self::of(identifier)),
+            IdentifierType::Id => identifier
+                .parse::<i64>()
+                .map(IdentifierKind::Id)
+                .map_err(|_| invalid_argument!("identifier", "an user id").into()),
+            IdentifierType::Email if check_email(identifier) => Ok(IdentifierKind::Email),
+            IdentifierType::Email => Err(invalid_argument!("identifier", "an email").into()),
+            IdentifierType::Phone => phone::normalize(identifier)
+                .map(IdentifierKind::Phone)
+                .ok_or_else(|| invalid_argument!("identifier", "a valid phone number").into()),
+            IdentifierType::Username => Ok(IdentifierKind::Username),
+        }
+    }
+
+    /// Canonical form of the identifier, every spelling of one identifier has the same.
+    pub fn canonical(&self, identifier: &str) -> String {
+        match self {
+            IdentifierKind::Id(uid) => format!("id:{}", uid),
+            IdentifierKind::Email => format!("email:{}", identifier.trim().to_lowercase()),
+            IdentifierKind::Phone(phone_num) => format!("phone:{}", phone_num),
+            IdentifierKind::Username => format!("username:{}", identifier.trim().to_lowercase()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            IdentifierKind::Id(_) => "id",
+            IdentifierKind::Email => "email",
+            IdentifierKind::Phone(_) => "phone",
+            IdentifierKind::Username => "username",
+        }
+    }
+}
+
+#[inline]
+fn authenticate(user: Option<User>, password: &str, secret: &str) -> GrpcResult<Option<User>> {
+    match user {
+        Some(user) if !user.is_deleted() => {
+            Ok(user.check_password(password, secret)?.then_some(user))
+        }
+        _ => {
+            // hash anyway, so that a missing or deleted account takes as long as a wrong password
+            hash_password(secret, password);
+            Ok(None)
+        }
+    }
+}
+
 impl User {
+    pub(in crate::user::domain) fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub(in crate::user::domain) fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub(in crate::user::domain) fn role_group(&self) -> &str {
+        &self.role_group
+    }
+
+    pub(in crate::user::domain) fn oauth_id(&self) -> Option<i64> {
+        self.oauth_id
+    }
+
+    pub(in crate::user::domain) fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
     pub(in crate::user::domain) fn register(
         name: &str,
         password: &str,
@@ -119,6 +237,36 @@ impl User {
         Ok(user)
     }
 
+    /// Query an user which has not been deleted.
+    pub(in crate::user::domain) fn query_alive(
+        uid: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<User> {
+        let user = Self::query_id(UserId::from(uid as u64), conn)?;
+        if user.is_deleted() {
+            return Err(not_found!(format!("user({})", uid)).into());
+        }
+        Ok(user)
+    }
+
+    /// Query alive users of the ids, the order of the ids is kept and missing users are skipped.
+    pub(in crate::user::domain) fn query_ids(
+        uids: &[i64],
+        conn: &mut PgConnection,
+    ) -> GrpcResult<Vec<User>> {
+        use migration::t_users::dsl::*;
+
+        let mut users: HashMap<i64, User> = t_users
+            .filter(id.eq_any(uids))
+            .filter(deleted_at.is_null())
+            .load::<User>(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect();
+        Ok(uids.iter().filter_map(|uid| users.remove(uid)).collect())
+    }
+
     pub(in crate::user::domain) fn query_email(
         mail: &str,
         conn: &mut PgConnection,
@@ -162,36 +310,45 @@ impl User {
         identifier: &str,
         conn: &mut PgConnection,
     ) -> GrpcResult<User> {
-        let uid = identifier.parse::<i64>();
-        if let Ok(uid) = uid {
-            return Self::query_id(UserId::from(uid as u64), conn);
-        }
-        if check_email(identifier) {
-            return Self::query_email(identifier, conn);
-        }
-        if check_cn_phone(identifier) {
-            return Self::query_phone(identifier, conn);
+        Self::query_kind(identifier, &IdentifierKind::of(identifier), conn)
+    }
+
+    /// Resolve the identifier to an user which has not been deleted, a deleted account
+    /// is kept until it is purged but cannot be used any more.
+    pub(in crate::user::domain) fn query_kind(
+        identifier: &str,
+        kind: &IdentifierKind,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<User> {
+        let user = match kind {
+            IdentifierKind::Id(uid) => Self::query_id(UserId::from(*uid as u64), conn),
+            IdentifierKind::Email => Self::query_email(identifier, conn),
+            IdentifierKind::Phone(phone_num) => Self::query_phone(phone_num, conn),
+            IdentifierKind::Username => Self::query_username(identifier, conn),
+        }?;
+        if user.is_deleted() {
+            return Err(not_found!(format!("user({})", identifier)).into());
         }
-        Self::query_username(identifier, conn)
+        Ok(user)
     }
 
+    /// Returns `None` if the identifier or the password is wrong, they must not be
+    /// told apart by callers, otherwise the existence of an account is leaked.
     pub(in crate::user::domain) fn login(
         identifier: &str,
+        kind: &IdentifierKind,
         password: &str,
         secret: &str,
         conn: &mut PgConnection,
-    ) -> GrpcResult<User> {
-        let uid = identifier.parse::<i64>();
-        if let Ok(uid) = uid {
-            return Self::login_id(uid, password, secret, conn);
-        }
-        if check_email(identifier) {
-            return Self::login_email(identifier, password, secret, conn);
-        }
-        if check_cn_phone(identifier) {
-            return Self::login_phone(identifier, password, secret, conn);
+    ) -> GrpcResult<Option<User>> {
+        match kind {
+            IdentifierKind::Id(uid) => Self::login_id(*uid, password, secret, conn),
+            IdentifierKind::Email => Self::login_email(identifier, password, secret, conn),
+            IdentifierKind::Phone(phone_num) => {
+                Self::login_phone(phone_num, password, secret, conn)
+            }
+            IdentifierKind::Username => Self::login_username(identifier, password, secret, conn),
         }
-        Self::login_username(identifier, password, secret, conn)
     }
 
     #[inline]
@@ -200,20 +357,24 @@ impl User {
         password: &str,
         secret: &str,
         conn: &mut PgConnection,
-    ) -> GrpcResult<User> {
+    ) -> GrpcResult<Option<User>> {
         use migration::t_users::dsl::*;
 
-        conn.transaction::<User, GrpcStatus, _>(|conn| {
-            let user: User = t_users
+        conn.transaction::<Option<User>, GrpcStatus, _>(|conn| {
+            let user: Option<User> = t_users
                 .filter(email.eq(mail))
                 .first(conn)
+                .optional()
                 .map_err(map_not_found("user", mail))?;
-            user.check_password(password, secret)?;
+            let user = match authenticate(user, password, secret)? {
+                Some(user) => user,
+                None => return Ok(None),
+            };
             diesel::update(t_users.filter(email.eq(mail)))
                 .set(last_login.eq(chrono::Local::now().naive_local()))
                 .execute(conn)
                 .map_err(map_not_found("user", mail))?;
-            Ok(user)
+            Ok(Some(user))
         })
     }
 
@@ -223,20 +384,24 @@ impl User {
         password: &str,
         secret: &str,
         conn: &mut PgConnection,
-    ) -> GrpcResult<User> {
+    ) -> GrpcResult<Option<User>> {
         use migration::t_users::dsl::*;
 
-        conn.transaction::<User, GrpcStatus, _>(|conn| {
-            let user: User = t_users
+        conn.transaction::<Option<User>, GrpcStatus, _>(|conn| {
+            let user: Option<User> = t_users
                 .filter(phone.eq(phone_num))
                 .first(conn)
+                .optional()
                 .map_err(map_not_found("user", phone_num))?;
-            user.check_password(password, secret)?;
+            let user = match authenticate(user, password, secret)? {
+                Some(user) => user,
+                None => return Ok(None),
+            };
             diesel::update(t_users.filter(phone.eq(phone_num)))
                 .set(last_login.eq(chrono::Local::now().naive_local()))
                 .execute(conn)
                 .map_err(map_not_found("user", phone_num))?;
-            Ok(user)
+            Ok(Some(user))
         })
     }
 
@@ -246,20 +411,24 @@ impl User {
         password: &str,
         secret: &str,
         conn: &mut PgConnection,
-    ) -> GrpcResult<User> {
+    ) -> GrpcResult<Option<User>> {
         use migration::t_users::dsl::*;
 
-        conn.transaction::<User, GrpcStatus, _>(|conn| {
-            let user: User = t_users
+        conn.transaction::<Option<User>, GrpcStatus, _>(|conn| {
+            let user: Option<User> = t_users
                 .filter(username.eq(uname))
                 .first(conn)
+                .optional()
                 .map_err(map_not_found("user", uname))?;
-            user.check_password(password, secret)?;
+            let user = match authenticate(user, password, secret)? {
+                Some(user) => user,
+                None => return Ok(None),
+            };
             diesel::update(t_users.filter(username.eq(uname)))
                 .set(last_login.eq(chrono::Local::now().naive_local()))
                 .execute(conn)
                 .map_err(map_not_found("user", uname))?;
-            Ok(user)
+            Ok(Some(user))
         })
     }
 
@@ -269,20 +438,24 @@ impl User {
         password: &str,
         secret: &str,
         conn: &mut PgConnection,
-    ) -> GrpcResult<User> {
+    ) -> GrpcResult<Option<User>> {
         use migration::t_users::dsl::*;
 
-        conn.transaction::<User, GrpcStatus, _>(|conn| {
-            let user: User = t_users
+        conn.transaction::<Option<User>, GrpcStatus, _>(|conn| {
+            let user: Option<User> = t_users
                 .find(uid)
                 .first(conn)
+                .optional()
                 .map_err(map_not_found("user", uid))?;
-            user.check_password(password, secret)?;
+            let user = match authenticate(user, password, secret)? {
+                Some(user) => user,
+                None => return Ok(None),
+            };
             diesel::update(t_users.find(uid))
                 .set(last_login.eq(chrono::Local::now().naive_local()))
                 .execute(conn)
                 .map_err(map_not_found("user", uid))?;
-            Ok(user)
+            Ok(Some(user))
         })
     }
 
@@ -290,15 +463,19 @@ impl User {
         &self,
         password: &str,
         secret: &str,
-    ) -> GrpcResult<()> {
+    ) -> GrpcResult<bool> {
         let mut mac = Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes())
             .expect("HMAC can take key of any size");
         mac.update(password.as_bytes());
         let bytes = base64::prelude::BASE64_STANDARD
             .decode(&self.hashed_password)
             .map_err(|_| internal!("Failed base64 decode hashed password"))?;
-        Mac::verify_slice(mac, bytes.as_slice())
-            .map_err(|_| invalid_argument!("password", "correct password").into())
+        Ok(Mac::verify_slice(mac, bytes.as_slice()).is_ok())
+    }
+
+    /// Whom login attempts of any identifier of the user are counted against.
+    pub(in crate::user::domain) fn account(&self) -> String {
+        IdentifierKind::Id(self.id).canonical("")
     }
 
     pub(in crate::user::domain) fn update_password(
@@ -325,6 +502,127 @@ impl User {
         Ok(())
     }
 
+    pub(in crate::user::domain) fn public_profile(&self) -> UserProfile {
+        UserProfile {
+            id: self.id,
+            nickname: self.nickname.clone(),
+            avatar_url: self.avatar_url.clone(),
+            bio: self.bio.clone(),
+            joined_at: self.created_at.timestamp(),
+        }
+    }
+
+    pub(in crate::user::domain) fn private_profile(&self) -> GetMeRes {
+        GetMeRes {
+            profile: Some(self.public_profile()),
+            username: self.username.clone(),
+            email: self.email.clone(),
+            phone: self.phone.clone(),
+            gender: self.gender.clone(),
+            location: self.location.clone(),
+            role_group: self.role_group.clone(),
+            last_login: self.last_login.map(|t| t.timestamp()),
+        }
+    }
+
+    pub(in crate::user::domain) fn update_profile(
+        &self,
+        put: PutProfile,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_users::dsl::*;
+
+        if put.is_empty() {
+            return Ok(());
+        }
+        diesel::update(t_users.find(self.id))
+            .set(put)
+            .execute(conn)
+            .map_err(map_not_found("user", self.id))?;
+        Ok(())
+    }
+
+    /// Soft delete the account, the personal data is kept until it is purged after
+    /// the grace period. OAuth links and the second factor are removed at once.
+    pub(in crate::user::domain) fn delete_account(
+        &mut self,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<()> {
+        use migration::t_users::dsl::*;
+
+        if self.is_deleted() {
+            return Err(not_found!(format!("user({})", self.id)).into());
+        }
+        let now = chrono::Local::now().naive_local();
+        conn.transaction::<(), GrpcStatus, _>(|conn| {
+            diesel::update(t_users.find(self.id))
+                .set((deleted_at.eq(now), oauth_id.eq(None::<i64>)))
+                .execute(conn)
+                .map_err(map_not_found("user", self.id))?;
+            if let Some(oid) = self.oauth_id {
+                diesel::delete(t_oauth::table.find(oid))
+                    .execute(conn)
+                    .map_err(|e| internal!(format!("Cannot delete oauth, err: {}", e)))?;
+            }
+            diesel::delete(
+                t_users_recovery_codes::table.filter(t_users_recovery_codes::uid.eq(self.id)),
+            )
+            .execute(conn)
+            .map_err(|e| internal!(format!("Cannot delete recovery codes, err: {}", e)))?;
+            diesel::delete(t_users_totp::table.filter(t_users_totp::uid.eq(self.id)))
+                .execute(conn)
+                .map_err(|e| internal!(format!("Cannot delete totp, err: {}", e)))?;
+            Ok(())
+        })?;
+        self.deleted_at = Some(now);
+        self.oauth_id = None;
+        Ok(())
+    }
+
+    /// Anonymise at most `batch` accounts which were deleted before `grace` seconds ago,
+    /// returns the number of purged accounts.
+    pub(in crate::user) fn purge_deleted(
+        grace: u64,
+        batch: i64,
+        conn: &mut PgConnection,
+    ) -> GrpcResult<usize> {
+        use migration::t_users::dsl::*;
+
+        let now = chrono::Local::now().naive_local();
+        let deadline = now - chrono::Duration::seconds(grace as i64);
+        let ids: Vec<i64> = t_users
+            .select(id)
+            .filter(deleted_at.lt(deadline))
+            .filter(purged_at.is_null())
+            .order(deleted_at.asc())
+            .limit(batch)
+            .load(conn)
+            .map_err(|e| internal!(format!("Database connection error: {}", e)))?;
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let purged = diesel::update(t_users.filter(id.eq_any(&ids)).filter(purged_at.is_null()))
+            .set((
+                // usernames are unique, the id keeps them apart
+                username.eq(diesel::dsl::sql::<diesel::sql_types::Varchar>(
+                    "'deleted_' || id",
+                )),
+                nickname.eq(DELETED_NICKNAME),
+                email.eq(None::<String>),
+                phone.eq(None::<String>),
+                // no password can match an empty hash
+                hashed_password.eq(""),
+                avatar_url.eq(None::<String>),
+                bio.eq(None::<String>),
+                gender.eq(None::<String>),
+                location.eq(None::<String>),
+                purged_at.eq(now),
+            ))
+            .execute(conn)
+            .map_err(|e| internal!(format!("Cannot purge deleted users, err: {}", e)))?;
+        Ok(purged)
+    }
+
     pub(in crate::user::domain) async fn sign_token_pair(
         &self,
         mut client: TokenServiceClient<Channel>,
@@ -347,6 +645,7 @@ impl User {
         Ok(LoginRes {
             access: res.access,
             refresh: res.refresh,
+            challenge: None,
         })
     }
 
@@ -357,6 +656,13 @@ impl User {
         conn: &mut PgConnection,
     ) -> GrpcResult<()> {
         use migration::t_users::dsl::*;
+        // phones are stored normalised, so that every format resolves to the same user
+        let phone_num = phone_num
+            .map(|num| {
+                phone::normalize(&num)
+                    .ok_or_else(|| invalid_argument!("phone", "a valid phone number"))
+            })
+            .transpose()?;
         // todo check it is already bind?
         diesel::update(t_users.find(self.id))
             .set(PutUser {
@@ -408,3 +714,114 @@ impl User {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn username_of_digits_by_explicit_type() {
+        assert_eq!(IdentifierKind::of("10086"), IdentifierKind::Id(10086));
+        assert_eq!(
+            IdentifierKind::parse("10086", Some(IdentifierType::Username as i32)).unwrap(),
+            IdentifierKind::Username
+        );
+    }
+
+    #[test]
+    fn phone_by_auto_in_any_format() {
+        let phone = IdentifierKind::Phone("+8613800138000".to_string());
+        for identifier in [
+            "+86 138 0013 8000",
+            "+8613800138000",
+            "13800138000",
+            "008613800138000",
+            "0086 13800138000",
+        ] {
+            assert_eq!(IdentifierKind::parse(identifier, None).unwrap(), phone);
+            assert_eq!(
+                IdentifierKind::parse(identifier, Some(IdentifierType::Auto as i32)).unwrap(),
+                phone
+            );
+            assert_eq!(
+                IdentifierKind::parse(identifier, Some(IdentifierType::Phone as i32)).unwrap(),
+                phone
+            );
+        }
+        // an id in the form of a phone number is resolved by the explicit type
+        assert_eq!(
+            IdentifierKind::parse("13800138000", Some(IdentifierType::Id as i32)).unwrap(),
+            IdentifierKind::Id(13800138000)
+        );
+    }
+
+    #[test]
+    fn id_by_auto() {
+        for (identifier, uid) in [("42", 42), ("0042", 42), ("10086", 10086)] {
+            assert_eq!(IdentifierKind::of(identifier), IdentifierKind::Id(uid));
+        }
+    }
+
+    #[test]
+    fn id_by_explicit_type() {
+        assert_eq!(
+            IdentifierKind::parse("42", Some(IdentifierType::Id as i32)).unwrap(),
+            IdentifierKind::Id(42)
+        );
+    }
+
+    #[test]
+    fn auto_detect() {
+        assert_eq!(
+            IdentifierKind::of("alice@example.com"),
+            IdentifierKind::Email
+        );
+        assert_eq!(
+            IdentifierKind::of("+86 138 0013 8000"),
+            IdentifierKind::Phone("+8613800138000".to_string())
+        );
+        assert_eq!(IdentifierKind::of("alice"), IdentifierKind::Username);
+    }
+
+    #[test]
+    fn canonical_spellings() {
+        let canonical = |identifier: &str, typ: IdentifierType| {
+            IdentifierKind::parse(identifier, Some(typ as i32))
+                .unwrap()
+                .canonical(identifier)
+        };
+        for id in ["42", "042", "0042"] {
+            assert_eq!(canonical(id, IdentifierType::Id), "id:42");
+        }
+        for phone in ["13800138000", "+86 138 0013 8000", "+86-138-0013-8000"] {
+            assert_eq!(
+                canonical(phone, IdentifierType::Phone),
+                "phone:+8613800138000"
+            );
+        }
+        for email in ["alice@example.com", "Alice@Example.COM"] {
+            assert_eq!(
+                canonical(email, IdentifierType::Email),
+                "email:alice@example.com"
+            );
+        }
+    }
+
+    #[test]
+    fn explicit_type_mismatch() {
+        let mismatches = [
+            ("alice", IdentifierType::Id),
+            ("alice@example.com", IdentifierType::Id),
+            ("alice", IdentifierType::Email),
+            ("42", IdentifierType::Email),
+            ("alice", IdentifierType::Phone),
+            ("alice@example.com", IdentifierType::Phone),
+        ];
+        for (identifier, typ) in mismatches {
+            let err = IdentifierKind::parse(identifier, Some(typ as i32)).unwrap_err();
+            assert_eq!(err.code(), tonic::Code::InvalidArgument, "{}", identifier);
+        }
+        let err = IdentifierKind::parse("alice", Some(42)).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::user::domain::user::model::user::IdentifierKind;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+
+fn default_reserved_names() -> Vec<String> {
+    [
+        "admin",
+        "administrator",
+        "root",
+        "system",
+        "douban",
+        "official",
+        "support",
+        "anonymous",
+        "null",
+        "undefined",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidationConfig {
+    // characters allowed in username, written as a regex character class
+    pub username_charset: String,
+    pub username_min: usize,
+    // no more than 64, username is stored in varchar(64)
+    pub username_max: usize,
+    // case insensitive
+    pub reserved_names: Vec<String>,
+    pub password_min: usize,
+    pub password_max: usize,
+    // least kinds of lowercase, uppercase, digit and symbol in a password
+    pub password_classes: usize,
+    // a local file of breached passwords, one password per line
+    pub breached_passwords: Option<PathBuf>,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            username_charset: "a-zA-Z0-9_".to_string(),
+            username_min: 3,
+            username_max: 32,
+            reserved_names: default_reserved_names(),
+            password_min: 8,
+            password_max: 128,
+            password_classes: 2,
+            breached_passwords: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FieldViolation {
+    pub field: &'static str,
+    pub expect: String,
+}
+
+impl FieldViolation {
+    fn new(field: &'static str, expect: impl Into<String>) -> Self {
+        Self {
+            field,
+            expect: expect.into(),
+        }
+    }
+}
+
+/// Validate register requests, shared by rpc and rest API so that
+/// the rest API can reject a request before calling the rpc.
+pub struct RegisterValidator {
+    conf: ValidationConfig,
+    username: Regex,
+    reserved: HashSet<String>,
+    breached: HashSet<String>,
+}
+
+impl RegisterValidator {
+    pub fn new(conf: &ValidationConfig) -> Self {
+        let username = Regex::new(&format!("^[{}]+$", conf.username_charset))
+            .expect("username_charset is not a valid regex character class");
+        let breached = conf
+            .breached_passwords
+            .as_ref()
+            .map(|path| {
+                std::fs::read_to_string(path)
+                    .unwrap_or_else(|e| panic!("Cannot read breached passwords {:?}: {}", path, e))
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            conf: ValidationConfig {
+                username_max: conf.username_max.min(64),
+                ..conf.clone()
+            },
+            username,
+            reserved: conf
+                .reserved_names
+                .iter()
+                .map(|name| name.to_lowercase())
+                .collect(),
+            breached,
+        }
+    }
+
+    pub fn validate_username(&self, username: &str) -> Vec<FieldViolation> {
+        let mut violations = vec![];
+        let len = username.chars().count();
+        if len < self.conf.username_min || len > self.conf.username_max {
+            violations.push(FieldViolation::new(
+                "username",
+                format!(
+                    "{} to {} characters",
+                    self.conf.username_min, self.conf.username_max
+                ),
+            ));
+        }
+        if !self.username.is_match(username) {
+            violations.push(FieldViolation::new(
+                "username",
+                format!("characters in [{}]", self.conf.username_charset),
+            ));
+        }
+        if self.reserved.contains(&username.to_lowercase()) {
+            violations.push(FieldViolation::new("username", "not a reserved name"));
+        }
+        // otherwise the username is shadowed by an id, email or phone when logging in
+        if IdentifierKind::of(username) != IdentifierKind::Username {
+            violations.push(FieldViolation::new(
+                "username",
+                "not in the format of an id, email or phone",
+            ));
+        }
+        violations
+    }
+
+    pub fn validate_password(&self, username: &str, password: &str) -> Vec<FieldViolation> {
+        let mut violations = vec![];
+        let len = password.chars().count();
+        if len < self.conf.password_min || len > self.conf.password_max {
+            violations.push(FieldViolation::new(
+                "password",
+                format!(
+                    "{} to {} characters",
+                    self.conf.password_min, self.conf.password_max
+                ),
+            ));
+        }
+        let classes = [
+            password.chars().any(|c| c.is_ascii_lowercase()),
+            password.chars().any(|c| c.is_ascii_uppercase()),
+            password.chars().any(|c| c.is_ascii_digit()),
+            password.chars().any(|c| !c.is_ascii_alphanumeric()),
+        ]
+        .iter()
+        .filter(|class| **class)
+        .count();
+        if classes < self.conf.password_classes {
+            violations.push(FieldViolation::new(
+                "password",
+                format!(
+                    "at least {} kinds of lowercase, uppercase, digit and symbol",
+                    self.conf.password_classes
+                ),
+            ));
+        }
+        if password.eq_ignore_ascii_case(username) {
+            violations.push(FieldViolation::new("password", "different from username"));
+        }
+        if self.breached.contains(password) || self.breached.contains(&password.to_lowercase()) {
+            violations.push(FieldViolation::new("password", "not a breached password"));
+        }
+        violations
+    }
+
+    pub fn validate(&self, username: &str, password: &str) -> Vec<FieldViolation> {
+        let mut violations = self.validate_username(username);
+        violations.extend(self.validate_password(username, password));
+        violations
+    }
+}
+
+/// Convert violations into an `INVALID_ARGUMENT` status, every violation
+/// is attached as a `BadRequest` field violation of the status details.
+pub fn violations_status(violations: &[FieldViolation]) -> Status {
+    let message = violations
+        .iter()
+        .map(|v| format!("Request field {} is invalid, expect {}", v.field, v.expect))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let mut details = ErrorDetails::new();
+    for violation in violations {
+        details.add_bad_request_violation(violation.field, &violation.expect);
+    }
+    Status::with_error_details(Code::InvalidArgument, message, details)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shadowed(validator: &RegisterValidator, username: &str) -> bool {
+        validator
+            .validate_username(username)
+            .iter()
+            .any(|violation| violation.expect == "not in the format of an id, email or phone")
+    }
+
+    #[test]
+    fn reject_username_like_identifier() {
+        let validator = RegisterValidator::new(&ValidationConfig {
+            username_charset: "a-zA-Z0-9_@.+".to_string(),
+            ..Default::default()
+        });
+        assert!(shadowed(&validator, "10086"));
+        assert!(shadowed(&validator, "13800138000"));
+        assert!(shadowed(&validator, "+8613800138000"));
+        assert!(shadowed(&validator, "alice@example.com"));
+        assert!(!shadowed(&validator, "alice_10086"));
+        assert!(validator.validate_username("alice_10086").is_empty());
+    }
+}
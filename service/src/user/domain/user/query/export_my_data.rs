@@ -0,0 +1,28 @@
+use crate::user::domain::user::model::export::Export;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Query, status::prelude::*};
+use common::{invalid_argument, not_found};
+use diesel::PgConnection;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ExportMyDataReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ExportMyDataRes> {
+    let format = pb::ExportFormat::from_i32(req.format)
+        .ok_or_else(|| invalid_argument!("format", "JSON or ZIP"))?;
+    let user = User::query_identifier(&req.identifier, conn)?;
+    if user.is_deleted() {
+        return Err(not_found!(format!("user({})", req.identifier)).into());
+    }
+    Export::collect(&user, conn)?.archive(format)
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_export_my_data(&self) -> impl Query<pb::ExportMyDataReq> + '_ {
+        move |req: pb::ExportMyDataReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
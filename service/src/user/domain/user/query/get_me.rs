@@ -0,0 +1,18 @@
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::GetMeReq, conn: &mut PgConnection) -> GrpcResult<pb::GetMeRes> {
+    let user = User::query_identifier(&req.identifier, conn)?;
+    Ok(user.private_profile())
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_get_me(&self) -> impl Query<pb::GetMeReq> + '_ {
+        move |req: pb::GetMeReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
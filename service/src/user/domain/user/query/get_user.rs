@@ -0,0 +1,24 @@
+use crate::user::domain::user::model::user::{User, UserId};
+use crate::user::rpc::UserResolver;
+use common::not_found;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::GetUserReq, conn: &mut PgConnection) -> GrpcResult<pb::GetUserRes> {
+    let user = User::query_id(UserId::from(req.id as u64), conn)?;
+    if user.is_deleted() {
+        return Err(not_found!(format!("user({})", req.id)).into());
+    }
+    Ok(pb::GetUserRes {
+        profile: Some(user.public_profile()),
+    })
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_get_user(&self) -> impl Query<pb::GetUserReq> + '_ {
+        move |req: pb::GetUserReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::user::domain::user::model::login_event::LoginEvent;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::ListLoginHistoryReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::ListLoginHistoryRes> {
+    let user = User::query_identifier(&req.identifier, conn)?;
+    let (limit, offset) = req
+        .slice
+        .unwrap_or_default()
+        .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+    let (events, total) = LoginEvent::list(user.id(), limit, offset, conn)?;
+    Ok(pb::ListLoginHistoryRes {
+        events: events.iter().map(LoginEvent::to_pb).collect(),
+        total,
+    })
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_list_login_history(
+        &self,
+    ) -> impl Query<pb::ListLoginHistoryReq> + '_ {
+        move |req: pb::ListLoginHistoryReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
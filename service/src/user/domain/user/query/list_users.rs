@@ -0,0 +1,19 @@
+use crate::user::domain::user::model::admin::{Admin, UserFilter};
+use crate::user::rpc::UserResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(req: pb::ListUsersReq, conn: &mut PgConnection) -> GrpcResult<pb::ListUsersRes> {
+    let admin = Admin::authorize(req.admin_id, conn)?;
+    let filter = UserFilter::parse(req)?;
+    admin.list_users(&filter, conn)
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_list_users(&self) -> impl Query<pb::ListUsersReq> + '_ {
+        move |req: pb::ListUsersReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -1,5 +1,10 @@
-use crate::user::domain::user::model::user::User;
+use crate::user::domain::user::model::attempt::LoginGuard;
+use crate::user::domain::user::model::ban::Ban;
+use crate::user::domain::user::model::login_event::LoginAttempt;
+use crate::user::domain::user::model::totp::{PendingLogin, Totp};
+use crate::user::domain::user::model::user::{IdentifierKind, User};
 use crate::user::rpc::UserResolver;
+use common::invalid_argument;
 use common::{infra::Query, status::prelude::*};
 use diesel::PgConnection;
 use proto::pb::auth::token::v1::token_service_client::TokenServiceClient;
@@ -11,10 +16,53 @@ use tonic::transport::Channel;
 async fn execute(
     req: pb::LoginReq,
     secret: &str,
+    guard: LoginGuard<'_>,
     conn: &mut PgConnection,
+    redis: &mut redis::Connection,
     client: TokenServiceClient<Channel>,
 ) -> GrpcResult<pb::LoginRes> {
-    let user = User::login(&req.identifier, &req.password, secret, conn)?;
+    let ip = req.client_ip.as_deref();
+    let kind = IdentifierKind::parse(&req.identifier, req.identifier_type)?;
+    // failures are counted against the owner, so that every identifier of it shares a counter
+    let owner = User::query_kind(&req.identifier, &kind, conn).ok();
+    let account = owner
+        .as_ref()
+        .map_or_else(|| kind.canonical(&req.identifier), User::account);
+    guard.check(&account, ip)?;
+    let attempt = LoginAttempt::new(&kind, ip, req.user_agent.as_deref());
+    let user = match User::login(&req.identifier, &kind, &req.password, secret, conn)? {
+        Some(user) => user,
+        None => {
+            guard.failed(&account, ip)?;
+            // the history belongs to the owner, attempts of unknown identifiers are dropped
+            if let Some(owner) = owner {
+                attempt.record(owner.id(), false, conn)?;
+            }
+            // one error for both wrong identifier and wrong password
+            return Err(invalid_argument!("credential", "correct identifier and password").into());
+        }
+    };
+    // only told after the password is checked, so that a ban does not leak the account
+    if let Some(ban) = Ban::active(user.id(), conn)? {
+        return Err(ban.status().into());
+    }
+    if Totp::is_enabled(user.id(), conn)? {
+        // the login succeeds and the token pair is signed after the second factor is verified
+        let pending = PendingLogin {
+            uid: user.id(),
+            attempt,
+        };
+        return Ok(pb::LoginRes {
+            access: None,
+            refresh: None,
+            challenge: Some(Totp::challenge(&pending, redis)?),
+        });
+    }
+    guard.succeeded(&account)?;
+    if attempt.is_new_device(user.id(), conn)? {
+        attempt.notify_new_device(user.id(), redis);
+    }
+    attempt.record(user.id(), true, conn)?;
     let result = user.sign_token_pair(client).await;
     result
 }
@@ -25,7 +73,9 @@ impl UserResolver {
             execute(
                 req,
                 self.hash_secret(),
+                LoginGuard::new(self.attempt_store(), self.login_guard_conf()),
                 self.pg_conn().deref_mut(),
+                self.redis_conn().deref_mut(),
                 self.token_client(),
             )
             .await
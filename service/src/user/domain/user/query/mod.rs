@@ -1 +1,7 @@
+pub mod export_my_data;
+pub mod get_me;
+pub mod get_user;
+pub mod list_login_history;
+pub mod list_users;
 pub mod login;
+pub mod search_users;
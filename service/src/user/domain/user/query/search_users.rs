@@ -0,0 +1,38 @@
+use crate::user::domain::follow::model::block::Block;
+use crate::user::domain::user::model::search::UserSearch;
+use crate::user::domain::user::model::user::User;
+use crate::user::rpc::UserResolver;
+use common::{infra::Query, status::prelude::*};
+use diesel::PgConnection;
+use proto::pb::user::sys::v1 as pb;
+use std::ops::DerefMut;
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 50;
+
+#[tracing::instrument(skip_all, err)]
+async fn execute(
+    req: pb::SearchUsersReq,
+    conn: &mut PgConnection,
+) -> GrpcResult<pb::SearchUsersRes> {
+    let hidden = match &req.viewer {
+        Some(viewer) => Block::hidden_from(User::query_identifier(viewer, conn)?.id(), conn)?,
+        None => vec![],
+    };
+    let search = UserSearch::new(&req.keyword, req.order, hidden)?;
+    let (limit, offset) = req
+        .slice
+        .unwrap_or_default()
+        .limit_offset(DEFAULT_LIMIT, MAX_LIMIT);
+    let (users, total) = search.search(limit, offset, conn)?;
+    Ok(pb::SearchUsersRes {
+        users: users.iter().map(User::public_profile).collect(),
+        total,
+    })
+}
+
+impl UserResolver {
+    pub(in crate::user) fn create_search_users(&self) -> impl Query<pb::SearchUsersReq> + '_ {
+        move |req: pb::SearchUsersReq| async move { execute(req, self.pg_conn().deref_mut()).await }
+    }
+}
@@ -0,0 +1,27 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    group: Extension<GroupId>,
+    Path(id): Path<i64>,
+    Form(BanUserReq { reason, expires_at }): Form<BanUserReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = async {
+        let admin_id = admin_id(&uid, &group)?;
+        resolver
+            .user_admin_client()
+            .ban_user(pb::BanUserReq {
+                admin_id,
+                uid: id,
+                reason,
+                expires_at,
+            })
+            .await
+            .map(|_| ())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
@@ -16,6 +16,8 @@ pub(crate) async fn handle(
             email,
             phone,
             github: github.map(|v| v.to_string()),
+            // the identifier is always the id of current user
+            identifier_type: Some(pb::IdentifierType::Id as i32),
         })
         .await
         .map(|_| ())
@@ -0,0 +1,18 @@
+use super::*;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Form(ConfirmTotpReq { code }): Form<ConfirmTotpReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = resolver
+        .user_client()
+        .confirm_totp(pb::ConfirmTotpReq {
+            identifier: uid.as_string(),
+            code,
+        })
+        .await
+        .map(|_| ())
+        .map_err(HttpStatus::from);
+    (resp.http_code(), Json(resp.into()))
+}
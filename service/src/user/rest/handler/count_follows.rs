@@ -0,0 +1,15 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<Resp<pb::CountFollowsRes>>) {
+    let resp = resolver
+        .user_client()
+        .count_follows(pb::CountFollowsReq { uid: id })
+        .await
+        .map(|res| res.into_inner())
+        .map_err(HttpStatus::from);
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,18 @@
+use super::*;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Form(DeleteAccountReq { password }): Form<DeleteAccountReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = resolver
+        .user_client()
+        .delete_account(pb::DeleteAccountReq {
+            identifier: uid.as_string(),
+            password,
+        })
+        .await
+        .map(|_| ())
+        .map_err(HttpStatus::from);
+    (resp.http_code(), Json(resp.into()))
+}
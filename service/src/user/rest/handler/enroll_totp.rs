@@ -0,0 +1,16 @@
+use super::*;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+) -> (StatusCode, Json<Resp<pb::EnrollTotpRes>>) {
+    let resp = resolver
+        .user_client()
+        .enroll_totp(pb::EnrollTotpReq {
+            identifier: uid.as_string(),
+        })
+        .await
+        .map(|res| res.into_inner())
+        .map_err(HttpStatus::from);
+    (resp.http_code(), Json(resp.into()))
+}
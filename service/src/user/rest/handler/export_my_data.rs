@@ -0,0 +1,51 @@
+use super::*;
+use axum::extract::Query;
+use axum::response::{IntoResponse, Response};
+use http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+
+// the archive is responded as an attachment rather than a json body
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Query(ExportMyDataReq { format }): Query<ExportMyDataReq>,
+) -> Response {
+    let resp = async {
+        let format = match format.as_deref() {
+            None | Some("json") => pb::ExportFormat::Json,
+            Some("zip") => pb::ExportFormat::Zip,
+            Some(_) => {
+                return Err(HttpStatus::from(Status::invalid_argument(
+                    "Request field format is invalid, expect json or zip",
+                )))
+            }
+        };
+        resolver
+            .user_client()
+            .export_my_data(pb::ExportMyDataReq {
+                identifier: uid.as_string(),
+                format: format as i32,
+            })
+            .await
+            .map(|res| res.into_inner())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    match resp {
+        Ok(res) => (
+            StatusCode::OK,
+            [
+                (CONTENT_TYPE, res.content_type),
+                (
+                    CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", res.filename),
+                ),
+            ],
+            res.data,
+        )
+            .into_response(),
+        Err(e) => {
+            let resp: Result<(), HttpStatus> = Err(e);
+            (resp.http_code(), Json(resp.into())).into_response()
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    group: Extension<GroupId>,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = async {
+        let admin_id = admin_id(&uid, &group)?;
+        resolver
+            .user_admin_client()
+            .force_logout(pb::ForceLogoutReq { admin_id, uid: id })
+            .await
+            .map(|_| ())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
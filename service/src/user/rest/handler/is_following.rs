@@ -0,0 +1,32 @@
+use super::*;
+use axum::extract::Query;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Query(IsFollowingReq { uids }): Query<IsFollowingReq>,
+) -> (StatusCode, Json<Resp<pb::IsFollowingRes>>) {
+    let resp = async {
+        let uids = uids
+            .split(',')
+            .filter(|id| !id.trim().is_empty())
+            .map(|id| id.trim().parse::<i64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| {
+                HttpStatus::from(Status::invalid_argument(
+                    "Request field uids is invalid, expect ids separated by comma",
+                ))
+            })?;
+        resolver
+            .user_client()
+            .is_following(pb::IsFollowingReq {
+                identifier: uid.as_string(),
+                uids,
+            })
+            .await
+            .map(|res| res.into_inner())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,28 @@
+use super::*;
+use axum::extract::{Path, Query};
+use proto::pb::common::v1::{ByPage, Slice};
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    viewer: Option<Extension<UserId>>,
+    Path(id): Path<i64>,
+    Query(ListFollowsReq { page, per_page }): Query<ListFollowsReq>,
+) -> (StatusCode, Json<Resp<pb::ListFollowsRes>>) {
+    let resp = resolver
+        .user_client()
+        .list_following(pb::ListFollowsReq {
+            uid: id,
+            viewer: viewer.map(|uid| uid.as_string()),
+            slice: Some(Slice {
+                limit: None,
+                page: Some(ByPage {
+                    page: page.unwrap_or(1),
+                    per_page: per_page.unwrap_or_default(),
+                }),
+            }),
+        })
+        .await
+        .map(|res| res.into_inner())
+        .map_err(HttpStatus::from);
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,26 @@
+use super::*;
+use axum::extract::Query;
+use proto::pb::common::v1::{ByPage, Slice};
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Query(ListLoginHistoryReq { page, per_page }): Query<ListLoginHistoryReq>,
+) -> (StatusCode, Json<Resp<pb::ListLoginHistoryRes>>) {
+    let resp = resolver
+        .user_client()
+        .list_login_history(pb::ListLoginHistoryReq {
+            identifier: uid.as_string(),
+            slice: Some(Slice {
+                limit: None,
+                page: Some(ByPage {
+                    page: page.unwrap_or(1),
+                    per_page: per_page.unwrap_or_default(),
+                }),
+            }),
+        })
+        .await
+        .map(|res| res.into_inner())
+        .map_err(HttpStatus::from);
+    (resp.http_code(), Json(resp.into()))
+}
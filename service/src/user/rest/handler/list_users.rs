@@ -0,0 +1,46 @@
+use super::*;
+use axum::extract::Query;
+use proto::pb::common::v1::{ByPage, Slice};
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    group: Extension<GroupId>,
+    Query(ListUsersReq {
+        role_group,
+        created_start,
+        created_end,
+        last_login_start,
+        last_login_end,
+        banned,
+        page,
+        per_page,
+    }): Query<ListUsersReq>,
+) -> (StatusCode, Json<Resp<pb::ListUsersRes>>) {
+    let resp = async {
+        let admin_id = admin_id(&uid, &group)?;
+        resolver
+            .user_admin_client()
+            .list_users(pb::ListUsersReq {
+                admin_id,
+                role_group,
+                created_start,
+                created_end,
+                last_login_start,
+                last_login_end,
+                banned,
+                slice: Some(Slice {
+                    limit: None,
+                    page: Some(ByPage {
+                        page: page.unwrap_or(1),
+                        per_page: per_page.unwrap_or_default(),
+                    }),
+                }),
+            })
+            .await
+            .map(|res| res.into_inner())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
@@ -3,49 +3,82 @@ use crate::auth::layer::KEY;
 use common::layer::write_cookie;
 use cookie::time::Duration;
 use cookie::{Cookie, CookieJar};
-use http::HeaderMap;
 
 pub(crate) async fn handle(
     State(resolver): State<Arc<RestResolver>>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
     Form(LoginReq {
         identifier,
+        identifier_type,
         password,
     }): Form<LoginReq>,
 ) -> (StatusCode, HeaderMap, Json<Resp<pb::LoginRes>>) {
-    let resp = resolver
-        .user_client()
-        .login(pb::LoginReq {
-            identifier,
-            password,
-        })
-        .await
-        .map(|res| res.into_inner())
-        .map_err(HttpStatus::from);
+    let resp = async {
+        let identifier_type = match identifier_type {
+            None => None,
+            Some(typ) => Some(
+                pb::IdentifierType::from_str_name(&typ.to_uppercase()).ok_or_else(|| {
+                    HttpStatus::from(Status::invalid_argument(
+                        "Request field identifier_type is invalid, expect auto, id, email, phone or username",
+                    ))
+                })? as i32,
+            ),
+        };
+        resolver
+            .user_client()
+            .login(pb::LoginReq {
+                identifier,
+                password,
+                client_ip: client_ip(&headers, connect_info, &resolver.conf.trusted_proxies),
+                user_agent: headers
+                    .get(http::header::USER_AGENT)
+                    .and_then(|value| value.to_str().ok())
+                    .map(ToString::to_string),
+                identifier_type,
+            })
+            .await
+            .map(|res| res.into_inner())
+            .map_err(HttpStatus::from)
+    }
+    .await;
     let mut header_map = HeaderMap::default();
-    let mut cookie_jar = CookieJar::new();
     if let Ok(ref res) = resp {
-        let conf = resolver.conf.cookie_conf.clone();
-        let cookie = Cookie::build(
-            conf.cookie_name,
-            format!(
-                "{}|{}",
-                res.access.clone().expect("Gateway error").value, // None indicates that something wrong with gateway
-                res.refresh.clone().expect("Gateway error").value
-            ),
-        )
-        .domain(conf.domain)
-        .max_age(Duration::seconds(conf.max_age))
-        .same_site(conf.same_site.into())
-        .path(conf.path)
-        .http_only(conf.http_only)
-        .secure(conf.secure)
-        .finish();
-        if let Some(key) = KEY.get() {
-            cookie_jar.private_mut(key).add(cookie);
-        } else {
-            cookie_jar.add(cookie);
-        }
+        write_token_cookie(&resolver, res, &mut header_map);
     }
-    write_cookie(&mut header_map, &cookie_jar);
     (resp.http_code(), header_map, Json(resp.into()))
 }
+
+// write token pair into cookie, nothing is written if 2FA challenge is pending
+pub(crate) fn write_token_cookie(
+    resolver: &RestResolver,
+    res: &pb::LoginRes,
+    header_map: &mut HeaderMap,
+) {
+    if res.challenge.is_some() {
+        return;
+    }
+    let mut cookie_jar = CookieJar::new();
+    let conf = resolver.conf.cookie_conf.clone();
+    let cookie = Cookie::build(
+        conf.cookie_name,
+        format!(
+            "{}|{}",
+            res.access.clone().expect("Gateway error").value, // None indicates that something wrong with gateway
+            res.refresh.clone().expect("Gateway error").value
+        ),
+    )
+    .domain(conf.domain)
+    .max_age(Duration::seconds(conf.max_age))
+    .same_site(conf.same_site.into())
+    .path(conf.path)
+    .http_only(conf.http_only)
+    .secure(conf.secure)
+    .finish();
+    if let Some(key) = KEY.get() {
+        cookie_jar.private_mut(key).add(cookie);
+    } else {
+        cookie_jar.add(cookie);
+    }
+    write_cookie(header_map, &cookie_jar);
+}
@@ -1,12 +1,79 @@
+pub(crate) mod ban_user;
 pub(crate) mod bind;
+pub(crate) mod block;
+pub(crate) mod confirm_totp;
+pub(crate) mod count_follows;
+pub(crate) mod delete_account;
+pub(crate) mod enroll_totp;
+pub(crate) mod export_my_data;
+pub(crate) mod follow;
+pub(crate) mod force_logout;
+pub(crate) mod get_me;
+pub(crate) mod get_user;
+pub(crate) mod is_following;
+pub(crate) mod list_followers;
+pub(crate) mod list_following;
+pub(crate) mod list_login_history;
+pub(crate) mod list_users;
 pub(crate) mod login;
 pub(crate) mod register;
+pub(crate) mod search_users;
+pub(crate) mod set_role_group;
+pub(crate) mod unban_user;
+pub(crate) mod unblock;
+pub(crate) mod unfollow;
+pub(crate) mod update_profile;
+pub(crate) mod verify_totp;
 
 use crate::user::rest::types::*;
 use crate::user::rest::RestResolver;
-use axum::extract::State;
+use crate::user::rpc::RoleGroup;
+use axum::extract::{ConnectInfo, State};
 use axum::*;
 use common::status::prelude::*;
-use http::StatusCode;
+use http::{HeaderMap, StatusCode};
 use proto::pb::user::sys::v1 as pb;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use tonic::Status;
+
+// the ip of the end user. Headers set by a reverse proxy are only read if the peer is a
+// trusted proxy, otherwise a client could send a new ip with every request. The last
+// address of x-forwarded-for not being a trusted proxy is the one appended by them.
+pub(crate) fn client_ip(
+    headers: &HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    trusted_proxies: &[IpAddr],
+) -> Option<String> {
+    let peer = connect_info.map(|ConnectInfo(addr)| addr.ip())?;
+    if !trusted_proxies.contains(&peer) {
+        return Some(peer.to_string());
+    }
+    let forwarded = headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value
+                .rsplit(',')
+                .map(|ip| ip.trim().parse::<IpAddr>())
+                .find(|ip| !matches!(ip, Ok(ip) if trusted_proxies.contains(ip)))
+        })
+        .and_then(Result::ok);
+    let real = || {
+        headers
+            .get("x-real-ip")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<IpAddr>().ok())
+    };
+    Some(forwarded.or_else(real).unwrap_or(peer).to_string())
+}
+
+// admins are checked again against the database by the rpc service, this only rejects others early
+pub(crate) fn admin_id(uid: &UserId, group: &GroupId) -> Result<i64, HttpStatus> {
+    if group.as_string() != RoleGroup::ADMIN.name() {
+        return Err(HttpStatus::from(Status::permission_denied("Admin only")));
+    }
+    uid.as_string()
+        .parse()
+        .map_err(|_| HttpStatus::from(Status::unauthenticated("Invalid user id")))
+}
@@ -1,9 +1,16 @@
 use super::*;
+use crate::user::domain::user::model::validation::violations_status;
 
 pub(crate) async fn handle(
     State(resolver): State<Arc<RestResolver>>,
     Form(RegisterReq { username, password }): Form<RegisterReq>,
 ) -> (StatusCode, Json<Resp<()>>) {
+    let violations = resolver.register_validator().validate(&username, &password);
+    if !violations.is_empty() {
+        let resp: Result<(), HttpStatus> = Err(violations_status(&violations).into());
+        return (resp.http_code(), Json(resp.into()));
+    }
+
     let resp = resolver
         .user_client()
         .register(pb::RegisterReq { username, password })
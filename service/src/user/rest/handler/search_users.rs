@@ -0,0 +1,45 @@
+use super::*;
+use axum::extract::Query;
+use proto::pb::common::v1::{ByPage, Slice};
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    viewer: Option<Extension<UserId>>,
+    Query(SearchUsersReq {
+        keyword,
+        order,
+        page,
+        per_page,
+    }): Query<SearchUsersReq>,
+) -> (StatusCode, Json<Resp<pb::SearchUsersRes>>) {
+    let resp = async {
+        let order = match order.as_deref() {
+            None | Some("popular") => pb::SearchOrder::Popular,
+            Some("recent") => pb::SearchOrder::Recent,
+            Some(_) => {
+                return Err(HttpStatus::from(Status::invalid_argument(
+                    "Request field order is invalid, expect popular or recent",
+                )))
+            }
+        };
+        resolver
+            .user_client()
+            .search_users(pb::SearchUsersReq {
+                keyword,
+                order: order as i32,
+                viewer: viewer.map(|uid| uid.as_string()),
+                slice: Some(Slice {
+                    limit: None,
+                    page: Some(ByPage {
+                        page: page.unwrap_or(1),
+                        per_page: per_page.unwrap_or_default(),
+                    }),
+                }),
+            })
+            .await
+            .map(|res| res.into_inner())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,26 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    group: Extension<GroupId>,
+    Path(id): Path<i64>,
+    Form(SetRoleGroupReq { role_group }): Form<SetRoleGroupReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = async {
+        let admin_id = admin_id(&uid, &group)?;
+        resolver
+            .user_admin_client()
+            .set_role_group(pb::SetRoleGroupReq {
+                admin_id,
+                uid: id,
+                role_group,
+            })
+            .await
+            .map(|_| ())
+            .map_err(HttpStatus::from)
+    }
+    .await;
+    (resp.http_code(), Json(resp.into()))
+}
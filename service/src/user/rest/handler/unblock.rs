@@ -0,0 +1,19 @@
+use super::*;
+use axum::extract::Path;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = resolver
+        .user_client()
+        .unblock(pb::UnblockReq {
+            identifier: uid.as_string(),
+            uid: id,
+        })
+        .await
+        .map(|_| ())
+        .map_err(HttpStatus::from);
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,28 @@
+use super::*;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    uid: Extension<UserId>,
+    Form(UpdateProfileReq {
+        nickname,
+        avatar_url,
+        bio,
+        gender,
+        location,
+    }): Form<UpdateProfileReq>,
+) -> (StatusCode, Json<Resp<()>>) {
+    let resp = resolver
+        .user_client()
+        .update_profile(pb::UpdateProfileReq {
+            identifier: uid.as_string(),
+            nickname,
+            avatar_url,
+            bio,
+            gender,
+            location,
+        })
+        .await
+        .map(|_| ())
+        .map_err(HttpStatus::from);
+    (resp.http_code(), Json(resp.into()))
+}
@@ -0,0 +1,19 @@
+use super::*;
+use crate::user::rest::handler::login::write_token_cookie;
+
+pub(crate) async fn handle(
+    State(resolver): State<Arc<RestResolver>>,
+    Form(VerifyTotpReq { challenge, code }): Form<VerifyTotpReq>,
+) -> (StatusCode, HeaderMap, Json<Resp<pb::LoginRes>>) {
+    let resp = resolver
+        .user_client()
+        .verify_totp(pb::VerifyTotpReq { challenge, code })
+        .await
+        .map(|res| res.into_inner())
+        .map_err(HttpStatus::from);
+    let mut header_map = HeaderMap::default();
+    if let Ok(ref res) = resp {
+        write_token_cookie(&resolver, res, &mut header_map);
+    }
+    (resp.http_code(), header_map, Json(resp.into()))
+}
@@ -1,3 +1,5 @@
+use crate::user::domain::user::model::phone;
+use crate::user::domain::user::model::validation::{RegisterValidator, ValidationConfig};
 use crate::user::rest::error::handle_error;
 use crate::user::rpc::UserResolver;
 use axum::error_handling::HandleErrorLayer;
@@ -7,8 +9,10 @@ use common::config::service::ServiceConfig;
 use common::config::Config;
 use common::infra::*;
 use common::registry::{EtcdRegistry, ServiceDiscover};
+use proto::pb::user::sys::v1::user_admin_service_client::UserAdminServiceClient;
 use proto::pb::user::sys::v1::user_service_client::UserServiceClient;
 use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 use tonic::transport::Channel;
 use tower::load_shed::LoadShedLayer;
@@ -20,7 +24,11 @@ mod handler;
 mod router;
 mod types;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+fn default_phone_region() -> String {
+    "CN".to_string()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RestConfig {
     #[serde(default)]
     pub service_conf: <Config as ServiceConfig>::RestService,
@@ -28,12 +36,39 @@ pub struct RestConfig {
     pub etcd: <Config as MiddlewareConfig>::Etcd,
     #[serde(default)]
     pub cookie_conf: <Config as LayerConfig>::CookieAuth,
+    // same as the user rpc service, requests are validated before calling rpc
+    #[serde(default)]
+    pub validation: ValidationConfig,
+    // addresses of the reverse proxies, x-forwarded-for and x-real-ip are only trusted
+    // from them, so that a client cannot pick its ip for the login throttling
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+    // same as the user rpc service, identifiers are resolved before calling rpc
+    #[serde(default = "default_phone_region")]
+    pub phone_region: String,
+}
+
+impl Default for RestConfig {
+    fn default() -> Self {
+        Self {
+            service_conf: Default::default(),
+            etcd: Default::default(),
+            cookie_conf: Default::default(),
+            validation: Default::default(),
+            trusted_proxies: Default::default(),
+            phone_region: default_phone_region(),
+        }
+    }
 }
 
+type Register<T> = common::config::register::Register<RestConfig, T>;
+
 #[derive(Clone)]
 pub struct RestResolver {
     conf: RestConfig,
     user_client: UserServiceClient<Channel>,
+    user_admin_client: UserAdminServiceClient<Channel>,
+    register_validator: Register<&'static RegisterValidator>,
 }
 
 impl Resolver for RestResolver {
@@ -55,14 +90,29 @@ impl RestResolver {
             .discover_to_channel(&service_key, tx)
             .await
             .expect("Cannot discover user service to channel");
-        let user_client = UserServiceClient::new(channel);
-        Self { conf, user_client }
+        let user_client = UserServiceClient::new(channel.clone());
+        let user_admin_client = UserAdminServiceClient::new(channel);
+        phone::init_default_region(&conf.phone_region);
+        Self {
+            conf,
+            user_client,
+            user_admin_client,
+            register_validator: Register::once_ref(|conf| RegisterValidator::new(&conf.validation)),
+        }
     }
 
     pub fn user_client(&self) -> UserServiceClient<Channel> {
         self.user_client.clone()
     }
 
+    pub fn user_admin_client(&self) -> UserAdminServiceClient<Channel> {
+        self.user_admin_client.clone()
+    }
+
+    pub fn register_validator(&self) -> &'static RegisterValidator {
+        self.resolve(&self.register_validator)
+    }
+
     pub async fn serve(&self) {
         let addr = self.conf.service_conf.service.listen_addr.parse().unwrap();
         axum::Server::bind(&addr)
@@ -82,7 +132,7 @@ impl RestResolver {
                             })
                             .concurrency_limit(self.conf.service_conf.service.concurrency_limit),
                     )
-                    .into_make_service(),
+                    .into_make_service_with_connect_info::<SocketAddr>(),
             )
             .with_graceful_shutdown(async {
                 let _ = tokio::signal::ctrl_c().await;
@@ -1,10 +1,33 @@
 use crate::auth::layer::{AuthBuilder, WWWAuth};
+use crate::user::rest::handler::ban_user;
 use crate::user::rest::handler::bind;
+use crate::user::rest::handler::block;
+use crate::user::rest::handler::confirm_totp;
+use crate::user::rest::handler::count_follows;
+use crate::user::rest::handler::delete_account;
+use crate::user::rest::handler::enroll_totp;
+use crate::user::rest::handler::export_my_data;
+use crate::user::rest::handler::follow;
+use crate::user::rest::handler::force_logout;
+use crate::user::rest::handler::get_me;
+use crate::user::rest::handler::get_user;
+use crate::user::rest::handler::is_following;
+use crate::user::rest::handler::list_followers;
+use crate::user::rest::handler::list_following;
+use crate::user::rest::handler::list_login_history;
+use crate::user::rest::handler::list_users;
 use crate::user::rest::handler::login;
 use crate::user::rest::handler::register;
+use crate::user::rest::handler::search_users;
+use crate::user::rest::handler::set_role_group;
+use crate::user::rest::handler::unban_user;
+use crate::user::rest::handler::unblock;
+use crate::user::rest::handler::unfollow;
+use crate::user::rest::handler::update_profile;
+use crate::user::rest::handler::verify_totp;
 use crate::user::rest::types::IdProvider;
 use crate::user::rest::RestResolver;
-use axum::routing::post;
+use axum::routing::{get, post};
 use axum::Router;
 use common::infra::Resolver;
 use common::layer::AsyncHttpAuthLayer;
@@ -21,13 +44,39 @@ impl RestResolver {
             ))
             .finish::<IdProvider, _>()
             .await;
-        let bind_router = Router::new()
+        let auth_router = Router::new()
             .route("/bind", post(bind::handle))
-            .layer(ServiceBuilder::new().layer(AsyncHttpAuthLayer::new(auth)));
+            .route("/totp/enroll", post(enroll_totp::handle))
+            .route("/totp/confirm", post(confirm_totp::handle))
+            .route("/me", get(get_me::handle).post(update_profile::handle))
+            .route("/me/delete", post(delete_account::handle))
+            .route("/me/export", get(export_my_data::handle))
+            .route("/me/logins", get(list_login_history::handle))
+            .route("/me/following", get(is_following::handle))
+            .route("/users/:id/follow", post(follow::handle))
+            .route("/users/:id/unfollow", post(unfollow::handle))
+            .route("/users/:id/block", post(block::handle))
+            .route("/users/:id/unblock", post(unblock::handle))
+            .route("/admin/users", get(list_users::handle))
+            .route("/admin/users/:id/role", post(set_role_group::handle))
+            .route("/admin/users/:id/ban", post(ban_user::handle))
+            .route("/admin/users/:id/unban", post(unban_user::handle))
+            .route("/admin/users/:id/logout", post(force_logout::handle))
+            .layer(ServiceBuilder::new().layer(AsyncHttpAuthLayer::new(auth.clone())));
+        // public, the blocks of the viewer are applied if a session is present
+        let viewer_router = Router::new()
+            .route("/search/users", get(search_users::handle))
+            .route("/users/:id/followers", get(list_followers::handle))
+            .route("/users/:id/following", get(list_following::handle))
+            .layer(ServiceBuilder::new().layer(AsyncHttpAuthLayer::new(auth.optional())));
         Router::new()
             .route("/register", post(register::handle))
             .route("/login", post(login::handle))
-            .merge(bind_router)
+            .route("/totp/verify", post(verify_totp::handle))
+            .route("/users/:id", get(get_user::handle))
+            .route("/users/:id/follows", get(count_follows::handle))
+            .merge(viewer_router)
+            .merge(auth_router)
             .with_state(Arc::new(self.clone()))
     }
 }
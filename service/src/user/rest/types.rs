@@ -11,6 +11,8 @@ pub(crate) struct RegisterReq {
 #[derive(Deserialize, Debug)]
 pub(crate) struct LoginReq {
     pub(crate) identifier: String,
+    // one of AUTO, ID, EMAIL, PHONE, USERNAME, detected if absent
+    pub(crate) identifier_type: Option<String>,
     pub(crate) password: String,
 }
 
@@ -21,6 +23,87 @@ pub(crate) struct BindReq {
     pub(crate) github: Option<i64>,
 }
 
+#[derive(Deserialize, Debug)]
+pub(crate) struct ConfirmTotpReq {
+    pub(crate) code: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct VerifyTotpReq {
+    pub(crate) challenge: String,
+    pub(crate) code: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct UpdateProfileReq {
+    pub(crate) nickname: Option<String>,
+    pub(crate) avatar_url: Option<String>,
+    pub(crate) bio: Option<String>,
+    pub(crate) gender: Option<String>,
+    pub(crate) location: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct DeleteAccountReq {
+    pub(crate) password: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct ExportMyDataReq {
+    // json (default) or zip
+    pub(crate) format: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct ListLoginHistoryReq {
+    pub(crate) page: Option<i32>,
+    pub(crate) per_page: Option<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct ListFollowsReq {
+    pub(crate) page: Option<i32>,
+    pub(crate) per_page: Option<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct IsFollowingReq {
+    // ids separated by comma
+    pub(crate) uids: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct SearchUsersReq {
+    pub(crate) keyword: String,
+    // popular (default) or recent
+    pub(crate) order: Option<String>,
+    pub(crate) page: Option<i32>,
+    pub(crate) per_page: Option<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct ListUsersReq {
+    pub(crate) role_group: Option<String>,
+    pub(crate) created_start: Option<i64>,
+    pub(crate) created_end: Option<i64>,
+    pub(crate) last_login_start: Option<i64>,
+    pub(crate) last_login_end: Option<i64>,
+    pub(crate) banned: Option<bool>,
+    pub(crate) page: Option<i32>,
+    pub(crate) per_page: Option<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct SetRoleGroupReq {
+    pub(crate) role_group: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct BanUserReq {
+    pub(crate) reason: String,
+    pub(crate) expires_at: Option<i64>,
+}
+
 #[derive(Clone)]
 pub(crate) struct IdProvider;
 
@@ -0,0 +1,50 @@
+use crate::user::rpc::UserResolver;
+use common::infra::*;
+use proto::pb::common::v1::EmptyRes;
+use proto::pb::user::sys::v1::user_admin_service_server;
+use proto::pb::user::sys::v1::*;
+use tonic::{Request, Response, Status};
+
+pub struct UserAdminService(pub UserResolver);
+
+#[tonic::async_trait]
+impl user_admin_service_server::UserAdminService for UserAdminService {
+    async fn list_users(
+        &self,
+        req: Request<ListUsersReq>,
+    ) -> Result<Response<ListUsersRes>, Status> {
+        let query = self.0.create_list_users();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn set_role_group(
+        &self,
+        req: Request<SetRoleGroupReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_set_role_group();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn ban_user(&self, req: Request<BanUserReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_ban_user();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn unban_user(&self, req: Request<UnbanUserReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_unban_user();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn force_logout(
+        &self,
+        req: Request<ForceLogoutReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_force_logout();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+}
@@ -1,7 +1,16 @@
+pub mod admin;
 pub mod user;
 
 use crate::auth::rpc::TokenResolver;
+use crate::user::domain::user::model::attempt::{
+    AttemptStore, MemoryAttemptStore, RedisAttemptStore,
+};
+use crate::user::domain::user::model::phone;
+use crate::user::domain::user::model::user::User;
+use crate::user::domain::user::model::validation::{RegisterValidator, ValidationConfig};
+use crate::user::rpc::admin::UserAdminService;
 use crate::user::rpc::user::UserService;
+use aes_gcm::{Aes256Gcm, KeyInit};
 use base64::Engine;
 use common::config::env::optional;
 use common::config::middleware::MiddlewareConfig;
@@ -12,11 +21,12 @@ use common::registry::{EtcdRegistry, ServiceDiscover, ServiceRegister};
 use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::PgConnection;
 use proto::pb::auth::token::v1::token_service_client::TokenServiceClient;
+use proto::pb::user::sys::v1::user_admin_service_server::UserAdminServiceServer;
 use proto::pb::user::sys::v1::user_service_server::UserServiceServer;
 use r2d2::PooledConnection;
 use rand::random;
 use serde::{Deserialize, Serialize};
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 use std::time::Duration;
 use tonic::transport::{Channel, Server};
 use tower::load_shed::LoadShedLayer;
@@ -28,10 +38,75 @@ fn random_hash_key() -> String {
     base64::prelude::BASE64_STANDARD.encode(key)
 }
 
+fn default_phone_region() -> String {
+    "CN".to_string()
+}
+
 fn pg_dsn() -> String {
     optional("PG_DB", "postgres://root:@localhost/s_douban_rs")
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AttemptStoreKind {
+    Redis,
+    Memory,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoginGuardConfig {
+    // where the failed attempts are counted, use redis when multiple instances are deployed
+    pub store: AttemptStoreKind,
+    // lock an account after the failures, of both passwords and second factors
+    pub max_failures: u64,
+    // lock a client ip after the failures
+    pub ip_max_failures: u64,
+    // unit (second), failures are forgotten after the window
+    pub window: u64,
+    // unit (second)
+    pub lock: u64,
+    // unit (second), the delay doubles after every failure
+    pub base_delay: u64,
+    // unit (second)
+    pub max_delay: u64,
+}
+
+impl Default for LoginGuardConfig {
+    fn default() -> Self {
+        Self {
+            store: AttemptStoreKind::Redis,
+            max_failures: 5,
+            ip_max_failures: 50,
+            window: 900,
+            lock: 900,
+            base_delay: 1,
+            max_delay: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeletionConfig {
+    // unit (second), deleted accounts are anonymised after the grace period
+    pub grace_period: u64,
+    // unit (second), how often the purge job runs
+    pub purge_interval: u64,
+    // max accounts purged in one run
+    pub purge_batch: i64,
+}
+
+impl Default for DeletionConfig {
+    fn default() -> Self {
+        Self {
+            grace_period: 30 * 24 * 3600,
+            purge_interval: 3600,
+            purge_batch: 100,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserConfig {
     #[serde(default)]
@@ -44,6 +119,18 @@ pub struct UserConfig {
     hash_secret: String,
     #[serde(default = "pg_dsn")]
     pg_dsn: String,
+    // AES-256 key used to encrypt TOTP secrets at rest
+    #[serde(default = "random_hash_key")]
+    totp_key: String,
+    #[serde(default)]
+    login_guard: LoginGuardConfig,
+    #[serde(default)]
+    validation: ValidationConfig,
+    #[serde(default)]
+    deletion: DeletionConfig,
+    // ISO 3166-1 alpha-2 region of phone numbers written without a country code
+    #[serde(default = "default_phone_region")]
+    phone_region: String,
 }
 
 impl Default for UserConfig {
@@ -54,6 +141,11 @@ impl Default for UserConfig {
             etcd: Default::default(),
             hash_secret: random_hash_key(),
             pg_dsn: pg_dsn(),
+            totp_key: random_hash_key(),
+            login_guard: Default::default(),
+            validation: Default::default(),
+            deletion: Default::default(),
+            phone_region: default_phone_region(),
         }
     }
 }
@@ -67,6 +159,9 @@ pub struct UserResolver {
     hash_secret: Register<&'static String>,
     pg_pool: Register<&'static Pool<ConnectionManager<PgConnection>>>,
     redis: Register<&'static r2d2::Pool<redis::Client>>,
+    totp_cipher: Register<&'static Aes256Gcm>,
+    attempt_store: Register<&'static Box<dyn AttemptStore>>,
+    register_validator: Register<&'static RegisterValidator>,
 }
 
 impl Resolver for UserResolver {
@@ -95,6 +190,14 @@ impl RoleGroup {
             RoleGroup::ADMIN => "admin",
         }
     }
+
+    pub fn from_name(name: &str) -> Option<RoleGroup> {
+        match name {
+            "user" => Some(RoleGroup::USER),
+            "admin" => Some(RoleGroup::ADMIN),
+            _ => None,
+        }
+    }
 }
 
 impl UserResolver {
@@ -106,6 +209,7 @@ impl UserResolver {
             .discover_to_channel(&service_key, tx)
             .await
             .expect("Cannot connect to etcd service.");
+        phone::init_default_region(&conf.phone_region);
         Self {
             conf,
             token_client: TokenServiceClient::new(channel),
@@ -113,7 +217,31 @@ impl UserResolver {
             pg_pool: Register::once_ref(|conf| {
                 Pool::new(ConnectionManager::new(&conf.pg_dsn)).unwrap()
             }),
-            redis: Register::once_ref(|conf| todo!()),
+            redis: Register::once_ref(|conf| {
+                r2d2::Pool::new(
+                    redis::Client::open(conf.redis.dsn.as_str()).expect("unexpect redis dsn"),
+                )
+                .expect("cannot create r2d2 pool")
+            }),
+            totp_cipher: Register::once_ref(|conf| {
+                let key = base64::prelude::BASE64_STANDARD
+                    .decode(&conf.totp_key)
+                    .expect("totp_key is not a valid base64 string");
+                Aes256Gcm::new_from_slice(&key).expect("totp_key must be 32 bytes")
+            }),
+            attempt_store: Register::once_ref(|conf| -> Box<dyn AttemptStore> {
+                match conf.login_guard.store {
+                    AttemptStoreKind::Redis => Box::new(RedisAttemptStore::new(
+                        r2d2::Pool::new(
+                            redis::Client::open(conf.redis.dsn.as_str())
+                                .expect("unexpect redis dsn"),
+                        )
+                        .expect("cannot create r2d2 pool"),
+                    )),
+                    AttemptStoreKind::Memory => Box::<MemoryAttemptStore>::default(),
+                }
+            }),
+            register_validator: Register::once_ref(|conf| RegisterValidator::new(&conf.validation)),
         }
     }
 
@@ -121,6 +249,22 @@ impl UserResolver {
         self.resolve(&self.hash_secret).deref()
     }
 
+    pub fn totp_cipher(&self) -> &'static Aes256Gcm {
+        self.resolve(&self.totp_cipher)
+    }
+
+    pub fn attempt_store(&self) -> &'static dyn AttemptStore {
+        self.resolve(&self.attempt_store).as_ref()
+    }
+
+    pub fn login_guard_conf(&self) -> &LoginGuardConfig {
+        &self.conf.login_guard
+    }
+
+    pub fn register_validator(&self) -> &'static RegisterValidator {
+        self.resolve(&self.register_validator)
+    }
+
     pub fn pg_conn(&self) -> PooledConnection<ConnectionManager<PgConnection>> {
         self.resolve(&self.pg_pool)
             .get()
@@ -149,8 +293,36 @@ impl UserResolver {
             .expect("Cannot register service into etcd");
     }
 
+    /// Anonymise the accounts whose grace period has ended in background,
+    /// purging is idempotent so it is fine to run it on every instance.
+    pub fn spawn_purge_job(&self) -> tokio::task::JoinHandle<()> {
+        let resolver = self.clone();
+        tokio::spawn(async move {
+            let conf = resolver.conf.deletion.clone();
+            let mut interval = tokio::time::interval(Duration::from_secs(conf.purge_interval));
+            loop {
+                interval.tick().await;
+                loop {
+                    match User::purge_deleted(
+                        conf.grace_period,
+                        conf.purge_batch,
+                        resolver.pg_conn().deref_mut(),
+                    ) {
+                        Ok(0) => break,
+                        Ok(purged) => tracing::info!("Purged {} deleted users", purged),
+                        Err(e) => {
+                            tracing::error!("Failed to purge deleted users, err: {:?}", e);
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
     pub async fn serve(&self) -> Result<(), tonic::transport::Error> {
         let token_srv = UserService(self.clone());
+        let admin_srv = UserAdminService(self.clone());
         let addr = self
             .conf
             .service_conf
@@ -177,11 +349,15 @@ impl UserResolver {
                 reporter
                     .set_serving::<UserServiceServer<UserService>>()
                     .await;
+                reporter
+                    .set_serving::<UserAdminServiceServer<UserAdminService>>()
+                    .await;
                 Some(svc)
             } else {
                 None
             })
-            .add_service(UserServiceServer::new(token_srv));
+            .add_service(UserServiceServer::new(token_srv))
+            .add_service(UserAdminServiceServer::new(admin_srv));
 
         serve
             .serve_with_shutdown(addr, async {
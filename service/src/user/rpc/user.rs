@@ -11,7 +11,21 @@ pub struct UserService(pub UserResolver);
 impl user_service_server::UserService for UserService {
     async fn login(&self, req: Request<LoginReq>) -> Result<Response<LoginRes>, Status> {
         let cmd = self.0.create_login();
-        let resp = cmd.execute(req.into_inner()).await?;
+        let remote_addr = req.remote_addr();
+        let user_agent = req
+            .metadata()
+            .get("user-agent")
+            .and_then(|value| value.to_str().ok())
+            .map(ToString::to_string);
+        let mut req = req.into_inner();
+        // called directly rather than through a gateway
+        if req.client_ip.is_none() {
+            req.client_ip = remote_addr.map(|addr| addr.ip().to_string());
+        }
+        if req.user_agent.is_none() {
+            req.user_agent = user_agent;
+        }
+        let resp = cmd.execute(req).await?;
         Ok(Response::new(resp))
     }
 
@@ -26,4 +40,151 @@ impl user_service_server::UserService for UserService {
         let resp = cmd.execute(req.into_inner()).await?;
         Ok(Response::new(resp))
     }
+
+    async fn enroll_totp(
+        &self,
+        req: Request<EnrollTotpReq>,
+    ) -> Result<Response<EnrollTotpRes>, Status> {
+        let cmd = self.0.create_enroll_totp();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn confirm_totp(
+        &self,
+        req: Request<ConfirmTotpReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_confirm_totp();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn verify_totp(&self, req: Request<VerifyTotpReq>) -> Result<Response<LoginRes>, Status> {
+        let cmd = self.0.create_verify_totp();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn unlock(&self, req: Request<UnlockReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_unlock();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn get_user(&self, req: Request<GetUserReq>) -> Result<Response<GetUserRes>, Status> {
+        let query = self.0.create_get_user();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn get_me(&self, req: Request<GetMeReq>) -> Result<Response<GetMeRes>, Status> {
+        let query = self.0.create_get_me();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn update_profile(
+        &self,
+        req: Request<UpdateProfileReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_update_profile();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn delete_account(
+        &self,
+        req: Request<DeleteAccountReq>,
+    ) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_delete_account();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn export_my_data(
+        &self,
+        req: Request<ExportMyDataReq>,
+    ) -> Result<Response<ExportMyDataRes>, Status> {
+        let query = self.0.create_export_my_data();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_login_history(
+        &self,
+        req: Request<ListLoginHistoryReq>,
+    ) -> Result<Response<ListLoginHistoryRes>, Status> {
+        let query = self.0.create_list_login_history();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn follow(&self, req: Request<FollowReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_follow();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn unfollow(&self, req: Request<UnfollowReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_unfollow();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_followers(
+        &self,
+        req: Request<ListFollowsReq>,
+    ) -> Result<Response<ListFollowsRes>, Status> {
+        let query = self.0.create_list_followers();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn list_following(
+        &self,
+        req: Request<ListFollowsReq>,
+    ) -> Result<Response<ListFollowsRes>, Status> {
+        let query = self.0.create_list_following();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn is_following(
+        &self,
+        req: Request<IsFollowingReq>,
+    ) -> Result<Response<IsFollowingRes>, Status> {
+        let query = self.0.create_is_following();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn count_follows(
+        &self,
+        req: Request<CountFollowsReq>,
+    ) -> Result<Response<CountFollowsRes>, Status> {
+        let query = self.0.create_count_follows();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn block(&self, req: Request<BlockReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_block();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn unblock(&self, req: Request<UnblockReq>) -> Result<Response<EmptyRes>, Status> {
+        let cmd = self.0.create_unblock();
+        let resp = cmd.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
+
+    async fn search_users(
+        &self,
+        req: Request<SearchUsersReq>,
+    ) -> Result<Response<SearchUsersRes>, Status> {
+        let query = self.0.create_search_users();
+        let resp = query.execute(req.into_inner()).await?;
+        Ok(Response::new(resp))
+    }
 }